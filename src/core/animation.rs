@@ -0,0 +1,269 @@
+use std::time::Duration;
+#[cfg(feature = "gif")]
+use std::{fs, path::Path};
+
+use crate::core::Sprite;
+#[cfg(feature = "gif")]
+use crate::core::ImageError;
+
+#[derive(Debug, PartialEq, Copy, Clone, Default)]
+/// Controls what happens when an animation reaches its last frame
+pub enum LoopMode {
+    /// Starts over at the first frame
+    #[default]
+    Loop,
+
+    /// Stops at the last frame
+    Once,
+
+    /// Plays the frames backwards and then forwards again
+    PingPong,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+/// Sequence of sprites that are shown one after the other
+///
+/// Advance the animation with [`update`] and draw it with [`draw_animation`]
+///
+/// [`update`]: #method.update
+/// [`draw_animation`]: struct.RainCore.html#method.draw_animation
+///
+/// ### Example
+#[cfg_attr(feature = "png", doc = "```no_run")]
+#[cfg_attr(not(feature = "png"), doc = "```ignore")]
+/// # use rain2d::core::*;
+/// # use std::time::Duration;
+/// # let mut core = RainCore::init("example app", 640, 360, true);
+/// # let dt = Duration::from_millis(16);
+/// let sheet = Sprite::from_png("assets/walk.png").unwrap();
+/// let mut walk = Animation::from_sheet(&sheet, 16, 16, Duration::from_millis(100));
+///
+/// // every frame
+/// walk.update(dt);
+/// core.draw_animation(100, 100, &walk);
+/// ```
+pub struct Animation {
+    frames: Vec<Sprite>,
+    durations: Vec<Duration>,
+    loop_mode: LoopMode,
+    frame: usize,
+    elapsed: Duration,
+    backwards: bool,
+    finished: bool,
+}
+
+impl Animation {
+    /// Creates an animation that shows every frame for `frame_duration`
+    pub fn new(frames: Vec<Sprite>, frame_duration: Duration) -> Self {
+        let durations = vec![frame_duration; frames.len()];
+        Animation {
+            frames,
+            durations,
+            loop_mode: LoopMode::default(),
+            frame: 0,
+            elapsed: Duration::ZERO,
+            backwards: false,
+            finished: false,
+        }
+    }
+
+    /// Creates an animation from a sprite sheet with frames of `frame_width` by `frame_height`
+    /// pixels, the frames are read row by row and partial frames at the edges are ignored
+    pub fn from_sheet(sheet: &Sprite, frame_width: usize, frame_height: usize, frame_duration: Duration) -> Self {
+        let mut frames = Vec::new();
+        if frame_width > 0 && frame_height > 0 {
+            for row in 0..sheet.height / frame_height {
+                for col in 0..sheet.width / frame_width {
+                    frames.push(sheet.crop(col * frame_width, row * frame_height, frame_width, frame_height));
+                }
+            }
+        }
+        Self::new(frames, frame_duration)
+    }
+
+    /// Loads an animated gif, every frame is shown for as long as the file specifies
+    ///
+    /// Animations loop if the file says so and stop at the last frame otherwise
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// let intro = Animation::from_gif("assets/intro.gif").expect("failed to load intro");
+    /// ```
+    #[cfg(feature = "gif")]
+    pub fn from_gif<P: AsRef<Path>>(path: P) -> Result<Self, ImageError> {
+        Self::from_gif_bytes(&fs::read(path)?)
+    }
+
+    /// Decodes an animated gif that is already in memory
+    #[cfg(feature = "gif")]
+    pub fn from_gif_bytes(bytes: &[u8]) -> Result<Self, ImageError> {
+        crate::core::gif::decode(bytes)
+    }
+
+    /// Sets the loop mode, animations loop by default
+    pub fn set_loop_mode(&mut self, mode: LoopMode) {
+        self.loop_mode = mode;
+    }
+
+    /// Changes how long the frame at `index` is shown for, does nothing if there is no such frame
+    pub fn set_frame_duration(&mut self, index: usize, duration: Duration) {
+        if let Some(d) = self.durations.get_mut(index) {
+            *d = duration;
+        }
+    }
+
+    /// Advances the animation by `dt`, skips frames if `dt` is longer than a frame
+    pub fn update(&mut self, dt: Duration) {
+        if self.frames.is_empty() { return; }
+        self.elapsed += dt;
+
+        // frames without a duration are skipped, but never more than once per update
+        let mut skipped = 0;
+        while !self.finished {
+            let duration = self.durations[self.frame];
+            if duration.is_zero() {
+                skipped += 1;
+                if skipped > self.frames.len() { break; }
+            } else if self.elapsed < duration {
+                break;
+            } else {
+                self.elapsed -= duration;
+            }
+            self.advance();
+        }
+    }
+
+    /// Goes back to the first frame
+    pub fn reset(&mut self) {
+        self.frame = 0;
+        self.elapsed = Duration::ZERO;
+        self.backwards = false;
+        self.finished = false;
+    }
+
+    /// Gets the sprite that should be shown, returns `None` if the animation has no frames
+    pub fn current_frame(&self) -> Option<&Sprite> {
+        self.frames.get(self.frame)
+    }
+
+    /// Gets the index of the frame that should be shown
+    pub fn frame_index(&self) -> usize {
+        self.frame
+    }
+
+    /// Gets the number of frames
+    pub fn frame_count(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Checks if an animation that doesn't loop has reached its last frame
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    fn advance(&mut self) {
+        let last = self.frames.len() - 1;
+        match self.loop_mode {
+            LoopMode::Loop => self.frame = if self.frame < last { self.frame + 1 } else { 0 },
+            LoopMode::Once if self.frame < last => self.frame += 1,
+            LoopMode::Once => self.finished = true,
+            LoopMode::PingPong if last == 0 => (),
+            LoopMode::PingPong => {
+                if self.backwards && self.frame == 0 || !self.backwards && self.frame == last {
+                    self.backwards = !self.backwards;
+                }
+                if self.backwards { self.frame -= 1; } else { self.frame += 1; }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn frames(count: usize) -> Vec<Sprite> {
+        (0..count).map(|i| Sprite::new(i + 1, 1)).collect()
+    }
+
+    fn ms(ms: u64) -> Duration {
+        Duration::from_millis(ms)
+    }
+
+    #[test]
+    fn test_loop() {
+        let mut animation = Animation::new(frames(3), ms(100));
+        assert_eq!(animation.frame_index(), 0);
+
+        animation.update(ms(50));
+        assert_eq!(animation.frame_index(), 0);
+        animation.update(ms(50));
+        assert_eq!(animation.frame_index(), 1);
+
+        // long updates skip frames
+        animation.update(ms(250));
+        assert_eq!(animation.frame_index(), 0);
+        assert_eq!(animation.current_frame().unwrap().width, 1);
+    }
+
+    #[test]
+    fn test_once() {
+        let mut animation = Animation::new(frames(3), ms(100));
+        animation.set_loop_mode(LoopMode::Once);
+
+        animation.update(ms(250));
+        assert!(!animation.is_finished());
+        animation.update(ms(1000));
+        assert_eq!(animation.frame_index(), 2);
+        assert!(animation.is_finished());
+
+        animation.reset();
+        assert_eq!(animation.frame_index(), 0);
+        assert!(!animation.is_finished());
+    }
+
+    #[test]
+    fn test_ping_pong() {
+        let mut animation = Animation::new(frames(3), ms(10));
+        animation.set_loop_mode(LoopMode::PingPong);
+
+        let mut order = Vec::new();
+        for _ in 0..6 {
+            order.push(animation.frame_index());
+            animation.update(ms(10));
+        }
+        assert_eq!(order, vec![0, 1, 2, 1, 0, 1]);
+    }
+
+    #[test]
+    fn test_frame_durations() {
+        let mut animation = Animation::new(frames(3), ms(100));
+        animation.set_frame_duration(1, ms(0));
+        animation.set_frame_duration(2, ms(300));
+
+        animation.update(ms(100));
+        assert_eq!(animation.frame_index(), 2);
+        animation.update(ms(299));
+        assert_eq!(animation.frame_index(), 2);
+
+        let mut empty = Animation::new(Vec::new(), ms(100));
+        empty.update(ms(100));
+        assert_eq!(empty.current_frame(), None);
+
+        // only frames without a duration
+        let mut instant = Animation::new(frames(2), ms(0));
+        instant.update(ms(100));
+    }
+
+    #[test]
+    fn test_from_sheet() {
+        let mut sheet = Sprite::new(5, 4);
+        sheet.data[2] = 1;
+        sheet.data[2 * 5] = 2;
+        let animation = Animation::from_sheet(&sheet, 2, 2, ms(100));
+        assert_eq!(animation.frame_count(), 4);
+        assert_eq!(animation.frames[1].data, vec![1, 0, 0, 0]);
+        assert_eq!(animation.frames[2].data, vec![2, 0, 0, 0]);
+    }
+}
@@ -0,0 +1,210 @@
+// fonts where every glyph is a sprite, loaded from BMFont text descriptors or cut from a grid
+
+use std::collections::HashMap;
+
+use crate::core::{FontError, Sprite};
+
+#[derive(Debug, Clone)]
+pub struct BitmapFont {
+    // size the font was exported at and distance between two lines, in pixels of the pages
+    pub size: f32,
+    pub line_height: f32,
+    pub glyphs: HashMap<char, Glyph>,
+    pub kerning: HashMap<(char, char), f32>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Glyph {
+    pub sprite: Sprite,
+    // position of the sprite relative to the pen at the top of the line
+    pub offset: (f32, f32),
+    pub advance: f32,
+}
+
+impl BitmapFont {
+    // parses a BMFont text descriptor, pages contains the page images ordered by id
+    pub fn parse(descriptor: &str, pages: &[Sprite]) -> Result<Self, FontError> {
+        let mut font = BitmapFont { size: 0.0, line_height: 0.0, glyphs: HashMap::new(), kerning: HashMap::new() };
+        for line in descriptor.lines() {
+            let (tag, attributes) = tag(line);
+            let get = |key: &str| -> Result<i32, FontError> {
+                attributes.iter()
+                    .find(|(k, _)| *k == key)
+                    .and_then(|(_, v)| v.parse().ok())
+                    .ok_or(FontError::Invalid("missing or invalid attribute"))
+            };
+
+            match tag {
+                "info" => font.size = get("size")?.abs() as f32,
+                "common" => font.line_height = get("lineHeight")? as f32,
+                "char" => {
+                    let c = match char::from_u32(get("id")? as u32) {
+                        Some(c) => c,
+                        None => continue,
+                    };
+                    let page = pages.get(get("page")? as usize).ok_or(FontError::Invalid("missing page"))?;
+                    let (x, y, width, height) = (get("x")?, get("y")?, get("width")?, get("height")?);
+                    if x < 0 || y < 0 || width < 0 || height < 0
+                        || (x + width) as usize > page.width || (y + height) as usize > page.height {
+                        return Err(FontError::Invalid("glyph outside of its page"));
+                    }
+
+                    font.glyphs.insert(c, Glyph {
+                        sprite: page.crop(x as usize, y as usize, width as usize, height as usize),
+                        offset: (get("xoffset")? as f32, get("yoffset")? as f32),
+                        advance: get("xadvance")? as f32,
+                    });
+                }
+                "kerning" => {
+                    let pair = (char::from_u32(get("first")? as u32), char::from_u32(get("second")? as u32));
+                    if let (Some(first), Some(second)) = pair {
+                        font.kerning.insert((first, second), get("amount")? as f32);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        if font.line_height <= 0.0 {
+            return Err(FontError::Invalid("missing line height"));
+        }
+        if font.size == 0.0 {
+            font.size = font.line_height;
+        }
+        Ok(font)
+    }
+
+    // cuts a sprite into a grid of glyphs of the same size, filled left to right and top to bottom
+    // starting at first
+    pub fn grid(sprite: &Sprite, glyph_width: usize, glyph_height: usize, first: char) -> Self {
+        let (columns, rows) = (sprite.width / glyph_width, sprite.height / glyph_height);
+        let glyphs = (0..columns * rows)
+            .filter_map(|i| {
+                let c = char::from_u32(first as u32 + i as u32)?;
+                let sprite = sprite.crop(i % columns * glyph_width, i / columns * glyph_height, glyph_width, glyph_height);
+                Some((c, Glyph { sprite, offset: (0.0, 0.0), advance: glyph_width as f32 }))
+            })
+            .collect();
+
+        let size = glyph_height as f32;
+        BitmapFont { size, line_height: size, glyphs, kerning: HashMap::new() }
+    }
+
+    // gets the glyph for the character, missing characters are shown as '?' if the font has it
+    pub fn glyph(&self, c: char) -> Option<&Glyph> {
+        self.glyphs.get(&c).or_else(|| self.glyphs.get(&'?'))
+    }
+}
+
+// gets the file names of the pages in a BMFont text descriptor ordered by id
+pub fn page_files(descriptor: &str) -> Result<Vec<String>, FontError> {
+    let mut pages = Vec::new();
+    for line in descriptor.lines() {
+        let (tag, attributes) = tag(line);
+        if tag != "page" { continue; }
+        let value = |key: &str| attributes.iter().find(|(k, _)| *k == key).map(|&(_, v)| v);
+        let id: usize = value("id").and_then(|id| id.parse().ok()).ok_or(FontError::Invalid("invalid page id"))?;
+        let file = value("file").ok_or(FontError::Invalid("missing page file"))?;
+        if pages.len() <= id {
+            pages.resize(id + 1, String::new());
+        }
+        pages[id] = file.to_string();
+    }
+    if pages.iter().any(|file| file.is_empty()) {
+        return Err(FontError::Invalid("missing page"));
+    }
+    Ok(pages)
+}
+
+// splits a line into its tag and key=value attributes, values can be quoted to contain spaces
+fn tag(line: &str) -> (&str, Vec<(&str, &str)>) {
+    let line = line.trim();
+    let (tag, mut rest) = line.split_at(line.find(' ').unwrap_or(line.len()));
+    let mut attributes = Vec::new();
+    loop {
+        rest = rest.trim_start();
+        let eq = match rest.find('=') {
+            Some(eq) => eq,
+            None => break,
+        };
+        let key = rest[..eq].trim();
+        rest = &rest[eq + 1..];
+        let (value, next) = if let Some(quoted) = rest.strip_prefix('"') {
+            let end = quoted.find('"').unwrap_or(quoted.len());
+            (&quoted[..end], quoted.get(end + 1..).unwrap_or(""))
+        } else {
+            let end = rest.find(' ').unwrap_or(rest.len());
+            (&rest[..end], &rest[end..])
+        };
+        attributes.push((key, value));
+        rest = next;
+    }
+    (tag, attributes)
+}
+
+#[cfg(test)]
+pub fn test_descriptor() -> &'static str {
+    // two glyphs on a 8x4 page and a kerning pair that moves B closer to A
+    "info face=\"Test Font\" size=-4 bold=0\n\
+     common lineHeight=5 base=4 scaleW=8 scaleH=4 pages=1\n\
+     page id=0 file=\"test font.png\"\n\
+     chars count=2\n\
+     char id=65 x=0 y=0 width=4 height=4 xoffset=0 yoffset=1 xadvance=5 page=0 chnl=15\n\
+     char id=66 x=4 y=0 width=2 height=4 xoffset=1 yoffset=0 xadvance=3 page=0 chnl=15\n\
+     kernings count=1\n\
+     kerning first=65 second=66 amount=-2\n"
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::core::color::WHITE;
+
+    #[test]
+    fn test_tag() {
+        let (tag, attributes) = tag("info face=\"Times New Roman\" size=32  bold=0");
+        assert_eq!(tag, "info");
+        assert_eq!(attributes, vec![("face", "Times New Roman"), ("size", "32"), ("bold", "0")]);
+        assert_eq!(super::tag("chars"), ("chars", vec![]));
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(page_files(test_descriptor()).unwrap(), vec!["test font.png".to_string()]);
+
+        let page = Sprite::from_colors(8, 4, &[WHITE; 32]).unwrap();
+        let font = BitmapFont::parse(test_descriptor(), &[page]).unwrap();
+        assert_eq!((font.size, font.line_height), (4.0, 5.0));
+        assert_eq!(font.glyphs.len(), 2);
+
+        let b = font.glyph('B').unwrap();
+        assert_eq!((b.sprite.width, b.sprite.height), (2, 4));
+        assert_eq!((b.offset, b.advance), ((1.0, 0.0), 3.0));
+        assert_eq!(font.kerning.get(&('A', 'B')), Some(&-2.0));
+        assert!(font.glyph('C').is_none());
+    }
+
+    #[test]
+    fn test_grid() {
+        // 2x2 grid of 2x3 glyphs, the bottom right glyph is the only visible pixel
+        let mut sprite = Sprite::new(5, 6);
+        sprite.set_pixel(3, 5, WHITE);
+        let font = BitmapFont::grid(&sprite, 2, 3, 'a');
+        assert_eq!((font.size, font.line_height), (3.0, 3.0));
+        assert_eq!(font.glyphs.len(), 4);
+
+        let d = font.glyph('d').unwrap();
+        assert_eq!((d.sprite.width, d.sprite.height, d.advance), (2, 3, 2.0));
+        assert_eq!(d.sprite.get_pixel(1, 2), Some(WHITE));
+        assert!(font.glyph('e').is_none());
+    }
+
+    #[test]
+    fn test_invalid() {
+        let small = Sprite::new(4, 4);
+        assert!(BitmapFont::parse(test_descriptor(), &[small]).is_err());
+        assert!(BitmapFont::parse(test_descriptor(), &[]).is_err());
+        assert!(BitmapFont::parse("info size=4", &[]).is_err());
+        assert!(page_files("page id=1 file=\"a.png\"").is_err());
+    }
+}
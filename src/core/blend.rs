@@ -0,0 +1,95 @@
+use crate::core::Color;
+
+#[derive(Debug, PartialEq, Copy, Clone, Default)]
+/// Controls how drawn colors are combined with the existing contents of the screen
+pub enum BlendMode {
+    /// Overwrites the existing color, alpha is ignored
+    Replace,
+
+    /// Source-over alpha blending
+    #[default]
+    Alpha,
+
+    /// Adds the color to the existing color, useful for glow and particle effects
+    Additive,
+
+    /// Multiplies the existing color with the color, darkens the result
+    Multiply,
+
+    /// Inverse of multiply, lightens the result
+    Screen,
+}
+
+impl BlendMode {
+    /// Blends `src` over `dst`, `coverage` scales the alpha of `src`
+    ///
+    /// ### Example
+    /// ```
+    ///# use rain2d::core::*;
+    /// let color = BlendMode::Additive.blend(Color::rgb(100, 100, 100), Color::rgb(200, 50, 0), 1.0);
+    /// assert_eq!(color, Color::rgb(255, 150, 100));
+    /// ```
+    pub fn blend(self, dst: Color, src: Color, coverage: f32) -> Color {
+        if self == BlendMode::Replace {
+            return src;
+        }
+
+        let a = src.a as f32 / 255.0 * coverage.clamp(0.0, 1.0);
+        let channel = |s: u8, d: u8| {
+            let (s, d) = (s as f32, d as f32);
+            let c = match self {
+                BlendMode::Additive => d + s * a,
+                BlendMode::Multiply => d + (d * s / 255.0 - d) * a,
+                BlendMode::Screen => d + (255.0 - (255.0 - s) * (255.0 - d) / 255.0 - d) * a,
+                _ => d + (s - d) * a,
+            };
+            f32::round(c.min(255.0)) as u8
+        };
+
+        Color {
+            r: channel(src.r, dst.r),
+            g: channel(src.g, dst.g),
+            b: channel(src.b, dst.b),
+            a: f32::round(a * 255.0 + dst.a as f32 * (1.0 - a)) as u8,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::core::color::{WHITE, BLACK};
+
+    #[test]
+    fn test_replace() {
+        let src = Color::rgba(10, 20, 30, 40);
+        assert_eq!(BlendMode::Replace.blend(WHITE, src, 0.5), src);
+    }
+
+    #[test]
+    fn test_alpha() {
+        let color = BlendMode::Alpha.blend(BLACK, Color::rgba(255, 0, 0, 128), 1.0);
+        assert_eq!(color, Color::rgb(128, 0, 0));
+
+        let color = BlendMode::Alpha.blend(BLACK, WHITE, 0.0);
+        assert_eq!(color, BLACK);
+    }
+
+    #[test]
+    fn test_additive() {
+        let color = BlendMode::Additive.blend(Color::rgb(200, 100, 0), Color::rgb(100, 100, 100), 1.0);
+        assert_eq!(color, Color::rgb(255, 200, 100));
+    }
+
+    #[test]
+    fn test_multiply() {
+        let color = BlendMode::Multiply.blend(Color::rgb(200, 100, 255), Color::rgb(255, 0, 128), 1.0);
+        assert_eq!(color, Color::rgb(200, 0, 128));
+    }
+
+    #[test]
+    fn test_screen() {
+        let color = BlendMode::Screen.blend(Color::rgb(0, 255, 128), Color::rgb(128, 0, 128), 1.0);
+        assert_eq!(color, Color::rgb(128, 255, 192));
+    }
+}
@@ -0,0 +1,161 @@
+// bmp decoder for uncompressed 24 and 32 bit images
+
+use crate::core::{Color, ImageError, Sprite};
+
+const INVALID: ImageError = ImageError::Invalid("corrupt bmp file");
+
+const BI_RGB: u32 = 0;
+const BI_BITFIELDS: u32 = 3;
+
+pub fn decode(bytes: &[u8]) -> Result<Sprite, ImageError> {
+    if bytes.len() < 54 || &bytes[..2] != b"BM" {
+        return Err(ImageError::Invalid("not a bmp file"));
+    }
+
+    let offset = u32_at(bytes, 10)? as usize;
+    let header_size = u32_at(bytes, 14)? as usize;
+    if header_size < 40 {
+        return Err(ImageError::Unsupported("bmp core header"));
+    }
+    let width = u32_at(bytes, 18)? as i32;
+    let height = u32_at(bytes, 22)? as i32;
+    let bit_count = u16_at(bytes, 28)?;
+    let compression = u32_at(bytes, 30)?;
+
+    if width < 0 { return Err(INVALID); }
+    if bit_count != 24 && bit_count != 32 {
+        return Err(ImageError::Unsupported("bmp bit depth other than 24 or 32"));
+    }
+
+    // channel masks in the order red, green, blue, alpha
+    let masks = match compression {
+        BI_RGB if bit_count == 32 => [0x00FF0000, 0x0000FF00, 0x000000FF, 0xFF000000],
+        BI_RGB => [0xFF0000, 0x00FF00, 0x0000FF, 0],
+        BI_BITFIELDS if bit_count == 32 => {
+            // the masks follow the header, or are part of it for newer headers
+            let alpha = if header_size >= 56 { u32_at(bytes, 66)? } else { 0 };
+            [u32_at(bytes, 54)?, u32_at(bytes, 58)?, u32_at(bytes, 62)?, alpha]
+        }
+        _ => return Err(ImageError::Unsupported("compressed bmp")),
+    };
+
+    // rows are stored bottom to top unless the height is negative
+    let (width, bottom_up) = (width as usize, height > 0);
+    let height = height.unsigned_abs() as usize;
+    let bytes_per_pixel = bit_count as usize / 8;
+    let stride = (width * bytes_per_pixel).div_ceil(4) * 4;
+    if bytes.len() < offset + stride * height {
+        return Err(INVALID);
+    }
+
+    let mut sprite = Sprite::new(width, height);
+    let mut has_alpha = false;
+    for row in 0..height {
+        let y = if bottom_up { height - 1 - row } else { row };
+        let start = offset + row * stride;
+        for (x, p) in bytes[start..start + width * bytes_per_pixel].chunks_exact(bytes_per_pixel).enumerate() {
+            let value = p.iter().rev().fold(0, |v, &b| v << 8 | b as u32);
+            let [r, g, b, a] = masks.map(|mask| channel(value, mask));
+            let a = if masks[3] == 0 { 255 } else { a };
+            has_alpha |= a != 0;
+            sprite.set_pixel(x as i32, y as i32, Color::rgba(r, g, b, a));
+        }
+    }
+
+    // a lot of programs write 32 bit images with the alpha channel left at zero
+    if !has_alpha {
+        for p in sprite.data.iter_mut() {
+            *p |= 0xFF000000;
+        }
+    }
+
+    Ok(sprite)
+}
+
+// extracts the channel selected by the mask and converts it to 8 bits
+fn channel(value: u32, mask: u32) -> u8 {
+    if mask == 0 { return 0; }
+    let bits = mask.count_ones();
+    let v = (value & mask) >> mask.trailing_zeros();
+    if bits >= 8 { (v >> (bits - 8)) as u8 } else { (v * 255 / ((1 << bits) - 1)) as u8 }
+}
+
+fn u16_at(bytes: &[u8], i: usize) -> Result<u16, ImageError> {
+    let b = bytes.get(i..i + 2).ok_or(INVALID)?;
+    Ok(u16::from_le_bytes([b[0], b[1]]))
+}
+
+fn u32_at(bytes: &[u8], i: usize) -> Result<u32, ImageError> {
+    let b = bytes.get(i..i + 4).ok_or(INVALID)?;
+    Ok(u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::core::color::{RED, GREEN, BLUE, WHITE};
+
+    // builds a bmp with a 40 byte info header
+    fn encode(width: i32, height: i32, bit_count: u16, compression: u32, masks: &[u32], pixels: &[u8]) -> Vec<u8> {
+        let offset = 54 + masks.len() as u32 * 4;
+        let mut out = b"BM".to_vec();
+        out.extend_from_slice(&(offset + pixels.len() as u32).to_le_bytes());
+        out.extend_from_slice(&[0; 4]);
+        out.extend_from_slice(&offset.to_le_bytes());
+        out.extend_from_slice(&40u32.to_le_bytes());
+        out.extend_from_slice(&width.to_le_bytes());
+        out.extend_from_slice(&height.to_le_bytes());
+        out.extend_from_slice(&1u16.to_le_bytes());
+        out.extend_from_slice(&bit_count.to_le_bytes());
+        out.extend_from_slice(&compression.to_le_bytes());
+        out.extend_from_slice(&[0; 20]);
+        for mask in masks {
+            out.extend_from_slice(&mask.to_le_bytes());
+        }
+        out.extend_from_slice(pixels);
+        out
+    }
+
+    #[test]
+    fn test_24_bit() {
+        // bottom up, rows padded to 4 bytes
+        let pixels = [0, 0, 255, 0, 255, 0, 0, 0, 255, 0, 0, 255, 255, 255, 0, 0];
+        let sprite = decode(&encode(2, 2, 24, BI_RGB, &[], &pixels)).unwrap();
+        assert_eq!((sprite.width, sprite.height), (2, 2));
+        assert_eq!(sprite.get_pixel(0, 1), Some(RED));
+        assert_eq!(sprite.get_pixel(1, 1), Some(GREEN));
+        assert_eq!(sprite.get_pixel(0, 0), Some(BLUE));
+        assert_eq!(sprite.get_pixel(1, 0), Some(WHITE));
+    }
+
+    #[test]
+    fn test_32_bit() {
+        // top down with an alpha channel
+        let pixels = [0, 0, 255, 255, 255, 0, 0, 128];
+        let sprite = decode(&encode(2, -1, 32, BI_RGB, &[], &pixels)).unwrap();
+        assert_eq!(sprite.get_pixel(0, 0), Some(RED));
+        assert_eq!(sprite.get_pixel(1, 0), Some(Color::rgba(0, 0, 255, 128)));
+
+        // alpha channel left at zero
+        let pixels = [0, 0, 255, 0];
+        let sprite = decode(&encode(1, 1, 32, BI_RGB, &[], &pixels)).unwrap();
+        assert_eq!(sprite.get_pixel(0, 0), Some(RED));
+    }
+
+    #[test]
+    fn test_bitfields() {
+        // rgba byte order
+        let masks = [0x000000FF, 0x0000FF00, 0x00FF0000];
+        let pixels = [255, 0, 0, 0, 0, 0, 255, 0];
+        let sprite = decode(&encode(2, 1, 32, BI_BITFIELDS, &masks, &pixels)).unwrap();
+        assert_eq!(sprite.get_pixel(0, 0), Some(RED));
+        assert_eq!(sprite.get_pixel(1, 0), Some(BLUE));
+    }
+
+    #[test]
+    fn test_invalid() {
+        assert!(decode(b"not a bmp").is_err());
+        assert!(decode(&encode(2, 2, 8, BI_RGB, &[], &[0; 8])).is_err());
+        assert!(decode(&encode(4, 4, 24, BI_RGB, &[], &[0; 8])).is_err());
+    }
+}
@@ -0,0 +1,71 @@
+use crate::core::Transform;
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+/// Camera that maps world coordinates to the screen, set it with [`set_camera`]
+///
+/// The camera position ends up at the center of the screen
+///
+/// [`set_camera`]: struct.RainCore.html#method.set_camera
+///
+/// ### Example
+/// ```
+///# use rain2d::core::Camera2D;
+/// let camera = Camera2D { position: (100.0, 50.0), zoom: 2.0, ..Camera2D::default() };
+/// let view = camera.view(640.0, 360.0);
+/// assert_eq!(view.apply(100.0, 50.0), (320.0, 180.0));
+/// assert_eq!(view.apply(110.0, 50.0), (340.0, 180.0));
+/// ```
+pub struct Camera2D {
+    /// World position shown at the center of the screen
+    pub position: (f32, f32),
+
+    /// Magnification, 2.0 shows everything twice as large
+    pub zoom: f32,
+
+    /// Rotation of the camera in radians, increasing clockwise, the world turns the other way
+    pub rotation: f32,
+}
+
+impl Default for Camera2D {
+    fn default() -> Self {
+        Camera2D { position: (0.0, 0.0), zoom: 1.0, rotation: 0.0 }
+    }
+}
+
+impl Camera2D {
+    /// Creates a camera looking at `(x, y)` without zoom or rotation
+    pub fn new(x: f32, y: f32) -> Self {
+        Camera2D { position: (x, y), ..Camera2D::default() }
+    }
+
+    /// Gets the transform from world coordinates to the coordinates of a screen with the
+    /// specified size
+    pub fn view(&self, width: f32, height: f32) -> Transform {
+        Transform::translation(-self.position.0, -self.position.1)
+            .then(Transform::rotation(-self.rotation))
+            .then(Transform::scaling(self.zoom, self.zoom))
+            .then(Transform::translation(width / 2.0, height / 2.0))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::f32::consts::FRAC_PI_2;
+
+    fn close(a: (f32, f32), b: (f32, f32)) -> bool {
+        (a.0 - b.0).abs() < 1e-4 && (a.1 - b.1).abs() < 1e-4
+    }
+
+    #[test]
+    fn test_view() {
+        let view = Camera2D::default().view(100.0, 50.0);
+        assert_eq!(view.apply(0.0, 0.0), (50.0, 25.0));
+
+        // turning the camera clockwise moves things on its right to the top of the screen
+        let camera = Camera2D { position: (10.0, 10.0), zoom: 0.5, rotation: FRAC_PI_2 };
+        let view = camera.view(100.0, 50.0);
+        assert!(close(view.apply(30.0, 10.0), (50.0, 15.0)));
+        assert!(close(view.inverse().unwrap().apply(50.0, 15.0), (30.0, 10.0)));
+    }
+}
@@ -0,0 +1,29 @@
+// system clipboard through arboard, minifb has no clipboard support of its own
+
+use std::io;
+
+// the clipboard is opened the first time it's used and kept open, on linux the text that was
+// set is only available while it's open
+#[derive(Default)]
+pub struct Clipboard {
+    clipboard: Option<arboard::Clipboard>,
+}
+
+impl Clipboard {
+    fn open(&mut self) -> io::Result<&mut arboard::Clipboard> {
+        if self.clipboard.is_none() {
+            self.clipboard = Some(arboard::Clipboard::new().map_err(io::Error::other)?);
+        }
+        Ok(self.clipboard.as_mut().expect("the clipboard was just opened"))
+    }
+
+    // gets the text on the clipboard, None if it's empty or can't be read
+    pub fn get(&mut self) -> Option<String> {
+        self.open().ok()?.get_text().ok().filter(|text| !text.is_empty())
+    }
+
+    // replaces the text on the clipboard
+    pub fn set(&mut self, text: &str) -> io::Result<()> {
+        self.open()?.set_text(text).map_err(io::Error::other)
+    }
+}
@@ -63,11 +63,61 @@ impl Color {
     pub fn rgba(r: u8, g: u8, b: u8, a: u8) -> Self {
         Color { r, g, b, a }
     }
+
+    /// Linearly interpolates between two colors, `t` is clamped between 0 and 1
+    ///
+    /// ### Example
+    /// ```
+    ///# use rain2d::core::*;
+    /// let color = BLACK.lerp(WHITE, 0.5);
+    /// assert_eq!(color, Color::rgb(128, 128, 128));
+    /// ```
+    pub fn lerp(self, other: Color, t: f32) -> Self {
+        let t = t.clamp(0.0, 1.0);
+        let mix = |a: u8, b: u8| f32::round(a as f32 + (b as f32 - a as f32) * t) as u8;
+        Color {
+            r: mix(self.r, other.r),
+            g: mix(self.g, other.g),
+            b: mix(self.b, other.b),
+            a: mix(self.a, other.a),
+        }
+    }
+
+    /// Multiplies every component with the matching component of the other color,
+    /// multiplying with [`WHITE`] doesn't change the color
+    ///
+    /// [`WHITE`]: constant.WHITE.html
+    ///
+    /// ### Example
+    /// ```
+    ///# use rain2d::core::*;
+    /// let color = Color::rgb(200, 100, 50).modulate(Color::rgba(255, 128, 0, 128));
+    /// assert_eq!(color, Color::rgba(200, 50, 0, 128));
+    /// ```
+    pub fn modulate(self, other: Color) -> Self {
+        let mul = |a: u8, b: u8| ((a as u32 * b as u32 + 127) / 255) as u8;
+        Color {
+            r: mul(self.r, other.r),
+            g: mul(self.g, other.g),
+            b: mul(self.b, other.b),
+            a: mul(self.a, other.a),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+/// Direction of a linear gradient
+pub enum GradientDirection {
+    /// From left to right
+    Horizontal,
+
+    /// From top to bottom
+    Vertical,
 }
 
-impl Into<u32> for Color {
-    fn into(self) -> u32 {
-        let (a, r, g, b) = (self.a as u32, self.r as u32, self.g as u32, self.b as u32);
+impl From<Color> for u32 {
+    fn from(color: Color) -> Self {
+        let (a, r, g, b) = (color.a as u32, color.r as u32, color.g as u32, color.b as u32);
         (a << 24) | (r << 16) | (g << 8) | b
     }
 }
@@ -99,6 +149,25 @@ mod test {
         assert_eq!(color, Color { r: 128, g: 255, b: 50, a: 150 });
     }
 
+    #[test]
+    fn test_color_lerp() {
+        let from = Color::rgba(0, 100, 200, 255);
+        let to = Color::rgba(100, 200, 0, 55);
+        assert_eq!(from.lerp(to, 0.0), from);
+        assert_eq!(from.lerp(to, 1.0), to);
+        assert_eq!(from.lerp(to, 0.5), Color::rgba(50, 150, 100, 155));
+        assert_eq!(from.lerp(to, 2.0), to);
+    }
+
+    #[test]
+    fn test_color_modulate() {
+        let color = Color::rgba(10, 128, 255, 200);
+        assert_eq!(color.modulate(WHITE), color);
+        assert_eq!(color.modulate(NONE), NONE);
+        assert_eq!(WHITE.modulate(color), color);
+        assert_eq!(color.modulate(Color::rgba(255, 128, 0, 255)), Color::rgba(10, 64, 0, 200));
+    }
+
     #[test]
     fn test_color_to_u32() {
         let color: u32 = Color { r: 124, g: 58, b: 231, a: 255}.into();
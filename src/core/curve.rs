@@ -0,0 +1,132 @@
+pub type Point = (f32, f32);
+
+// maximum distance in pixels between the curve and the approximating lines
+const TOLERANCE: f32 = 0.25;
+
+// limits subdivision for degenerate input (nan, huge coordinates)
+const MAX_DEPTH: u32 = 16;
+
+fn midpoint(a: Point, b: Point) -> Point {
+    ((a.0 + b.0) * 0.5, (a.1 + b.1) * 0.5)
+}
+
+fn length(x: f32, y: f32) -> f32 {
+    f32::sqrt(x * x + y * y)
+}
+
+// appends points approximating the quadratic bezier curve, p0 is not included
+pub fn flatten_quad(p0: Point, p1: Point, p2: Point, points: &mut Vec<Point>) {
+    flatten_quad_rec(p0, p1, p2, points, 0);
+}
+
+fn flatten_quad_rec(p0: Point, p1: Point, p2: Point, points: &mut Vec<Point>, depth: u32) {
+    // max distance from the chord is a quarter of the second difference
+    let d = length(p0.0 - 2.0 * p1.0 + p2.0, p0.1 - 2.0 * p1.1 + p2.1) * 0.25;
+    if d <= TOLERANCE || depth >= MAX_DEPTH {
+        points.push(p2);
+        return;
+    }
+
+    // de casteljau subdivision at t = 0.5
+    let p01 = midpoint(p0, p1);
+    let p12 = midpoint(p1, p2);
+    let mid = midpoint(p01, p12);
+    flatten_quad_rec(p0, p01, mid, points, depth + 1);
+    flatten_quad_rec(mid, p12, p2, points, depth + 1);
+}
+
+// appends points approximating the cubic bezier curve, p0 is not included
+pub fn flatten_cubic(p0: Point, p1: Point, p2: Point, p3: Point, points: &mut Vec<Point>) {
+    flatten_cubic_rec(p0, p1, p2, p3, points, 0);
+}
+
+fn flatten_cubic_rec(p0: Point, p1: Point, p2: Point, p3: Point, points: &mut Vec<Point>, depth: u32) {
+    // max distance from the chord is bounded by 3/4 of the largest second difference
+    let d1 = length(p0.0 - 2.0 * p1.0 + p2.0, p0.1 - 2.0 * p1.1 + p2.1);
+    let d2 = length(p1.0 - 2.0 * p2.0 + p3.0, p1.1 - 2.0 * p2.1 + p3.1);
+    if f32::max(d1, d2) * 0.75 <= TOLERANCE || depth >= MAX_DEPTH {
+        points.push(p3);
+        return;
+    }
+
+    // de casteljau subdivision at t = 0.5
+    let p01 = midpoint(p0, p1);
+    let p12 = midpoint(p1, p2);
+    let p23 = midpoint(p2, p3);
+    let p012 = midpoint(p01, p12);
+    let p123 = midpoint(p12, p23);
+    let mid = midpoint(p012, p123);
+    flatten_cubic_rec(p0, p01, p012, mid, points, depth + 1);
+    flatten_cubic_rec(mid, p123, p23, p3, points, depth + 1);
+}
+
+// appends points approximating a catmull-rom spline through all points, the first point is included
+pub fn flatten_catmull_rom(control: &[Point], points: &mut Vec<Point>) {
+    if let Some(&first) = control.first() {
+        points.push(first);
+    }
+
+    for i in 0..control.len().saturating_sub(1) {
+        // end points are repeated so the curve passes through them
+        let p0 = control[i.saturating_sub(1)];
+        let (p1, p2) = (control[i], control[i + 1]);
+        let p3 = control[(i + 2).min(control.len() - 1)];
+
+        // each segment is equivalent to a cubic bezier curve
+        let c1 = (p1.0 + (p2.0 - p0.0) / 6.0, p1.1 + (p2.1 - p0.1) / 6.0);
+        let c2 = (p2.0 - (p3.0 - p1.0) / 6.0, p2.1 - (p3.1 - p1.1) / 6.0);
+        flatten_cubic(p1, c1, c2, p2, points);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_flatten_straight_quad() {
+        let mut points = Vec::new();
+        flatten_quad((0.0, 0.0), (5.0, 0.0), (10.0, 0.0), &mut points);
+        assert_eq!(points, vec![(10.0, 0.0)]);
+    }
+
+    #[test]
+    fn test_flatten_quad() {
+        let mut points = Vec::new();
+        flatten_quad((0.0, 0.0), (50.0, 100.0), (100.0, 0.0), &mut points);
+
+        assert!(points.len() > 4);
+        assert_eq!(points.last(), Some(&(100.0, 0.0)));
+
+        // curve peaks at half the control point height
+        let peak = points.iter().map(|p| p.1).fold(0.0, f32::max);
+        assert!((peak - 50.0).abs() <= TOLERANCE);
+    }
+
+    #[test]
+    fn test_flatten_cubic() {
+        let mut points = Vec::new();
+        flatten_cubic((0.0, 0.0), (0.0, 100.0), (100.0, 100.0), (100.0, 0.0), &mut points);
+
+        assert!(points.len() > 4);
+        assert_eq!(points.last(), Some(&(100.0, 0.0)));
+
+        // curve peaks at 3/4 of the control point height
+        let peak = points.iter().map(|p| p.1).fold(0.0, f32::max);
+        assert!((peak - 75.0).abs() <= TOLERANCE);
+    }
+
+    #[test]
+    fn test_flatten_catmull_rom() {
+        let control = [(0.0, 0.0), (10.0, 10.0), (20.0, 0.0), (30.0, 10.0)];
+        let mut points = Vec::new();
+        flatten_catmull_rom(&control, &mut points);
+
+        // curve passes through every control point
+        for p in control.iter() {
+            assert!(points.contains(p));
+        }
+        assert_eq!(points.first(), Some(&(0.0, 0.0)));
+        assert_eq!(points.last(), Some(&(30.0, 10.0)));
+    }
+}
@@ -0,0 +1,257 @@
+use crate::core::{BlendMode, Color, RainCore, RenderTarget};
+
+#[derive(Debug, PartialEq, Clone)]
+enum Command {
+    BlendMode(BlendMode),
+    Pixel(i32, i32, Color),
+    Line(i32, i32, i32, i32, Color),
+    LineThick(i32, i32, i32, i32, i32, Color),
+    LineAa(i32, i32, i32, i32, Color),
+    Rect(i32, i32, i32, i32, Color),
+    FillRect(i32, i32, i32, i32, Color),
+    Circle(i32, i32, i32, Color),
+    FillCircle(i32, i32, i32, Color),
+    Ellipse(i32, i32, i32, i32, Color),
+    FillEllipse(i32, i32, i32, i32, Color),
+    Triangle(i32, i32, i32, i32, i32, i32, Color),
+    FillTriangle(i32, i32, i32, i32, i32, i32, Color),
+    Polygon(Vec<(i32, i32)>, Color),
+    FillPolygon(Vec<(i32, i32)>, Color),
+}
+
+#[derive(Debug, PartialEq, Clone, Default)]
+/// Recorded list of draw calls that can be replayed later
+///
+/// Useful for caching scenery that doesn't change every frame, the methods have the
+/// same parameters as the matching methods on [`RainCore`]
+///
+/// [`RainCore`]: struct.RainCore.html
+///
+/// ### Example
+/// ```no_run
+/// # use rain2d::core::*;
+/// # let mut core = RainCore::init("example app", 640, 360, true);
+/// let mut tree = DrawList::new();
+/// tree.fill_rect(-2, 0, 4, 10, Color::rgb(120, 80, 40));
+/// tree.fill_circle(0, -4, 6, GREEN);
+///
+/// tree.replay(&mut core, 100, 200);
+/// tree.replay(&mut core, 150, 200);
+/// ```
+pub struct DrawList {
+    commands: Vec<Command>,
+}
+
+impl DrawList {
+    /// Creates an empty draw list
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Gets the number of recorded draw calls
+    pub fn len(&self) -> usize {
+        self.commands.len()
+    }
+
+    /// Checks if nothing has been recorded
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+
+    /// Removes all recorded draw calls
+    pub fn clear(&mut self) {
+        self.commands.clear();
+    }
+
+    /// Records a blend mode change, the previous blend mode is restored after replaying
+    pub fn set_blend_mode(&mut self, mode: BlendMode) {
+        self.commands.push(Command::BlendMode(mode));
+    }
+
+    /// Records a single pixel
+    pub fn draw(&mut self, x: i32, y: i32, color: Color) {
+        self.commands.push(Command::Pixel(x, y, color));
+    }
+
+    /// Records a line
+    pub fn draw_line(&mut self, x1: i32, y1: i32, x2: i32, y2: i32, color: Color) {
+        self.commands.push(Command::Line(x1, y1, x2, y2, color));
+    }
+
+    /// Records a thick line
+    pub fn draw_line_thick(&mut self, x1: i32, y1: i32, x2: i32, y2: i32, thickness: i32, color: Color) {
+        self.commands.push(Command::LineThick(x1, y1, x2, y2, thickness, color));
+    }
+
+    /// Records an anti-aliased line
+    pub fn draw_line_aa(&mut self, x1: i32, y1: i32, x2: i32, y2: i32, color: Color) {
+        self.commands.push(Command::LineAa(x1, y1, x2, y2, color));
+    }
+
+    /// Records a rectangle outline
+    pub fn draw_rect(&mut self, x: i32, y: i32, width: i32, height: i32, color: Color) {
+        self.commands.push(Command::Rect(x, y, width, height, color));
+    }
+
+    /// Records a filled in rectangle
+    pub fn fill_rect(&mut self, x: i32, y: i32, width: i32, height: i32, color: Color) {
+        self.commands.push(Command::FillRect(x, y, width, height, color));
+    }
+
+    /// Records a circle outline
+    pub fn draw_circle(&mut self, x: i32, y: i32, r: i32, color: Color) {
+        self.commands.push(Command::Circle(x, y, r, color));
+    }
+
+    /// Records a filled in circle
+    pub fn fill_circle(&mut self, x: i32, y: i32, r: i32, color: Color) {
+        self.commands.push(Command::FillCircle(x, y, r, color));
+    }
+
+    /// Records an ellipse outline
+    pub fn draw_ellipse(&mut self, x: i32, y: i32, rx: i32, ry: i32, color: Color) {
+        self.commands.push(Command::Ellipse(x, y, rx, ry, color));
+    }
+
+    /// Records a filled in ellipse
+    pub fn fill_ellipse(&mut self, x: i32, y: i32, rx: i32, ry: i32, color: Color) {
+        self.commands.push(Command::FillEllipse(x, y, rx, ry, color));
+    }
+
+    /// Records a triangle outline
+    pub fn draw_triangle(&mut self, x1: i32, y1: i32, x2: i32, y2: i32, x3: i32, y3: i32, color: Color) {
+        self.commands.push(Command::Triangle(x1, y1, x2, y2, x3, y3, color));
+    }
+
+    /// Records a filled in triangle
+    pub fn fill_triangle(&mut self, x1: i32, y1: i32, x2: i32, y2: i32, x3: i32, y3: i32, color: Color) {
+        self.commands.push(Command::FillTriangle(x1, y1, x2, y2, x3, y3, color));
+    }
+
+    /// Records a polygon outline
+    pub fn draw_polygon(&mut self, points: &[(i32, i32)], color: Color) {
+        self.commands.push(Command::Polygon(points.to_vec(), color));
+    }
+
+    /// Records a filled in polygon
+    pub fn fill_polygon(&mut self, points: &[(i32, i32)], color: Color) {
+        self.commands.push(Command::FillPolygon(points.to_vec(), color));
+    }
+
+    /// Replays the recorded draw calls on the screen, offset by `(x, y)`
+    ///
+    /// The offset is applied on top of the current transform
+    pub fn replay(&self, core: &mut RainCore, x: i32, y: i32) {
+        let blend_mode = core.get_blend_mode();
+        core.push_transform();
+        core.translate(x as f32, y as f32);
+
+        for command in &self.commands {
+            match *command {
+                Command::BlendMode(mode) => core.set_blend_mode(mode),
+                Command::Pixel(x, y, color) => core.draw(x, y, color),
+                Command::Line(x1, y1, x2, y2, color) => core.draw_line(x1, y1, x2, y2, color),
+                Command::LineThick(x1, y1, x2, y2, thickness, color) => {
+                    core.draw_line_thick(x1, y1, x2, y2, thickness, color)
+                }
+                Command::LineAa(x1, y1, x2, y2, color) => core.draw_line_aa(x1, y1, x2, y2, color),
+                Command::Rect(x, y, width, height, color) => core.draw_rect(x, y, width, height, color),
+                Command::FillRect(x, y, width, height, color) => core.fill_rect(x, y, width, height, color),
+                Command::Circle(x, y, r, color) => core.draw_circle(x, y, r, color),
+                Command::FillCircle(x, y, r, color) => core.fill_circle(x, y, r, color),
+                Command::Ellipse(x, y, rx, ry, color) => core.draw_ellipse(x, y, rx, ry, color),
+                Command::FillEllipse(x, y, rx, ry, color) => core.fill_ellipse(x, y, rx, ry, color),
+                Command::Triangle(x1, y1, x2, y2, x3, y3, color) => {
+                    core.draw_triangle(x1, y1, x2, y2, x3, y3, color)
+                }
+                Command::FillTriangle(x1, y1, x2, y2, x3, y3, color) => {
+                    core.fill_triangle(x1, y1, x2, y2, x3, y3, color)
+                }
+                Command::Polygon(ref points, color) => core.draw_polygon(points, color),
+                Command::FillPolygon(ref points, color) => core.fill_polygon(points, color),
+            }
+        }
+
+        core.pop_transform();
+        core.set_blend_mode(blend_mode);
+    }
+
+    /// Replays the recorded draw calls on `target` instead of the screen, offset by `(x, y)`, the
+    /// camera, transform and supersampling of the screen are not applied
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// let mut list = DrawList::new();
+    /// list.fill_circle(16, 16, 12, RED);
+    ///
+    /// let mut target = RenderTarget::new(32, 32);
+    /// list.replay_to(&mut core, &mut target, 0, 0);
+    /// ```
+    pub fn replay_to(&self, core: &mut RainCore, target: &mut RenderTarget, x: i32, y: i32) {
+        core.draw_to_target(target, |core| self.replay(core, x, y));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::core::color::{WHITE, RED, NONE};
+    use crate::core::{Camera2D, Transform};
+
+    #[test]
+    fn test_record() {
+        let mut list = DrawList::new();
+        assert!(list.is_empty());
+
+        list.draw_line(0, 0, 5, 5, WHITE);
+        list.fill_polygon(&[(0, 0), (4, 0), (0, 4)], RED);
+        assert_eq!(list.len(), 2);
+
+        list.clear();
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    fn test_replay() {
+        let mut core = RainCore::init("", 20, 20, false);
+        let mut list = DrawList::new();
+        list.draw(0, 0, WHITE);
+        list.fill_rect(1, 0, 2, 1, RED);
+
+        list.replay(&mut core, 2, 3);
+        list.replay(&mut core, 10, 10);
+        assert_eq!(core.render_target.get_pixel(2, 3), Some(WHITE));
+        assert_eq!(core.render_target.get_pixel(4, 3), Some(RED));
+        assert_eq!(core.render_target.get_pixel(10, 10), Some(WHITE));
+        assert_eq!(core.render_target.get_pixel(11, 10), Some(RED));
+        assert_eq!(core.render_target.get_pixel(0, 0), Some(NONE));
+        assert!(core.get_transform().is_identity());
+    }
+
+    #[test]
+    fn test_replay_to() {
+        let mut core = RainCore::init("", 20, 20, false);
+        let mut target = RenderTarget::new(4, 4);
+        let mut list = DrawList::new();
+        list.set_blend_mode(BlendMode::Additive);
+        list.draw(1, 1, WHITE);
+
+        list.replay_to(&mut core, &mut target, 0, 0);
+        assert_eq!(target.get_pixel(1, 1), Some(WHITE));
+        assert_eq!(target.blend_mode, BlendMode::Alpha);
+        assert_eq!(core.render_target.get_pixel(1, 1), Some(NONE));
+        assert_eq!(core.get_blend_mode(), BlendMode::Alpha);
+
+        let mut target = RenderTarget::new(4, 4);
+        core.set_camera(Some(Camera2D { position: (3.0, 3.0), zoom: 2.0, ..Camera2D::default() }));
+        core.set_supersampling(true);
+        core.translate(5.0, 5.0);
+        list.replay_to(&mut core, &mut target, 1, 0);
+        assert_eq!(target.get_pixel(2, 1), Some(WHITE));
+        assert_eq!(target.get_pixel(1, 1), Some(NONE));
+        assert_eq!(target.get_pixel(3, 3), Some(NONE));
+        assert_eq!(core.get_transform(), Transform::translation(5.0, 5.0));
+    }
+}
@@ -0,0 +1,405 @@
+// files dropped on the window. minifb doesn't report them, so the drag and drop protocol of each
+// platform is hooked up to its window by hand
+
+use std::path::PathBuf;
+
+use minifb::Window;
+
+// receives the files dropped on a window until it's dropped, nothing is received on wayland
+pub struct FileDrop {
+    #[cfg(target_os = "linux")]
+    xdnd: Option<xdnd::Xdnd>,
+}
+
+impl FileDrop {
+    #[cfg(target_os = "linux")]
+    pub fn new(window: &Window) -> Self {
+        FileDrop { xdnd: xdnd::Xdnd::new(window) }
+    }
+
+    #[cfg(windows)]
+    pub fn new(window: &Window) -> Self {
+        win32::accept_files(window);
+        FileDrop {}
+    }
+
+    #[cfg(target_os = "macos")]
+    pub fn new(window: &Window) -> Self {
+        cocoa::accept_files(window);
+        FileDrop {}
+    }
+
+    #[cfg(not(any(target_os = "linux", windows, target_os = "macos")))]
+    pub fn new(_window: &Window) -> Self {
+        FileDrop {}
+    }
+
+    // gets the files dropped since the last call
+    #[cfg(target_os = "linux")]
+    pub fn take(&mut self) -> Vec<PathBuf> {
+        self.xdnd.as_mut().map_or_else(Vec::new, |xdnd| xdnd.poll())
+    }
+
+    #[cfg(any(windows, target_os = "macos"))]
+    pub fn take(&mut self) -> Vec<PathBuf> {
+        DROPPED.with(|dropped| dropped.take())
+    }
+
+    #[cfg(not(any(target_os = "linux", windows, target_os = "macos")))]
+    pub fn take(&mut self) -> Vec<PathBuf> {
+        Vec::new()
+    }
+}
+
+// windows and macos report drops while minifb handles the events of the window, on the thread
+// that owns it
+#[cfg(any(windows, target_os = "macos"))]
+thread_local! {
+    static DROPPED: std::cell::Cell<Vec<PathBuf>> = const { std::cell::Cell::new(Vec::new()) };
+}
+
+#[cfg(any(windows, target_os = "macos"))]
+fn push_dropped(path: PathBuf) {
+    DROPPED.with(|dropped| {
+        let mut paths = dropped.take();
+        paths.push(path);
+        dropped.set(paths);
+    });
+}
+
+// XDND, the window gets an XdndProxy so the messages of the drag go to a window on a connection
+// of our own and minifb never sees them
+#[cfg(target_os = "linux")]
+mod xdnd {
+    use std::{ffi::{CString, OsString}, os::{raw::c_long, unix::ffi::OsStringExt}, path::PathBuf, ptr, slice};
+
+    use minifb::Window;
+    use x11_dl::xlib::{self, Atom, Display};
+
+    use crate::core::window::{with_xlib, xlib_window};
+
+    const VERSION: c_long = 5;
+
+    struct Atoms {
+        aware: Atom,
+        proxy: Atom,
+        enter: Atom,
+        position: Atom,
+        status: Atom,
+        drop: Atom,
+        finished: Atom,
+        selection: Atom,
+        action_copy: Atom,
+        uri_list: Atom,
+        property: Atom,
+    }
+
+    pub struct Xdnd {
+        display: *mut Display,
+        proxy: xlib::Window,
+        atoms: Atoms,
+        // window the files are dragged from and the window they are dropped on
+        source: xlib::Window,
+        target: xlib::Window,
+    }
+
+    impl Xdnd {
+        pub fn new(window: &Window) -> Option<Self> {
+            with_xlib(|xlib| unsafe {
+                let (_, target) = xlib_window(window)?;
+                let display = (xlib.XOpenDisplay)(ptr::null());
+                if display.is_null() { return None; }
+                let atom = |name: &str| {
+                    let name = CString::new(name).expect("atom names have no nul");
+                    (xlib.XInternAtom)(display, name.as_ptr(), xlib::False)
+                };
+                let atoms = Atoms {
+                    aware: atom("XdndAware"),
+                    proxy: atom("XdndProxy"),
+                    enter: atom("XdndEnter"),
+                    position: atom("XdndPosition"),
+                    status: atom("XdndStatus"),
+                    drop: atom("XdndDrop"),
+                    finished: atom("XdndFinished"),
+                    selection: atom("XdndSelection"),
+                    action_copy: atom("XdndActionCopy"),
+                    uri_list: atom("text/uri-list"),
+                    property: atom("RAIN2D_DROP"),
+                };
+                let root = (xlib.XDefaultRootWindow)(display);
+                let proxy = (xlib.XCreateSimpleWindow)(display, root, 0, 0, 1, 1, 0, 0, 0);
+
+                // both windows accept drops and the proxy points to itself
+                let set = |window, property, kind, value: c_long| {
+                    (xlib.XChangeProperty)(display, window, property, kind, 32, xlib::PropModeReplace,
+                                           &value as *const c_long as *const u8, 1);
+                };
+                set(proxy, atoms.aware, xlib::XA_ATOM, VERSION);
+                set(proxy, atoms.proxy, xlib::XA_WINDOW, proxy as c_long);
+                set(target, atoms.aware, xlib::XA_ATOM, VERSION);
+                set(target, atoms.proxy, xlib::XA_WINDOW, proxy as c_long);
+                (xlib.XFlush)(display);
+                Some(Xdnd { display, proxy, atoms, source: 0, target })
+            })
+        }
+
+        // handles the messages of the drag, returns the files once they arrive
+        pub fn poll(&mut self) -> Vec<PathBuf> {
+            with_xlib(|xlib| unsafe {
+                let mut files = Vec::new();
+                while (xlib.XPending)(self.display) > 0 {
+                    let mut event: xlib::XEvent = std::mem::zeroed();
+                    (xlib.XNextEvent)(self.display, &mut event);
+                    match event.get_type() {
+                        xlib::ClientMessage => self.handle_message(xlib, &event.client_message),
+                        xlib::SelectionNotify if event.selection.property == self.atoms.property => {
+                            files.extend(self.read_files(xlib));
+                        }
+                        xlib::SelectionNotify => self.send(xlib, self.atoms.finished, [0, self.atoms.action_copy]),
+                        _ => {}
+                    }
+                }
+                Some(files)
+            }).unwrap_or_default()
+        }
+
+        unsafe fn handle_message(&mut self, xlib: &xlib::Xlib, message: &xlib::XClientMessageEvent) {
+            let data = &message.data;
+            let kind = message.message_type;
+            if kind == self.atoms.enter {
+                self.source = data.get_long(0) as xlib::Window;
+            } else if kind == self.atoms.position {
+                // accept the files anywhere in the window, the empty rectangle asks for more
+                // positions so dropping works after the window moved
+                self.source = data.get_long(0) as xlib::Window;
+                self.send(xlib, self.atoms.status, [1, self.atoms.action_copy]);
+            } else if kind == self.atoms.drop {
+                self.source = data.get_long(0) as xlib::Window;
+                let time = data.get_long(2) as xlib::Time;
+                (xlib.XConvertSelection)(self.display, self.atoms.selection, self.atoms.uri_list,
+                                         self.atoms.property, self.proxy, time);
+            }
+        }
+
+        unsafe fn read_files(&mut self, xlib: &xlib::Xlib) -> Vec<PathBuf> {
+            let (mut kind, mut format, mut count, mut remaining) = (0, 0, 0, 0);
+            let mut data = ptr::null_mut();
+            (xlib.XGetWindowProperty)(self.display, self.proxy, self.atoms.property, 0, c_long::MAX / 4,
+                                      xlib::True, xlib::AnyPropertyType as Atom, &mut kind, &mut format,
+                                      &mut count, &mut remaining, &mut data);
+            let files = if data.is_null() || format != 8 {
+                Vec::new()
+            } else {
+                super::parse_uri_list(slice::from_raw_parts(data, count as usize))
+                    .into_iter()
+                    .map(|path| PathBuf::from(OsString::from_vec(path)))
+                    .collect()
+            };
+            if !data.is_null() {
+                (xlib.XFree)(data as *mut _);
+            }
+            self.send(xlib, self.atoms.finished, [!files.is_empty() as Atom, self.atoms.action_copy]);
+            files
+        }
+
+        // sends XdndStatus or XdndFinished to the source, the message names the target window
+        // followed by the flags and the action
+        unsafe fn send(&self, xlib: &xlib::Xlib, kind: Atom, data: [Atom; 2]) {
+            if self.source == 0 { return; }
+            let mut message = xlib::XClientMessageEvent {
+                type_: xlib::ClientMessage,
+                serial: 0,
+                send_event: xlib::True,
+                display: self.display,
+                window: self.source,
+                message_type: kind,
+                format: 32,
+                data: xlib::ClientMessageData::new(),
+            };
+            message.data.set_long(0, self.target as c_long);
+            message.data.set_long(1, data[0] as c_long);
+            // XdndFinished has the action in the third field, XdndStatus the fifth after a
+            // rectangle
+            let action = if kind == self.atoms.finished { 2 } else { 4 };
+            message.data.set_long(action, data[1] as c_long);
+            let mut event = xlib::XEvent { client_message: message };
+            (xlib.XSendEvent)(self.display, self.source, xlib::False, xlib::NoEventMask, &mut event);
+            (xlib.XFlush)(self.display);
+        }
+    }
+
+    impl Drop for Xdnd {
+        fn drop(&mut self) {
+            with_xlib(|xlib| unsafe {
+                (xlib.XDestroyWindow)(self.display, self.proxy);
+                (xlib.XCloseDisplay)(self.display);
+                Some(())
+            });
+        }
+    }
+}
+
+// reads the local files of a text/uri-list, one file:// uri per line with lines starting with #
+// being comments
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn parse_uri_list(list: &[u8]) -> Vec<Vec<u8>> {
+    list.split(|&byte| byte == b'\n')
+        .map(|line| line.strip_suffix(b"\r").unwrap_or(line))
+        .filter_map(|line| line.strip_prefix(b"file://"))
+        // the host comes before the path, usually empty or localhost
+        .filter_map(|uri| uri.iter().position(|&byte| byte == b'/').map(|start| &uri[start..]))
+        .map(percent_decode)
+        .collect()
+}
+
+// replaces escapes like %20 with the byte they stand for
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn percent_decode(text: &[u8]) -> Vec<u8> {
+    let hex = |byte: u8| (byte as char).to_digit(16).map(|digit| digit as u8);
+    let mut decoded = Vec::with_capacity(text.len());
+    let mut i = 0;
+    while i < text.len() {
+        let escaped = match text.get(i..i + 3) {
+            Some(&[b'%', high, low]) => hex(high).zip(hex(low)).map(|(high, low)| high * 16 + low),
+            _ => None,
+        };
+        match escaped {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            None => {
+                decoded.push(text[i]);
+                i += 1;
+            }
+        }
+    }
+    decoded
+}
+
+// WM_DROPFILES, the window procedure of minifb is wrapped to see the message and the procedure it
+// replaced is kept as a property of the window
+#[cfg(windows)]
+mod win32 {
+    use std::{ffi::OsString, os::windows::ffi::OsStringExt, path::PathBuf, ptr};
+
+    use minifb::Window;
+    use winapi::shared::minwindef::{LPARAM, LRESULT, TRUE, UINT, WPARAM};
+    use winapi::shared::windef::HWND;
+    use winapi::um::winnt::HANDLE;
+    use winapi::um::shellapi::{DragAcceptFiles, DragFinish, DragQueryFileW, HDROP};
+    use winapi::um::winuser::*;
+
+    use crate::core::window::hwnd;
+
+    // "rain2d_wndproc"
+    const PROPERTY: [u16; 15] = [114, 97, 105, 110, 50, 100, 95, 119, 110, 100, 112, 114, 111, 99, 0];
+
+    pub fn accept_files(window: &Window) {
+        let hwnd = match hwnd(window) {
+            Some(hwnd) => hwnd,
+            None => return,
+        };
+        unsafe {
+            if !GetPropW(hwnd, PROPERTY.as_ptr()).is_null() { return; }
+            let previous = SetWindowLongPtrW(hwnd, GWLP_WNDPROC, window_proc as *const () as _);
+            SetPropW(hwnd, PROPERTY.as_ptr(), previous as _);
+            DragAcceptFiles(hwnd, TRUE);
+        }
+    }
+
+    unsafe extern "system" fn window_proc(hwnd: HWND, message: UINT, wparam: WPARAM, lparam: LPARAM) -> LRESULT {
+        let previous = GetPropW(hwnd, PROPERTY.as_ptr());
+        if message == WM_DROPFILES {
+            let drop = wparam as HDROP;
+            for i in 0..DragQueryFileW(drop, u32::MAX, ptr::null_mut(), 0) {
+                let len = DragQueryFileW(drop, i, ptr::null_mut(), 0);
+                let mut path = vec![0u16; len as usize + 1];
+                DragQueryFileW(drop, i, path.as_mut_ptr(), len + 1);
+                super::push_dropped(PathBuf::from(OsString::from_wide(&path[..len as usize])));
+            }
+            DragFinish(drop);
+            return 0;
+        }
+        if message == WM_NCDESTROY {
+            RemovePropW(hwnd, PROPERTY.as_ptr());
+        }
+        CallWindowProcW(std::mem::transmute::<HANDLE, WNDPROC>(previous), hwnd, message, wparam, lparam)
+    }
+}
+
+// the window is registered for file urls and gets the methods of a dragging destination added
+// to its class at runtime
+#[cfg(target_os = "macos")]
+mod cocoa {
+    use std::{ffi::CStr, os::raw::{c_char, c_void}, path::PathBuf, ptr};
+
+    use minifb::Window;
+    use raw_window_handle::{HasRawWindowHandle, RawWindowHandle};
+
+    use crate::core::window::objc::{self, class_addMethod, object_getClass, Id};
+
+    const DRAG_OPERATION_COPY: usize = 1;
+
+    #[link(name = "AppKit", kind = "framework")]
+    extern "C" {
+        static NSPasteboardTypeFileURL: Id;
+    }
+
+    pub fn accept_files(window: &Window) {
+        let ns_window = match window.raw_window_handle() {
+            RawWindowHandle::MacOS(handle) if !handle.ns_window.is_null() => handle.ns_window as Id,
+            _ => return,
+        };
+        unsafe {
+            let class = object_getClass(ns_window);
+            let operation = dragging_operation as unsafe extern "C" fn(Id, Id, Id) -> usize;
+            let perform = perform_drag as unsafe extern "C" fn(Id, Id, Id) -> i8;
+            // methods that already exist are kept, so adding them again for a new window is fine
+            class_addMethod(class, objc::selector_named(b"draggingEntered:\0"), operation as *const c_void,
+                            b"Q@:@\0".as_ptr() as *const c_char);
+            class_addMethod(class, objc::selector_named(b"draggingUpdated:\0"), operation as *const c_void,
+                            b"Q@:@\0".as_ptr() as *const c_char);
+            class_addMethod(class, objc::selector_named(b"performDragOperation:\0"), perform as *const c_void,
+                            b"c@:@\0".as_ptr() as *const c_char);
+            let types: Id = objc::send1(objc::class_named(b"NSArray\0"), b"arrayWithObject:\0", NSPasteboardTypeFileURL);
+            objc::send1::<Id, ()>(ns_window, b"registerForDraggedTypes:\0", types);
+        }
+    }
+
+    unsafe extern "C" fn dragging_operation(_window: Id, _selector: Id, _info: Id) -> usize {
+        DRAG_OPERATION_COPY
+    }
+
+    unsafe extern "C" fn perform_drag(_window: Id, _selector: Id, info: Id) -> i8 {
+        let pasteboard: Id = objc::send(info, b"draggingPasteboard\0");
+        let classes: Id = objc::send1(objc::class_named(b"NSArray\0"), b"arrayWithObject:\0",
+                                      objc::class_named(b"NSURL\0"));
+        let urls: Id = objc::send2(pasteboard, b"readObjectsForClasses:options:\0", classes, ptr::null_mut::<c_void>());
+        if urls.is_null() { return 0; }
+        let count: usize = objc::send(urls, b"count\0");
+        for i in 0..count {
+            let url: Id = objc::send1(urls, b"objectAtIndex:\0", i);
+            let path: Id = objc::send(url, b"path\0");
+            if objc::send::<i8>(url, b"isFileURL\0") == 0 || path.is_null() { continue; }
+            let path: *const c_char = objc::send(path, b"fileSystemRepresentation\0");
+            if !path.is_null() {
+                super::push_dropped(PathBuf::from(CStr::from_ptr(path).to_string_lossy().into_owned()));
+            }
+        }
+        1
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_uri_list() {
+        let list = b"# dragged from the file manager\r\nfile:///home/me/level%201.png\r\n\
+                     file://localhost/tmp/a%2Fb%zz\r\nhttp://example.com/image.png\r\n";
+        assert_eq!(parse_uri_list(list), vec![b"/home/me/level 1.png".to_vec(), b"/tmp/a/b%zz".to_vec()]);
+        assert!(parse_uri_list(b"").is_empty());
+    }
+}
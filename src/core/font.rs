@@ -0,0 +1,396 @@
+use std::{error::Error, fmt, fs, io, path::Path};
+
+use crate::core::{ImageError, Sprite};
+use crate::core::bitmapfont::{self, BitmapFont};
+#[cfg(feature = "truetype")]
+use crate::core::truetype::TrueType;
+
+#[derive(Debug)]
+/// Error returned when loading a font fails
+pub enum FontError {
+    /// The file couldn't be read
+    Io(io::Error),
+
+    /// The data is not a valid font
+    Invalid(&'static str),
+
+    /// The font uses a feature that isn't supported
+    Unsupported(&'static str),
+
+    /// A page image of a bitmap font couldn't be loaded
+    Image(ImageError),
+}
+
+impl fmt::Display for FontError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FontError::Io(e) => write!(f, "failed to read font: {}", e),
+            FontError::Invalid(reason) => write!(f, "invalid font: {}", reason),
+            FontError::Unsupported(feature) => write!(f, "unsupported font: {}", feature),
+            FontError::Image(e) => write!(f, "failed to load font page: {}", e),
+        }
+    }
+}
+
+impl Error for FontError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            FontError::Io(e) => Some(e),
+            FontError::Image(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for FontError {
+    fn from(e: io::Error) -> Self {
+        FontError::Io(e)
+    }
+}
+
+impl From<ImageError> for FontError {
+    fn from(e: ImageError) -> Self {
+        FontError::Image(e)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+/// Font that text can be drawn with using [`draw_text_ttf`] and measured with [`measure_text`]
+///
+/// [`Font::builtin`] is the 5x7 font used by [`draw_text`], it is scaled by whole pixels
+/// to the closest multiple of its 8 pixel line height. Bitmap fonts exported from BMFont or
+/// Hiero can be loaded with [`Font::from_bmfont`]. With the `truetype` feature fonts with
+/// TrueType outlines can be loaded, OpenType fonts with CFF outlines and font collections are
+/// not supported
+///
+/// [`draw_text_ttf`]: struct.RainCore.html#method.draw_text_ttf
+/// [`measure_text`]: struct.RainCore.html#method.measure_text
+/// [`draw_text`]: struct.RainCore.html#method.draw_text
+/// [`Font::builtin`]: #method.builtin
+/// [`Font::from_bmfont`]: #method.from_bmfont
+///
+/// ### Example
+#[cfg_attr(feature = "truetype", doc = "```no_run")]
+#[cfg_attr(not(feature = "truetype"), doc = "```ignore")]
+/// # use rain2d::core::*;
+/// # let mut core = RainCore::init("example app", 640, 360, true);
+/// let font = Font::from_file("assets/font.ttf").expect("failed to load font");
+/// core.draw_text_ttf(10, 10, "Hello world", &font, 24.0, WHITE);
+/// ```
+pub struct Font {
+    pub(crate) kind: FontKind,
+}
+
+#[derive(Debug, Clone, Default)]
+pub(crate) enum FontKind {
+    #[default]
+    Builtin,
+    Bitmap(BitmapFont),
+    #[cfg(feature = "truetype")]
+    TrueType(TrueType),
+}
+
+impl Font {
+    /// Gets the built-in 5x7 font
+    pub fn builtin() -> Self {
+        Font { kind: FontKind::Builtin }
+    }
+
+    /// Loads a ttf file
+    #[cfg(feature = "truetype")]
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, FontError> {
+        Ok(Font { kind: FontKind::TrueType(TrueType::parse(fs::read(path)?)?) })
+    }
+
+    /// Reads a ttf file that is already in memory, useful together with `include_bytes!`
+    #[cfg(feature = "truetype")]
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, FontError> {
+        Ok(Font { kind: FontKind::TrueType(TrueType::parse(bytes.to_vec())?) })
+    }
+
+    /// Loads a bitmap font from a BMFont text descriptor (.fnt) and its page images, which are
+    /// looked up relative to the descriptor
+    ///
+    /// Pages can be png or bmp files, `size` in [`draw_text_ttf`] is relative to the size the
+    /// font was exported at. The binary and xml descriptor formats are not supported
+    ///
+    /// [`draw_text_ttf`]: struct.RainCore.html#method.draw_text_ttf
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// let font = Font::from_bmfont("assets/fonts/pixel.fnt").expect("failed to load font");
+    /// core.draw_text_ttf(10, 10, "Hello world", &font, 16.0, WHITE);
+    /// ```
+    pub fn from_bmfont<P: AsRef<Path>>(path: P) -> Result<Self, FontError> {
+        let path = path.as_ref();
+        let bytes = fs::read(path)?;
+        if bytes.starts_with(b"BMF") {
+            return Err(FontError::Unsupported("binary descriptor"));
+        }
+        if bytes.starts_with(b"<?xml") {
+            return Err(FontError::Unsupported("xml descriptor"));
+        }
+        let descriptor = String::from_utf8(bytes).map_err(|_| FontError::Invalid("descriptor isn't utf-8"))?;
+
+        let dir = path.parent().unwrap_or_else(|| Path::new(""));
+        let pages = bitmapfont::page_files(&descriptor)?.iter()
+            .map(|file| load_page(&dir.join(file)))
+            .collect::<Result<Vec<_>, _>>()?;
+        Self::from_bmfont_str(&descriptor, pages)
+    }
+
+    /// Reads a BMFont text descriptor with page images that are already loaded, `pages` is
+    /// ordered by page id
+    pub fn from_bmfont_str(descriptor: &str, pages: Vec<Sprite>) -> Result<Self, FontError> {
+        Ok(Font { kind: FontKind::Bitmap(BitmapFont::parse(descriptor, &pages)?) })
+    }
+
+    /// Creates a monospaced bitmap font from a sprite with the glyphs laid out in a grid
+    ///
+    /// Glyphs are `glyph_width` by `glyph_height` pixels, the first one is in the upper left
+    /// corner and is used for `first_char`, the following characters go left to right and top
+    /// to bottom. Most fonts made for retro games are a 16x16 grid starting at `'\0'` or a
+    /// 16x6 grid starting at `' '`. Returns `None` if the sprite is smaller than one glyph
+    ///
+    /// `size` in [`draw_text_ttf`] is relative to the glyph height, glyphs are tinted with the
+    /// text color so white glyphs take on the color exactly
+    ///
+    /// [`draw_text_ttf`]: struct.RainCore.html#method.draw_text_ttf
+    ///
+    /// ### Example
+    #[cfg_attr(feature = "png", doc = "```no_run")]
+    #[cfg_attr(not(feature = "png"), doc = "```ignore")]
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// let sheet = Sprite::from_png("assets/fonts/8x8.png").unwrap();
+    /// let font = Font::from_grid(&sheet, 8, 8, '\0').unwrap();
+    /// core.draw_text_ttf(10, 10, "PRESS START", &font, 16.0, YELLOW);
+    /// ```
+    pub fn from_grid(sprite: &Sprite, glyph_width: usize, glyph_height: usize, first_char: char) -> Option<Self> {
+        if glyph_width == 0 || glyph_height == 0 || glyph_width > sprite.width || glyph_height > sprite.height {
+            return None;
+        }
+        Some(Font { kind: FontKind::Bitmap(BitmapFont::grid(sprite, glyph_width, glyph_height, first_char)) })
+    }
+
+    // pixels per font unit, the built-in font only scales by whole pixels
+    pub(crate) fn scale(&self, size: f32) -> f32 {
+        match &self.kind {
+            FontKind::Builtin => (size / LINE_HEIGHT as f32).round().max(1.0),
+            FontKind::Bitmap(bitmap) => size / bitmap.size,
+            #[cfg(feature = "truetype")]
+            FontKind::TrueType(ttf) => size / ttf.units_per_em(),
+        }
+    }
+
+    // distance between two lines in pixels
+    pub(crate) fn line_height(&self, size: f32) -> f32 {
+        let scale = self.scale(size);
+        match &self.kind {
+            FontKind::Builtin => LINE_HEIGHT as f32 * scale,
+            FontKind::Bitmap(bitmap) => bitmap.line_height * scale,
+            #[cfg(feature = "truetype")]
+            FontKind::TrueType(ttf) => (ttf.ascent() - ttf.descent() + ttf.line_gap()) * scale,
+        }
+    }
+
+    // distance the pen moves after the character in pixels
+    pub(crate) fn advance(&self, c: char, size: f32) -> f32 {
+        let scale = self.scale(size);
+        match &self.kind {
+            FontKind::Builtin => ADVANCE as f32 * scale,
+            FontKind::Bitmap(bitmap) => bitmap.glyph(c).map_or(0.0, |glyph| glyph.advance) * scale,
+            #[cfg(feature = "truetype")]
+            FontKind::TrueType(ttf) => ttf.advance(ttf.glyph_index(c)) * scale,
+        }
+    }
+
+    // extra distance between two characters in pixels, usually negative
+    pub(crate) fn kerning(&self, first: char, second: char, size: f32) -> f32 {
+        match &self.kind {
+            FontKind::Bitmap(bitmap) => bitmap.kerning.get(&(first, second)).map_or(0.0, |k| k * self.scale(size)),
+            _ => 0.0,
+        }
+    }
+
+    // moves the pen through the text and calls glyph with every character and the position of
+    // the pen at the top of the line, new lines start at left
+    pub(crate) fn layout<F: FnMut(char, f32, f32)>(&self, text: &str, size: f32, left: f32, pen: &mut Pen, mut glyph: F) {
+        for c in text.chars() {
+            if c == '\n' {
+                *pen = Pen { x: left, y: pen.y + self.line_height(size), prev: None };
+                continue;
+            }
+            if let Some(prev) = pen.prev {
+                pen.x += self.kerning(prev, c, size);
+            }
+            glyph(c, pen.x, pen.y);
+            pen.x += self.advance(c, size);
+            pen.prev = Some(c);
+        }
+    }
+
+    // width of a single line in pixels
+    pub(crate) fn line_width(&self, line: &str, size: f32) -> f32 {
+        let mut pen = Pen::new(0.0, 0.0);
+        self.layout(line, size, 0.0, &mut pen, |_, _, _| ());
+        pen.x
+    }
+}
+
+// position of the next character and the character before it for kerning
+pub(crate) struct Pen {
+    pub x: f32,
+    pub y: f32,
+    pub prev: Option<char>,
+}
+
+impl Pen {
+    pub fn new(x: f32, y: f32) -> Self {
+        Pen { x, y, prev: None }
+    }
+}
+
+// loads a page of a bitmap font based on its extension
+fn load_page(path: &Path) -> Result<Sprite, FontError> {
+    let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("").to_ascii_lowercase();
+    match extension.as_str() {
+        #[cfg(feature = "png")]
+        "png" => Ok(Sprite::from_png(path)?),
+        "bmp" => Ok(Sprite::from_bmp(path)?),
+        _ => Err(FontError::Unsupported("page image format")),
+    }
+}
+
+// built-in 5x7 font covering printable ascii, every glyph is a 1 bit image with one byte per
+// row and the leftmost pixel in the most significant bit
+pub const GLYPH_WIDTH: usize = 5;
+pub const GLYPH_HEIGHT: usize = 7;
+
+// distance between the start of two characters and two lines
+pub const ADVANCE: i32 = 6;
+pub const LINE_HEIGHT: i32 = 8;
+
+// glyphs start at the space character
+const GLYPHS: [[u8; GLYPH_HEIGHT]; 95] = [
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], // space
+    [0x20, 0x20, 0x20, 0x20, 0x20, 0x00, 0x20], // !
+    [0x50, 0x50, 0x50, 0x00, 0x00, 0x00, 0x00], // "
+    [0x50, 0x50, 0xF8, 0x50, 0xF8, 0x50, 0x50], // #
+    [0x20, 0x78, 0xA0, 0x70, 0x28, 0xF0, 0x20], // $
+    [0xC0, 0xC8, 0x10, 0x20, 0x40, 0x98, 0x18], // %
+    [0x60, 0x90, 0xA0, 0x40, 0xA8, 0x90, 0x68], // &
+    [0x20, 0x20, 0x40, 0x00, 0x00, 0x00, 0x00], // '
+    [0x10, 0x20, 0x40, 0x40, 0x40, 0x20, 0x10], // (
+    [0x40, 0x20, 0x10, 0x10, 0x10, 0x20, 0x40], // )
+    [0x00, 0x20, 0xA8, 0x70, 0xA8, 0x20, 0x00], // *
+    [0x00, 0x20, 0x20, 0xF8, 0x20, 0x20, 0x00], // +
+    [0x00, 0x00, 0x00, 0x00, 0x60, 0x20, 0x40], // ,
+    [0x00, 0x00, 0x00, 0xF8, 0x00, 0x00, 0x00], // -
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x60, 0x60], // .
+    [0x00, 0x08, 0x10, 0x20, 0x40, 0x80, 0x00], // /
+    [0x70, 0x88, 0x98, 0xA8, 0xC8, 0x88, 0x70], // 0
+    [0x20, 0x60, 0x20, 0x20, 0x20, 0x20, 0x70], // 1
+    [0x70, 0x88, 0x08, 0x10, 0x20, 0x40, 0xF8], // 2
+    [0xF8, 0x10, 0x20, 0x10, 0x08, 0x88, 0x70], // 3
+    [0x10, 0x30, 0x50, 0x90, 0xF8, 0x10, 0x10], // 4
+    [0xF8, 0x80, 0xF0, 0x08, 0x08, 0x88, 0x70], // 5
+    [0x30, 0x40, 0x80, 0xF0, 0x88, 0x88, 0x70], // 6
+    [0xF8, 0x08, 0x10, 0x20, 0x40, 0x40, 0x40], // 7
+    [0x70, 0x88, 0x88, 0x70, 0x88, 0x88, 0x70], // 8
+    [0x70, 0x88, 0x88, 0x78, 0x08, 0x10, 0x60], // 9
+    [0x00, 0x60, 0x60, 0x00, 0x60, 0x60, 0x00], // :
+    [0x00, 0x60, 0x60, 0x00, 0x60, 0x20, 0x40], // ;
+    [0x10, 0x20, 0x40, 0x80, 0x40, 0x20, 0x10], // <
+    [0x00, 0x00, 0xF8, 0x00, 0xF8, 0x00, 0x00], // =
+    [0x40, 0x20, 0x10, 0x08, 0x10, 0x20, 0x40], // >
+    [0x70, 0x88, 0x08, 0x10, 0x20, 0x00, 0x20], // ?
+    [0x70, 0x88, 0x08, 0x68, 0xA8, 0xA8, 0x70], // @
+    [0x70, 0x88, 0x88, 0xF8, 0x88, 0x88, 0x88], // A
+    [0xF0, 0x88, 0x88, 0xF0, 0x88, 0x88, 0xF0], // B
+    [0x70, 0x88, 0x80, 0x80, 0x80, 0x88, 0x70], // C
+    [0xE0, 0x90, 0x88, 0x88, 0x88, 0x90, 0xE0], // D
+    [0xF8, 0x80, 0x80, 0xF0, 0x80, 0x80, 0xF8], // E
+    [0xF8, 0x80, 0x80, 0xF0, 0x80, 0x80, 0x80], // F
+    [0x70, 0x88, 0x80, 0xB8, 0x88, 0x88, 0x78], // G
+    [0x88, 0x88, 0x88, 0xF8, 0x88, 0x88, 0x88], // H
+    [0x70, 0x20, 0x20, 0x20, 0x20, 0x20, 0x70], // I
+    [0x38, 0x10, 0x10, 0x10, 0x10, 0x90, 0x60], // J
+    [0x88, 0x90, 0xA0, 0xC0, 0xA0, 0x90, 0x88], // K
+    [0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0xF8], // L
+    [0x88, 0xD8, 0xA8, 0xA8, 0x88, 0x88, 0x88], // M
+    [0x88, 0x88, 0xC8, 0xA8, 0x98, 0x88, 0x88], // N
+    [0x70, 0x88, 0x88, 0x88, 0x88, 0x88, 0x70], // O
+    [0xF0, 0x88, 0x88, 0xF0, 0x80, 0x80, 0x80], // P
+    [0x70, 0x88, 0x88, 0x88, 0xA8, 0x90, 0x68], // Q
+    [0xF0, 0x88, 0x88, 0xF0, 0xA0, 0x90, 0x88], // R
+    [0x78, 0x80, 0x80, 0x70, 0x08, 0x08, 0xF0], // S
+    [0xF8, 0x20, 0x20, 0x20, 0x20, 0x20, 0x20], // T
+    [0x88, 0x88, 0x88, 0x88, 0x88, 0x88, 0x70], // U
+    [0x88, 0x88, 0x88, 0x88, 0x88, 0x50, 0x20], // V
+    [0x88, 0x88, 0x88, 0xA8, 0xA8, 0xA8, 0x50], // W
+    [0x88, 0x88, 0x50, 0x20, 0x50, 0x88, 0x88], // X
+    [0x88, 0x88, 0x50, 0x20, 0x20, 0x20, 0x20], // Y
+    [0xF8, 0x08, 0x10, 0x20, 0x40, 0x80, 0xF8], // Z
+    [0x70, 0x40, 0x40, 0x40, 0x40, 0x40, 0x70], // [
+    [0x00, 0x80, 0x40, 0x20, 0x10, 0x08, 0x00], // \
+    [0x70, 0x10, 0x10, 0x10, 0x10, 0x10, 0x70], // ]
+    [0x20, 0x50, 0x88, 0x00, 0x00, 0x00, 0x00], // ^
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xF8], // _
+    [0x40, 0x20, 0x10, 0x00, 0x00, 0x00, 0x00], // `
+    [0x00, 0x00, 0x70, 0x08, 0x78, 0x88, 0x78], // a
+    [0x80, 0x80, 0xB0, 0xC8, 0x88, 0x88, 0xF0], // b
+    [0x00, 0x00, 0x70, 0x80, 0x80, 0x88, 0x70], // c
+    [0x08, 0x08, 0x68, 0x98, 0x88, 0x88, 0x78], // d
+    [0x00, 0x00, 0x70, 0x88, 0xF8, 0x80, 0x70], // e
+    [0x30, 0x48, 0x40, 0xE0, 0x40, 0x40, 0x40], // f
+    [0x00, 0x78, 0x88, 0x88, 0x78, 0x08, 0x70], // g
+    [0x80, 0x80, 0xB0, 0xC8, 0x88, 0x88, 0x88], // h
+    [0x20, 0x00, 0x60, 0x20, 0x20, 0x20, 0x70], // i
+    [0x10, 0x00, 0x30, 0x10, 0x10, 0x90, 0x60], // j
+    [0x80, 0x80, 0x90, 0xA0, 0xC0, 0xA0, 0x90], // k
+    [0x60, 0x20, 0x20, 0x20, 0x20, 0x20, 0x70], // l
+    [0x00, 0x00, 0xD0, 0xA8, 0xA8, 0x88, 0x88], // m
+    [0x00, 0x00, 0xB0, 0xC8, 0x88, 0x88, 0x88], // n
+    [0x00, 0x00, 0x70, 0x88, 0x88, 0x88, 0x70], // o
+    [0x00, 0x00, 0xF0, 0x88, 0xF0, 0x80, 0x80], // p
+    [0x00, 0x00, 0x68, 0x98, 0x78, 0x08, 0x08], // q
+    [0x00, 0x00, 0xB0, 0xC8, 0x80, 0x80, 0x80], // r
+    [0x00, 0x00, 0x70, 0x80, 0x70, 0x08, 0xF0], // s
+    [0x40, 0x40, 0xE0, 0x40, 0x40, 0x48, 0x30], // t
+    [0x00, 0x00, 0x88, 0x88, 0x88, 0x98, 0x68], // u
+    [0x00, 0x00, 0x88, 0x88, 0x88, 0x50, 0x20], // v
+    [0x00, 0x00, 0x88, 0x88, 0xA8, 0xA8, 0x50], // w
+    [0x00, 0x00, 0x88, 0x50, 0x20, 0x50, 0x88], // x
+    [0x00, 0x00, 0x88, 0x88, 0x78, 0x08, 0x70], // y
+    [0x00, 0x00, 0xF8, 0x10, 0x20, 0x40, 0xF8], // z
+    [0x10, 0x20, 0x20, 0x40, 0x20, 0x20, 0x10], // {
+    [0x20, 0x20, 0x20, 0x20, 0x20, 0x20, 0x20], // |
+    [0x40, 0x20, 0x20, 0x10, 0x20, 0x20, 0x40], // }
+    [0x00, 0x00, 0x40, 0xA8, 0x10, 0x00, 0x00], // ~
+];
+
+// gets the glyph for the character, characters that aren't printable ascii are shown as '?'
+pub fn glyph(c: char) -> &'static [u8; GLYPH_HEIGHT] {
+    match c {
+        ' '..='~' => &GLYPHS[c as usize - ' ' as usize],
+        _ => &GLYPHS['?' as usize - ' ' as usize],
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_glyph() {
+        assert_eq!(glyph(' '), &[0; GLYPH_HEIGHT]);
+        assert_eq!(glyph('|'), &[0x20; GLYPH_HEIGHT]);
+        assert_eq!(glyph('é'), glyph('?'));
+
+        // every glyph fits in the glyph width
+        assert!(GLYPHS.iter().flatten().all(|&row| row & 0x07 == 0));
+    }
+}
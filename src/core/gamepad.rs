@@ -0,0 +1,376 @@
+// gamepad state and a backend reading the linux joystick interface, other platforms never see
+// any gamepads connected
+
+#[cfg(target_os = "linux")]
+use std::{fs::File, io::{ErrorKind, Read}, os::unix::fs::OpenOptionsExt, fs::OpenOptions};
+use std::time::{Duration, Instant};
+
+// number of gamepads that are looked for
+const MAX_GAMEPADS: usize = 4;
+const BUTTON_COUNT: usize = 15;
+const AXIS_COUNT: usize = 6;
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+/// Gamepad button, the face buttons are named after their position
+pub enum GamepadButton {
+    /// Bottom face button, A on Xbox controllers
+    South,
+
+    /// Right face button, B on Xbox controllers
+    East,
+
+    /// Left face button, X on Xbox controllers
+    West,
+
+    /// Top face button, Y on Xbox controllers
+    North,
+
+    /// Left shoulder button
+    LeftShoulder,
+
+    /// Right shoulder button
+    RightShoulder,
+
+    /// Select or back button
+    Select,
+
+    /// Start button
+    Start,
+
+    /// Button with the logo of the vendor
+    Mode,
+
+    /// Pressing the left stick
+    LeftStick,
+
+    /// Pressing the right stick
+    RightStick,
+
+    /// Up on the d-pad
+    DPadUp,
+
+    /// Down on the d-pad
+    DPadDown,
+
+    /// Left on the d-pad
+    DPadLeft,
+
+    /// Right on the d-pad
+    DPadRight,
+}
+
+// every button in the order of the enum
+pub const BUTTONS: [GamepadButton; BUTTON_COUNT] = {
+    use GamepadButton::*;
+    [South, East, West, North, LeftShoulder, RightShoulder, Select, Start, Mode, LeftStick, RightStick,
+     DPadUp, DPadDown, DPadLeft, DPadRight]
+};
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+/// Analog input of a gamepad
+pub enum GamepadAxis {
+    /// Horizontal position of the left stick, from -1.0 on the left to 1.0 on the right
+    LeftStickX,
+
+    /// Vertical position of the left stick, from -1.0 at the top to 1.0 at the bottom
+    LeftStickY,
+
+    /// Horizontal position of the right stick, from -1.0 on the left to 1.0 on the right
+    RightStickX,
+
+    /// Vertical position of the right stick, from -1.0 at the top to 1.0 at the bottom
+    RightStickY,
+
+    /// Left trigger, from 0.0 when released to 1.0 when fully pressed
+    LeftTrigger,
+
+    /// Right trigger, from 0.0 when released to 1.0 when fully pressed
+    RightTrigger,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+/// State of a connected gamepad, get it with [`gamepad`]
+///
+/// [`gamepad`]: struct.RainCore.html#method.gamepad
+///
+/// ### Example
+/// ```no_run
+/// # use rain2d::core::*;
+/// # let mut core = RainCore::init("example app", 640, 360, true);
+/// if let Some(pad) = core.gamepad(0) {
+///     let (x, y) = pad.left_stick();
+///     if pad.button_pressed(GamepadButton::South) {
+///         println!("jump");
+///     }
+/// }
+/// ```
+pub struct Gamepad {
+    /// Sticks closer to the center than this are reported as centered, from 0.0 to 1.0
+    pub deadzone: f32,
+
+    buttons: [bool; BUTTON_COUNT],
+    previous: [bool; BUTTON_COUNT],
+    axes: [f32; AXIS_COUNT],
+}
+
+impl Default for Gamepad {
+    fn default() -> Self {
+        Gamepad { deadzone: 0.15, buttons: [false; BUTTON_COUNT], previous: [false; BUTTON_COUNT], axes: [0.0; AXIS_COUNT] }
+    }
+}
+
+impl Gamepad {
+    /// Checks if the button is currently down
+    pub fn button_down(&self, button: GamepadButton) -> bool {
+        self.buttons[button as usize]
+    }
+
+    /// Checks if the button was pressed since the last update
+    pub fn button_pressed(&self, button: GamepadButton) -> bool {
+        self.buttons[button as usize] && !self.previous[button as usize]
+    }
+
+    /// Checks if the button was released since the last update
+    pub fn button_released(&self, button: GamepadButton) -> bool {
+        !self.buttons[button as usize] && self.previous[button as usize]
+    }
+
+    /// Gets the value of the axis, values inside the deadzone are 0.0 and the rest is scaled so
+    /// it still starts at 0.0
+    pub fn axis(&self, axis: GamepadAxis) -> f32 {
+        let (value, deadzone) = (self.axes[axis as usize], self.clamped_deadzone());
+        let magnitude = ((value.abs() - deadzone) / (1.0 - deadzone)).clamp(0.0, 1.0);
+        magnitude.copysign(value)
+    }
+
+    /// Gets the position of the left stick, see [`stick`](#method.stick)
+    pub fn left_stick(&self) -> (f32, f32) {
+        self.stick(GamepadAxis::LeftStickX, GamepadAxis::LeftStickY)
+    }
+
+    /// Gets the position of the right stick, see [`stick`](#method.stick)
+    pub fn right_stick(&self) -> (f32, f32) {
+        self.stick(GamepadAxis::RightStickX, GamepadAxis::RightStickY)
+    }
+
+    /// Gets the position of a stick with a round deadzone, diagonal movement isn't snapped to
+    /// the axes like it is when using [`axis`](#method.axis) for both directions
+    pub fn stick(&self, x: GamepadAxis, y: GamepadAxis) -> (f32, f32) {
+        let (x, y) = (self.axes[x as usize], self.axes[y as usize]);
+        let (length, deadzone) = ((x * x + y * y).sqrt(), self.clamped_deadzone());
+        if length <= deadzone { return (0.0, 0.0); }
+        let scale = ((length - deadzone) / (1.0 - deadzone)).min(1.0) / length;
+        (x * scale, y * scale)
+    }
+
+    // deadzone that leaves room for scaling the rest of the range, 1.0 would divide by 0
+    fn clamped_deadzone(&self) -> f32 {
+        self.deadzone.min(0.999)
+    }
+
+    // applies an event of the linux joystick interface, the numbers follow the layout of the
+    // xpad driver used by most controllers
+    #[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+    fn handle_event(&mut self, kind: u8, number: u8, value: i16) {
+        use GamepadButton::*;
+        let axis_value = (value as f32 / 32767.0).clamp(-1.0, 1.0);
+        match (kind & 0x7f, number) {
+            (1, _) => {
+                let button = match number {
+                    0 => South,
+                    1 => East,
+                    2 => West,
+                    3 => North,
+                    4 => LeftShoulder,
+                    5 => RightShoulder,
+                    6 => Select,
+                    7 => Start,
+                    8 => Mode,
+                    9 => LeftStick,
+                    10 => RightStick,
+                    _ => return,
+                };
+                self.buttons[button as usize] = value != 0;
+            }
+            (2, 0) => self.axes[GamepadAxis::LeftStickX as usize] = axis_value,
+            (2, 1) => self.axes[GamepadAxis::LeftStickY as usize] = axis_value,
+            (2, 2) => self.axes[GamepadAxis::LeftTrigger as usize] = (axis_value + 1.0) / 2.0,
+            (2, 3) => self.axes[GamepadAxis::RightStickX as usize] = axis_value,
+            (2, 4) => self.axes[GamepadAxis::RightStickY as usize] = axis_value,
+            (2, 5) => self.axes[GamepadAxis::RightTrigger as usize] = (axis_value + 1.0) / 2.0,
+            (2, 6) => {
+                self.buttons[DPadLeft as usize] = value < 0;
+                self.buttons[DPadRight as usize] = value > 0;
+            }
+            (2, 7) => {
+                self.buttons[DPadUp as usize] = value < 0;
+                self.buttons[DPadDown as usize] = value > 0;
+            }
+            _ => {}
+        }
+    }
+}
+
+// all gamepads with the devices they are read from
+#[derive(Default)]
+pub struct Gamepads {
+    pads: [Option<Gamepad>; MAX_GAMEPADS],
+    #[cfg(target_os = "linux")]
+    devices: [Option<File>; MAX_GAMEPADS],
+    last_scan: Option<Instant>,
+}
+
+impl Gamepads {
+    pub fn get(&self, index: usize) -> Option<&Gamepad> {
+        self.pads.get(index)?.as_ref()
+    }
+
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut Gamepad> {
+        self.pads.get_mut(index)?.as_mut()
+    }
+
+    // reads all events since the last update, looks for new gamepads every second. returns the
+    // index of every gamepad that was connected or disconnected with its new state
+    pub fn update(&mut self) -> Vec<(usize, bool)> {
+        let connected = self.connected();
+        for pad in self.pads.iter_mut().flatten() {
+            pad.previous = pad.buttons;
+        }
+
+        let now = Instant::now();
+        if self.last_scan.is_none_or(|last| now - last >= Duration::from_secs(1)) {
+            self.last_scan = Some(now);
+            self.scan();
+        }
+        self.read_events();
+
+        self.connected().iter()
+            .zip(connected)
+            .enumerate()
+            .filter(|(_, (&now, before))| now != *before)
+            .map(|(i, (&now, _))| (i, now))
+            .collect()
+    }
+
+    fn connected(&self) -> [bool; MAX_GAMEPADS] {
+        self.pads.each_ref().map(Option::is_some)
+    }
+
+    #[cfg(target_os = "linux")]
+    fn scan(&mut self) {
+        // O_NONBLOCK so reading stops once all pending events are read
+        const O_NONBLOCK: i32 = 0o4000;
+        for (i, device) in self.devices.iter_mut().enumerate().filter(|(_, d)| d.is_none()) {
+            let path = format!("/dev/input/js{}", i);
+            if let Ok(file) = OpenOptions::new().read(true).custom_flags(O_NONBLOCK).open(path) {
+                *device = Some(file);
+                self.pads[i] = Some(Gamepad::default());
+            }
+        }
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    fn scan(&mut self) {}
+
+    #[cfg(target_os = "linux")]
+    fn read_events(&mut self) {
+        for (device, pad) in self.devices.iter_mut().zip(self.pads.iter_mut()) {
+            let (file, gamepad) = match (device.as_mut(), pad.as_mut()) {
+                (Some(file), Some(gamepad)) => (file, gamepad),
+                _ => continue,
+            };
+            let mut event = [0u8; 8];
+            loop {
+                match file.read(&mut event) {
+                    Ok(8) => gamepad.handle_event(event[6], event[7], i16::from_le_bytes([event[4], event[5]])),
+                    Err(e) if e.kind() == ErrorKind::WouldBlock => break,
+                    // the gamepad was disconnected
+                    _ => {
+                        *device = None;
+                        *pad = None;
+                        break;
+                    }
+                }
+            }
+        }
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    fn read_events(&mut self) {}
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_buttons() {
+        let mut pad = Gamepad::default();
+        pad.handle_event(0x81, 0, 0);
+        pad.handle_event(1, 3, 1);
+        assert!(pad.button_down(GamepadButton::North));
+        assert!(pad.button_pressed(GamepadButton::North));
+        assert!(!pad.button_down(GamepadButton::South));
+
+        pad.previous = pad.buttons;
+        pad.handle_event(1, 3, 0);
+        assert!(pad.button_released(GamepadButton::North));
+
+        // the d-pad is reported as two axes
+        pad.handle_event(2, 6, -32767);
+        pad.handle_event(2, 7, 32767);
+        assert!(pad.button_down(GamepadButton::DPadLeft));
+        assert!(pad.button_down(GamepadButton::DPadDown));
+        assert!(!pad.button_down(GamepadButton::DPadRight));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_disconnect() {
+        // reading an empty file looks like a disconnected device
+        let path = std::env::temp_dir().join("rain2d_test_gamepad");
+        File::create(&path).unwrap();
+        let mut gamepads = Gamepads { last_scan: Some(Instant::now()), ..Gamepads::default() };
+        gamepads.devices[1] = Some(File::open(&path).unwrap());
+        gamepads.pads[1] = Some(Gamepad::default());
+
+        assert_eq!(gamepads.update(), vec![(1, false)]);
+        assert!(gamepads.get(1).is_none());
+        assert!(gamepads.update().is_empty());
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_axes() {
+        let mut pad = Gamepad { deadzone: 0.2, ..Gamepad::default() };
+        pad.handle_event(2, 0, 3000);
+        assert_eq!(pad.axis(GamepadAxis::LeftStickX), 0.0);
+        pad.handle_event(2, 0, -32767);
+        assert_eq!(pad.axis(GamepadAxis::LeftStickX), -1.0);
+        pad.handle_event(2, 5, -32767);
+        assert_eq!(pad.axis(GamepadAxis::RightTrigger), 0.0);
+        pad.handle_event(2, 5, 32767);
+        assert_eq!(pad.axis(GamepadAxis::RightTrigger), 1.0);
+
+        // round deadzone keeps the direction
+        pad.handle_event(2, 0, 0);
+        pad.handle_event(2, 1, 1000);
+        assert_eq!(pad.left_stick(), (0.0, 0.0));
+        pad.handle_event(2, 0, 19660);
+        pad.handle_event(2, 1, 19660);
+        let (x, y) = pad.left_stick();
+        assert!((x - y).abs() < 1e-6);
+        assert!(((x * x + y * y).sqrt() - (0.6f32 * 2f32.sqrt() - 0.2) / 0.8).abs() < 1e-3);
+
+        // a deadzone covering everything still gives finite values
+        pad.deadzone = 1.0;
+        pad.handle_event(2, 0, 32767);
+        pad.handle_event(2, 1, 32767);
+        assert_eq!(pad.axis(GamepadAxis::LeftStickX), 1.0);
+        assert_eq!(pad.axis(GamepadAxis::RightTrigger), 1.0);
+        let (x, y) = pad.left_stick();
+        assert!(x.is_finite() && y.is_finite() && x > 0.0);
+        pad.handle_event(2, 0, 16000);
+        assert_eq!(pad.axis(GamepadAxis::LeftStickX), 0.0);
+    }
+}
@@ -0,0 +1,376 @@
+// gif decoder that composes every frame onto the full image
+
+use std::time::Duration;
+
+use crate::core::{Animation, Color, ImageError, LoopMode, Sprite, NONE};
+
+const INVALID: ImageError = ImageError::Invalid("corrupt gif file");
+
+// delays below this are shown at the same speed as browsers do
+const MIN_DELAY: u16 = 2;
+const DEFAULT_DELAY: Duration = Duration::from_millis(100);
+
+pub fn decode(bytes: &[u8]) -> Result<Animation, ImageError> {
+    if bytes.len() < 13 || (&bytes[..6] != b"GIF87a" && &bytes[..6] != b"GIF89a") {
+        return Err(ImageError::Invalid("not a gif file"));
+    }
+
+    let width = u16_at(bytes, 6)? as usize;
+    let height = u16_at(bytes, 8)? as usize;
+    let flags = bytes[10];
+    let mut reader = Reader { bytes, pos: 13 };
+    let global_palette = if flags & 0x80 != 0 {
+        Some(reader.palette(flags)?)
+    } else {
+        None
+    };
+
+    let mut canvas = Sprite::new(width, height);
+    let mut frames = Vec::new();
+    let mut delays = Vec::new();
+    let mut looping = false;
+    let mut control = Control::default();
+
+    loop {
+        match reader.byte()? {
+            // extension
+            0x21 => {
+                let label = reader.byte()?;
+                let data = reader.sub_blocks()?;
+                match label {
+                    0xF9 if data.len() >= 4 => {
+                        control = Control {
+                            disposal: (data[0] >> 2) & 0x07,
+                            delay: u16::from_le_bytes([data[1], data[2]]),
+                            transparent: if data[0] & 0x01 != 0 { Some(data[3]) } else { None },
+                        };
+                    }
+                    // the loop count of the netscape extension is stored in the block after
+                    // the application identifier, zero means forever
+                    0xFF if data.starts_with(b"NETSCAPE2.0") => {
+                        looping = data.len() >= 14 && data[12] == 0 && data[13] == 0;
+                    }
+                    _ => (),
+                }
+            }
+            // image descriptor
+            0x2C => {
+                let x = u16_at(bytes, reader.pos)? as usize;
+                let y = u16_at(bytes, reader.pos + 2)? as usize;
+                let w = u16_at(bytes, reader.pos + 4)? as usize;
+                let h = u16_at(bytes, reader.pos + 6)? as usize;
+                reader.pos += 8;
+                let flags = reader.byte()?;
+
+                let local_palette = if flags & 0x80 != 0 { Some(reader.palette(flags)?) } else { None };
+                let palette = local_palette.as_ref().or(global_palette.as_ref()).ok_or(INVALID)?;
+                let min_code_size = reader.byte()?;
+                let indices = lzw_decode(&reader.sub_blocks()?, min_code_size, w * h)?;
+
+                // restoring to the previous frame needs a copy from before drawing
+                let previous = if control.disposal == 3 { Some(canvas.clone()) } else { None };
+
+                let rows = row_order(h, flags & 0x40 != 0);
+                for (src_row, &dst_row) in rows.iter().enumerate() {
+                    for col in 0..w {
+                        let index = match indices.get(src_row * w + col) {
+                            Some(&index) => index,
+                            None => continue,
+                        };
+                        if control.transparent == Some(index) { continue; }
+                        let color = *palette.get(index as usize).ok_or(INVALID)?;
+                        canvas.set_pixel((x + col) as i32, (y + dst_row) as i32, color);
+                    }
+                }
+
+                frames.push(canvas.clone());
+                delays.push(if control.delay < MIN_DELAY {
+                    DEFAULT_DELAY
+                } else {
+                    Duration::from_millis(control.delay as u64 * 10)
+                });
+
+                match control.disposal {
+                    2 => {
+                        for row in y..(y + h).min(height) {
+                            for col in x..(x + w).min(width) {
+                                canvas.data[row * width + col] = NONE.into();
+                            }
+                        }
+                    }
+                    3 => canvas = previous.unwrap_or(canvas),
+                    _ => (),
+                }
+                control = Control::default();
+            }
+            // trailer
+            0x3B => break,
+            _ => return Err(INVALID),
+        }
+    }
+
+    if frames.is_empty() {
+        return Err(ImageError::Invalid("gif without frames"));
+    }
+
+    let mut animation = Animation::new(frames, DEFAULT_DELAY);
+    for (i, delay) in delays.into_iter().enumerate() {
+        animation.set_frame_duration(i, delay);
+    }
+    animation.set_loop_mode(if looping { LoopMode::Loop } else { LoopMode::Once });
+    Ok(animation)
+}
+
+// settings from the graphic control extension, they only apply to the next image
+#[derive(Default)]
+struct Control {
+    disposal: u8,
+    delay: u16,
+    transparent: Option<u8>,
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn byte(&mut self) -> Result<u8, ImageError> {
+        let b = *self.bytes.get(self.pos).ok_or(INVALID)?;
+        self.pos += 1;
+        Ok(b)
+    }
+
+    // reads a color table, its size is stored in the lowest bits of the flags
+    fn palette(&mut self, flags: u8) -> Result<Vec<Color>, ImageError> {
+        let len = 3 << ((flags & 0x07) + 1);
+        let data = self.bytes.get(self.pos..self.pos + len).ok_or(INVALID)?;
+        self.pos += len;
+        Ok(data.chunks_exact(3).map(|c| Color::rgb(c[0], c[1], c[2])).collect())
+    }
+
+    // joins the data sub-blocks that follow, each one starts with its length
+    fn sub_blocks(&mut self) -> Result<Vec<u8>, ImageError> {
+        let mut data = Vec::new();
+        loop {
+            let len = self.byte()? as usize;
+            if len == 0 { return Ok(data); }
+            data.extend_from_slice(self.bytes.get(self.pos..self.pos + len).ok_or(INVALID)?);
+            self.pos += len;
+        }
+    }
+}
+
+// maps the order rows are stored in to the rows of the image
+fn row_order(height: usize, interlaced: bool) -> Vec<usize> {
+    if !interlaced {
+        return (0..height).collect();
+    }
+    [(0, 8), (4, 8), (2, 4), (1, 2)].iter()
+        .flat_map(|&(start, step)| (start..height).step_by(step))
+        .collect()
+}
+
+// decodes variable width codes, stops after max_len indices
+fn lzw_decode(data: &[u8], min_code_size: u8, max_len: usize) -> Result<Vec<u8>, ImageError> {
+    if !(1..=11).contains(&min_code_size) { return Err(INVALID); }
+    let clear = 1u16 << min_code_size;
+    let end = clear + 1;
+
+    // every code is a previous code followed by one byte, first is the first byte of the sequence
+    let mut prefix = vec![0u16; 4096];
+    let mut suffix = vec![0u8; 4096];
+    let mut first = vec![0u8; 4096];
+    for i in 0..clear {
+        suffix[i as usize] = i as u8;
+        first[i as usize] = i as u8;
+    }
+
+    let mut out = Vec::with_capacity(max_len);
+    let mut stack = Vec::new();
+    let mut size = min_code_size as u32 + 1;
+    let mut next = end + 1;
+    let mut prev: Option<u16> = None;
+    let (mut buf, mut count, mut pos) = (0u32, 0u32, 0usize);
+
+    while out.len() < max_len {
+        while count < size {
+            let byte = match data.get(pos) {
+                Some(&b) => b,
+                // some encoders leave out the end code
+                None => return Ok(out),
+            };
+            buf |= (byte as u32) << count;
+            count += 8;
+            pos += 1;
+        }
+        let code = (buf & ((1 << size) - 1)) as u16;
+        buf >>= size;
+        count -= size;
+
+        if code == clear {
+            size = min_code_size as u32 + 1;
+            next = end + 1;
+            prev = None;
+            continue;
+        }
+        if code == end { break; }
+
+        let p = match prev {
+            Some(p) => p,
+            None => {
+                if code >= clear { return Err(INVALID); }
+                out.push(code as u8);
+                prev = Some(code);
+                continue;
+            }
+        };
+
+        // a code that isn't in the table yet is the previous sequence plus its own first byte
+        let known = code < next;
+        if !known && code != next { return Err(INVALID); }
+        let head = if known { first[code as usize] } else { first[p as usize] };
+
+        if next < 4096 {
+            prefix[next as usize] = p;
+            suffix[next as usize] = head;
+            first[next as usize] = first[p as usize];
+            next += 1;
+            if next == 1 << size && size < 12 { size += 1; }
+        }
+
+        let mut c = code;
+        while c > end {
+            stack.push(suffix[c as usize]);
+            c = prefix[c as usize];
+        }
+        stack.push(c as u8);
+        out.extend(stack.drain(..).rev());
+        prev = Some(code);
+    }
+
+    out.truncate(max_len);
+    Ok(out)
+}
+
+fn u16_at(bytes: &[u8], i: usize) -> Result<u16, ImageError> {
+    let b = bytes.get(i..i + 2).ok_or(INVALID)?;
+    Ok(u16::from_le_bytes([b[0], b[1]]))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::core::color::{RED, BLUE};
+
+    // builds a gif from frames of palette indices using uncompressed lzw codes,
+    // every frame is (delay, disposal, transparent index, indices)
+    fn encode(width: u16, height: u16, palette: &[Color], looping: bool, frames: &[(u16, u8, Option<u8>, &[u8])]) -> Vec<u8> {
+        let mut out = b"GIF89a".to_vec();
+        out.extend_from_slice(&width.to_le_bytes());
+        out.extend_from_slice(&height.to_le_bytes());
+        // global table with 4 entries
+        out.extend_from_slice(&[0x81, 0, 0]);
+        for i in 0..4 {
+            let c = palette.get(i).copied().unwrap_or(NONE);
+            out.extend_from_slice(&[c.r, c.g, c.b]);
+        }
+        if looping {
+            out.extend_from_slice(&[0x21, 0xFF, 11]);
+            out.extend_from_slice(b"NETSCAPE2.0");
+            out.extend_from_slice(&[3, 1, 0, 0, 0]);
+        }
+
+        for &(delay, disposal, transparent, indices) in frames {
+            let packed = disposal << 2 | transparent.is_some() as u8;
+            out.extend_from_slice(&[0x21, 0xF9, 4, packed]);
+            out.extend_from_slice(&delay.to_le_bytes());
+            out.extend_from_slice(&[transparent.unwrap_or(0), 0]);
+
+            out.push(0x2C);
+            for v in [0, 0, width, height] {
+                out.extend_from_slice(&v.to_le_bytes());
+            }
+            out.push(0);
+
+            // min code size 2 means 3 bit codes, a clear code before every index keeps
+            // the width at 3 bits
+            let mut codes = Vec::new();
+            for &i in indices {
+                codes.extend_from_slice(&[4, i as u16]);
+            }
+            codes.push(5);
+            let (mut bytes, mut buf, mut count) = (Vec::new(), 0u32, 0);
+            for code in codes {
+                buf |= (code as u32) << count;
+                count += 3;
+                while count >= 8 {
+                    bytes.push(buf as u8);
+                    buf >>= 8;
+                    count -= 8;
+                }
+            }
+            if count > 0 { bytes.push(buf as u8); }
+
+            out.push(2);
+            for block in bytes.chunks(255) {
+                out.push(block.len() as u8);
+                out.extend_from_slice(block);
+            }
+            out.push(0);
+        }
+        out.push(0x3B);
+        out
+    }
+
+    #[test]
+    fn test_frames() {
+        let gif = encode(2, 1, &[RED, BLUE], true, &[(10, 1, None, &[0, 1]), (0, 0, Some(0), &[0, 0])]);
+        let mut animation = decode(&gif).unwrap();
+        assert_eq!(animation.frame_count(), 2);
+        assert_eq!(animation.current_frame().unwrap().get_pixel(1, 0), Some(BLUE));
+
+        // transparent pixels keep the previous frame
+        animation.update(Duration::from_millis(100));
+        assert_eq!(animation.frame_index(), 1);
+        assert_eq!(animation.current_frame().unwrap().get_pixel(1, 0), Some(BLUE));
+
+        // missing delays default to 100ms
+        animation.update(Duration::from_millis(99));
+        assert_eq!(animation.frame_index(), 1);
+        animation.update(Duration::from_millis(1));
+        assert_eq!(animation.frame_index(), 0);
+    }
+
+    #[test]
+    fn test_disposal() {
+        let gif = encode(1, 1, &[RED, BLUE], false, &[(10, 2, None, &[1]), (10, 0, Some(0), &[0])]);
+        let mut animation = decode(&gif).unwrap();
+        animation.update(Duration::from_secs(1));
+        assert!(animation.is_finished());
+        assert_eq!(animation.current_frame().unwrap().get_pixel(0, 0), Some(NONE));
+    }
+
+    #[test]
+    fn test_lzw() {
+        // codes 4 (clear), 1, 1, 6 (1 1), 5 (end) with growing table entries
+        let data = [0x4C, 0x5C];
+        assert_eq!(lzw_decode(&data, 2, 10).unwrap(), vec![1, 1, 1, 1]);
+
+        // data of the well known 1x1 transparent gif
+        assert_eq!(lzw_decode(&[0x44, 0x01], 2, 1).unwrap(), vec![0]);
+    }
+
+    #[test]
+    fn test_interlaced_rows() {
+        assert_eq!(row_order(5, true), vec![0, 4, 2, 1, 3]);
+        assert_eq!(row_order(3, false), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn test_invalid() {
+        assert!(decode(b"not a gif").is_err());
+        let gif = encode(1, 1, &[RED], false, &[]);
+        assert!(decode(&gif).is_err());
+    }
+}
@@ -0,0 +1,254 @@
+// minimal zlib/deflate decoder, only used to read png image data
+
+use crate::core::ImageError;
+
+const LENGTH_BASE: [u16; 29] = [
+    3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115, 131, 163, 195, 227, 258,
+];
+const LENGTH_EXTRA: [u8; 29] = [0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0];
+const DIST_BASE: [u16; 30] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513, 769, 1025, 1537, 2049, 3073, 4097,
+    6145, 8193, 12289, 16385, 24577,
+];
+const DIST_EXTRA: [u8; 30] = [0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13, 13];
+
+// order in which code length code lengths are stored
+const CODE_LENGTH_ORDER: [usize; 19] = [16, 17, 18, 0, 8, 7, 9, 6, 10, 5, 11, 4, 12, 3, 13, 2, 14, 1, 15];
+
+const INVALID: ImageError = ImageError::Invalid("corrupt deflate stream");
+
+// decompresses a zlib stream, the checksum is not verified
+pub fn zlib_decompress(data: &[u8]) -> Result<Vec<u8>, ImageError> {
+    if data.len() < 2 { return Err(INVALID); }
+    let (cmf, flg) = (data[0], data[1]);
+    if cmf & 0x0F != 8 || !(cmf as u16 * 256 + flg as u16).is_multiple_of(31) {
+        return Err(ImageError::Invalid("invalid zlib header"));
+    }
+    if flg & 0x20 != 0 {
+        return Err(ImageError::Unsupported("zlib preset dictionary"));
+    }
+    inflate(&data[2..])
+}
+
+// decompresses raw deflate data
+pub fn inflate(data: &[u8]) -> Result<Vec<u8>, ImageError> {
+    let mut reader = BitReader { data, pos: 0, buf: 0, count: 0 };
+    let mut out = Vec::with_capacity(data.len() * 4);
+
+    loop {
+        let last = reader.bits(1)? == 1;
+        match reader.bits(2)? {
+            0 => stored_block(&mut reader, &mut out)?,
+            1 => {
+                let (lit, dist) = fixed_tables();
+                huffman_block(&mut reader, &mut out, &lit, &dist)?;
+            }
+            2 => {
+                let (lit, dist) = dynamic_tables(&mut reader)?;
+                huffman_block(&mut reader, &mut out, &lit, &dist)?;
+            }
+            _ => return Err(INVALID),
+        }
+        if last { return Ok(out); }
+    }
+}
+
+struct BitReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+    buf: u64,
+    count: u32,
+}
+
+impl<'a> BitReader<'a> {
+    // reads n bits, least significant bit first
+    fn bits(&mut self, n: u32) -> Result<u32, ImageError> {
+        while self.count < n {
+            let byte = *self.data.get(self.pos).ok_or(INVALID)?;
+            self.buf |= (byte as u64) << self.count;
+            self.pos += 1;
+            self.count += 8;
+        }
+        let value = (self.buf & ((1 << n) - 1)) as u32;
+        self.buf >>= n;
+        self.count -= n;
+        Ok(value)
+    }
+
+    // discards the remaining bits of the current byte
+    fn align(&mut self) {
+        let skip = self.count % 8;
+        self.buf >>= skip;
+        self.count -= skip;
+    }
+}
+
+// canonical huffman code stored as the number of codes per length and the symbols in code order
+struct Huffman {
+    counts: [u16; 16],
+    symbols: Vec<u16>,
+}
+
+impl Huffman {
+    fn new(lengths: &[u8]) -> Self {
+        let mut counts = [0u16; 16];
+        for &len in lengths {
+            counts[len as usize] += 1;
+        }
+        counts[0] = 0;
+
+        let mut offsets = [0u16; 16];
+        for len in 1..15 {
+            offsets[len + 1] = offsets[len] + counts[len];
+        }
+
+        let mut symbols = vec![0; lengths.len()];
+        for (symbol, &len) in lengths.iter().enumerate() {
+            if len != 0 {
+                symbols[offsets[len as usize] as usize] = symbol as u16;
+                offsets[len as usize] += 1;
+            }
+        }
+
+        Huffman { counts, symbols }
+    }
+
+    fn decode(&self, reader: &mut BitReader) -> Result<u16, ImageError> {
+        let (mut code, mut first, mut index) = (0i32, 0i32, 0i32);
+        for len in 1..16 {
+            code |= reader.bits(1)? as i32;
+            let count = self.counts[len] as i32;
+            if code - first < count {
+                return Ok(self.symbols[(index + code - first) as usize]);
+            }
+            index += count;
+            first = (first + count) << 1;
+            code <<= 1;
+        }
+        Err(INVALID)
+    }
+}
+
+fn stored_block(reader: &mut BitReader, out: &mut Vec<u8>) -> Result<(), ImageError> {
+    reader.align();
+    let len = reader.bits(16)?;
+    let nlen = reader.bits(16)?;
+    if len != !nlen & 0xFFFF { return Err(INVALID); }
+
+    // the reader is byte aligned and the header was fully consumed, so the buffer is empty
+    let start = reader.pos;
+    let end = start + len as usize;
+    out.extend_from_slice(reader.data.get(start..end).ok_or(INVALID)?);
+    reader.pos = end;
+    Ok(())
+}
+
+fn fixed_tables() -> (Huffman, Huffman) {
+    let mut lengths = [0u8; 288];
+    lengths[..144].fill(8);
+    lengths[144..256].fill(9);
+    lengths[256..280].fill(7);
+    lengths[280..].fill(8);
+    (Huffman::new(&lengths), Huffman::new(&[5; 30]))
+}
+
+fn dynamic_tables(reader: &mut BitReader) -> Result<(Huffman, Huffman), ImageError> {
+    let hlit = reader.bits(5)? as usize + 257;
+    let hdist = reader.bits(5)? as usize + 1;
+    let hclen = reader.bits(4)? as usize + 4;
+
+    let mut code_lengths = [0u8; 19];
+    for &i in CODE_LENGTH_ORDER.iter().take(hclen) {
+        code_lengths[i] = reader.bits(3)? as u8;
+    }
+    let code_length_code = Huffman::new(&code_lengths);
+
+    // literal/length and distance code lengths are stored as one sequence
+    let mut lengths = vec![0u8; hlit + hdist];
+    let mut i = 0;
+    while i < lengths.len() {
+        let symbol = code_length_code.decode(reader)?;
+        let (value, repeat) = match symbol {
+            0..=15 => (symbol as u8, 1),
+            16 => (*lengths.get(i.wrapping_sub(1)).ok_or(INVALID)?, 3 + reader.bits(2)?),
+            17 => (0, 3 + reader.bits(3)?),
+            _ => (0, 11 + reader.bits(7)?),
+        };
+        let end = i + repeat as usize;
+        lengths.get_mut(i..end).ok_or(INVALID)?.fill(value);
+        i = end;
+    }
+
+    Ok((Huffman::new(&lengths[..hlit]), Huffman::new(&lengths[hlit..])))
+}
+
+fn huffman_block(reader: &mut BitReader, out: &mut Vec<u8>, lit: &Huffman, dist: &Huffman) -> Result<(), ImageError> {
+    loop {
+        let symbol = lit.decode(reader)? as usize;
+        match symbol {
+            0..=255 => out.push(symbol as u8),
+            256 => return Ok(()),
+            _ => {
+                let i = symbol - 257;
+                if i >= LENGTH_BASE.len() { return Err(INVALID); }
+                let len = LENGTH_BASE[i] as usize + reader.bits(LENGTH_EXTRA[i] as u32)? as usize;
+
+                let j = dist.decode(reader)? as usize;
+                if j >= DIST_BASE.len() { return Err(INVALID); }
+                let distance = DIST_BASE[j] as usize + reader.bits(DIST_EXTRA[j] as u32)? as usize;
+                if distance > out.len() { return Err(INVALID); }
+
+                // copy byte by byte, the source can overlap the bytes being written
+                let start = out.len() - distance;
+                for k in 0..len {
+                    out.push(out[start + k]);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_stored() {
+        // zlib header, final stored block with "abc"
+        let data = [0x78, 0x01, 0x01, 0x03, 0x00, 0xFC, 0xFF, b'a', b'b', b'c'];
+        assert_eq!(zlib_decompress(&data).unwrap(), b"abc");
+    }
+
+    #[test]
+    fn test_fixed() {
+        // "hello hello hello" compressed with zlib level 9
+        let data = [
+            0x78, 0xDA, 0xCB, 0x48, 0xCD, 0xC9, 0xC9, 0x57, 0xC8, 0x40, 0x90, 0x00, 0x3A, 0x2E, 0x06, 0x7D,
+        ];
+        assert_eq!(zlib_decompress(&data).unwrap(), b"hello hello hello");
+    }
+
+    #[test]
+    fn test_dynamic() {
+        // 200 pseudo random letters with skewed frequencies, compressed with zlib level 9
+        let data = [
+            0x78, 0xDA, 0x35, 0x4E, 0x89, 0x11, 0xC0, 0x20, 0x0C, 0x9A, 0x95, 0x67, 0xFF, 0x19, 0x1A, 0xA0,
+            0x46, 0x73, 0x12, 0x38, 0x30, 0xC0, 0x8A, 0x10, 0xAF, 0xDF, 0xB0, 0x2E, 0x97, 0x07, 0xBC, 0xCB,
+            0x49, 0x3E, 0x44, 0xEC, 0xDC, 0x50, 0x5E, 0xBF, 0xC6, 0xD9, 0xCA, 0x8A, 0x55, 0x60, 0x26, 0xD5,
+            0x4C, 0x46, 0x14, 0x25, 0xE1, 0x90, 0xE7, 0x90, 0xC3, 0xEA, 0xAD, 0xC1, 0xD9, 0x3D, 0xBC, 0x3F,
+            0x2B, 0xA8, 0x7B, 0x1C, 0x93, 0x70, 0x4D, 0xD2, 0x60, 0xEA, 0x03, 0xF1, 0x83, 0x4C, 0x48,
+        ];
+        let mut x = 1u32;
+        let expected: Vec<u8> = (0..200).map(|_| {
+            x = (x * 75 + 74) % 65537;
+            b"aaaaaaabbbcd"[x as usize % 12]
+        }).collect();
+        assert_eq!(zlib_decompress(&data).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_invalid() {
+        assert!(zlib_decompress(&[0x78, 0x01]).is_err());
+        assert!(zlib_decompress(&[0x00, 0x00, 0x00]).is_err());
+    }
+}
@@ -0,0 +1,454 @@
+use std::{collections::HashMap, error::Error, fmt, fs, io, path::Path, str::FromStr};
+
+#[cfg(feature = "gamepad")]
+use crate::core::GamepadButton;
+#[cfg(feature = "gamepad")]
+use crate::core::gamepad::BUTTONS;
+use crate::core::{Key, MouseButton, MOUSE_BUTTONS};
+
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+/// A key by its position on the keyboard, named after the key in that position on the US
+/// layout, so `PhysicalKey(Key::W)` is the key above `PhysicalKey(Key::S)` on every layout
+///
+/// Use it for controls like WASD movement that should stay in place on AZERTY or Dvorak
+/// keyboards, and [`Key`](enum.Key.html) for shortcuts that should follow the printed letter.
+/// Wayland doesn't tell where a key is, the keys of the layout are used there
+///
+/// ### Example
+/// ```no_run
+/// # use rain2d::core::*;
+/// # let mut core = RainCore::init("example app", 640, 360, true);
+/// # let mut y = 0.0;
+/// // z on a french keyboard
+/// if core.physical_key_down(PhysicalKey(Key::W)) {
+///     y -= 1.0;
+/// }
+/// ```
+pub struct PhysicalKey(pub Key);
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+/// Input that can trigger an action of an [`InputMap`](struct.InputMap.html)
+pub enum Binding {
+    /// Key on the keyboard, following the layout
+    Key(Key),
+
+    /// Key at a position on the keyboard, see [`PhysicalKey`](struct.PhysicalKey.html)
+    Physical(PhysicalKey),
+
+    /// Mouse button
+    Mouse(MouseButton),
+
+    /// Button on the gamepad with the index
+    #[cfg(feature = "gamepad")]
+    Gamepad(usize, GamepadButton),
+}
+
+#[derive(Debug)]
+/// Error returned when loading an input map fails
+pub enum InputMapError {
+    /// The file couldn't be read or written
+    Io(io::Error),
+
+    /// The text is not a valid input map, contains the line number starting at 1
+    Invalid(usize, &'static str),
+}
+
+impl fmt::Display for InputMapError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InputMapError::Io(e) => write!(f, "failed to read input map: {}", e),
+            InputMapError::Invalid(line, reason) => write!(f, "invalid input map on line {}: {}", line, reason),
+        }
+    }
+}
+
+impl Error for InputMapError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            InputMapError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for InputMapError {
+    fn from(e: io::Error) -> Self {
+        InputMapError::Io(e)
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Default)]
+/// Named actions that are triggered by any of their bindings, set it with [`set_input_map`]
+///
+/// Game logic asks for actions like "jump" instead of concrete keys so controls can be changed
+/// in one place or by the player
+///
+/// [`set_input_map`]: struct.RainCore.html#method.set_input_map
+///
+/// ### Example
+/// ```no_run
+/// # use rain2d::core::*;
+/// # let mut core = RainCore::init("example app", 640, 360, true);
+/// let mut input = InputMap::new();
+/// input.bind("jump", Binding::Key(Key::Space));
+/// input.bind("jump", Binding::Key(Key::W));
+/// input.bind("fire", Binding::Mouse(MouseButton::Left));
+/// core.set_input_map(input);
+///
+/// // every frame
+/// if core.action_pressed("jump") {
+///     println!("jump");
+/// }
+/// ```
+pub struct InputMap {
+    actions: HashMap<String, Vec<Binding>>,
+}
+
+impl InputMap {
+    /// Creates an input map without any actions
+    pub fn new() -> Self {
+        InputMap::default()
+    }
+
+    /// Adds a binding that triggers the action, actions are created when they are first bound
+    pub fn bind(&mut self, action: &str, binding: Binding) {
+        let bindings = self.actions.entry(action.to_string()).or_default();
+        if !bindings.contains(&binding) {
+            bindings.push(binding);
+        }
+    }
+
+    /// Removes a binding from the action
+    pub fn unbind(&mut self, action: &str, binding: Binding) {
+        if let Some(bindings) = self.actions.get_mut(action) {
+            bindings.retain(|&b| b != binding);
+        }
+    }
+
+    /// Removes all bindings of the action
+    pub fn clear(&mut self, action: &str) {
+        self.actions.remove(action);
+    }
+
+    /// Gets the bindings of the action, unknown actions don't have any
+    pub fn bindings(&self, action: &str) -> &[Binding] {
+        self.actions.get(action).map_or(&[], |bindings| bindings.as_slice())
+    }
+
+    /// Gets the names of all actions in no particular order
+    pub fn actions(&self) -> impl Iterator<Item = &str> {
+        self.actions.keys().map(String::as_str)
+    }
+
+    /// Loads an input map saved with [`save`](#method.save)
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// # let mut defaults = InputMap::new();
+    /// let input = InputMap::from_file("controls.txt").unwrap_or(defaults);
+    /// core.set_input_map(input);
+    /// ```
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, InputMapError> {
+        fs::read_to_string(path)?.parse()
+    }
+
+    /// Saves the input map as text with one action per line, like
+    /// `jump = Key Space, Physical W, Mouse Left, Gamepad 0 South`
+    ///
+    /// The text is also available with `to_string` and read back with `parse`. Action names
+    /// can't contain `=` or line breaks and surrounding spaces are removed
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), InputMapError> {
+        Ok(fs::write(path, self.to_string())?)
+    }
+}
+
+impl fmt::Display for InputMap {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // sorted so saving the same bindings always gives the same file
+        let mut actions: Vec<_> = self.actions.iter().collect();
+        actions.sort_by_key(|(action, _)| action.as_str());
+        for (action, bindings) in actions {
+            write!(f, "{} =", action)?;
+            for (i, binding) in bindings.iter().enumerate() {
+                let separator = if i == 0 { "" } else { "," };
+                match binding {
+                    Binding::Key(key) => write!(f, "{} Key {:?}", separator, key)?,
+                    Binding::Physical(PhysicalKey(key)) => write!(f, "{} Physical {:?}", separator, key)?,
+                    Binding::Mouse(button) => write!(f, "{} Mouse {:?}", separator, button)?,
+                    #[cfg(feature = "gamepad")]
+                    Binding::Gamepad(index, button) => write!(f, "{} Gamepad {} {:?}", separator, index, button)?,
+                }
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for InputMap {
+    type Err = InputMapError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut input = InputMap::new();
+        for (i, line) in s.lines().enumerate().filter(|(_, line)| !line.trim().is_empty()) {
+            let invalid = |reason| InputMapError::Invalid(i + 1, reason);
+            let (action, bindings) = line.split_once('=').ok_or(invalid("missing ="))?;
+            let action = action.trim();
+            if action.is_empty() {
+                return Err(invalid("missing action name"));
+            }
+
+            // actions without bindings are kept so they don't fall back to defaults
+            input.actions.entry(action.to_string()).or_default();
+            for binding in bindings.split(',').filter(|binding| !binding.trim().is_empty()) {
+                input.bind(action, parse_binding(binding).ok_or(invalid("invalid binding"))?);
+            }
+        }
+        Ok(input)
+    }
+}
+
+/// Gets the key with the name, the same name `{:?}` gives it, like `Space` or `NumPad1`
+pub fn key_from_name(name: &str) -> Option<Key> {
+    KEYS.iter().copied().find(|key| format!("{:?}", key) == name)
+}
+
+fn parse_binding(binding: &str) -> Option<Binding> {
+    let mut words = binding.split_whitespace();
+    let binding = match (words.next()?, words.next()?) {
+        ("Key", key) => Binding::Key(key_from_name(key)?),
+        ("Physical", key) => Binding::Physical(PhysicalKey(key_from_name(key)?)),
+        ("Mouse", "Left") => Binding::Mouse(MouseButton::Left),
+        ("Mouse", "Middle") => Binding::Mouse(MouseButton::Middle),
+        ("Mouse", "Right") => Binding::Mouse(MouseButton::Right),
+        #[cfg(feature = "gamepad")]
+        ("Gamepad", index) => {
+            let button = words.next()?;
+            let button = BUTTONS.iter().copied().find(|b| format!("{:?}", b) == button)?;
+            Binding::Gamepad(index.parse().ok()?, button)
+        }
+        _ => return None,
+    };
+    if words.next().is_some() { None } else { Some(binding) }
+}
+
+// every key that can be bound
+pub(crate) const KEYS: [Key; 106] = [
+    Key::Key0, Key::Key1, Key::Key2, Key::Key3, Key::Key4, Key::Key5, Key::Key6, Key::Key7,
+    Key::Key8, Key::Key9, Key::A, Key::B, Key::C, Key::D, Key::E, Key::F, Key::G, Key::H, Key::I,
+    Key::J, Key::K, Key::L, Key::M, Key::N, Key::O, Key::P, Key::Q, Key::R, Key::S, Key::T, Key::U,
+    Key::V, Key::W, Key::X, Key::Y, Key::Z, Key::F1, Key::F2, Key::F3, Key::F4, Key::F5, Key::F6,
+    Key::F7, Key::F8, Key::F9, Key::F10, Key::F11, Key::F12, Key::F13, Key::F14, Key::F15,
+    Key::Down, Key::Left, Key::Right, Key::Up, Key::Apostrophe, Key::Backquote, Key::Backslash,
+    Key::Comma, Key::Equal, Key::LeftBracket, Key::Minus, Key::Period, Key::RightBracket,
+    Key::Semicolon, Key::Slash, Key::Backspace, Key::Delete, Key::End, Key::Enter, Key::Escape,
+    Key::Home, Key::Insert, Key::Menu, Key::PageDown, Key::PageUp, Key::Pause, Key::Space, Key::Tab,
+    Key::NumLock, Key::CapsLock, Key::ScrollLock, Key::LeftShift, Key::RightShift, Key::LeftCtrl,
+    Key::RightCtrl, Key::NumPad0, Key::NumPad1, Key::NumPad2, Key::NumPad3, Key::NumPad4,
+    Key::NumPad5, Key::NumPad6, Key::NumPad7, Key::NumPad8, Key::NumPad9, Key::NumPadDot,
+    Key::NumPadSlash, Key::NumPadAsterisk, Key::NumPadMinus, Key::NumPadPlus, Key::NumPadEnter,
+    Key::LeftAlt, Key::RightAlt, Key::LeftSuper, Key::RightSuper,
+];
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+/// Change of a key passed to [`on_key_event`](trait.RainApp.html#method.on_key_event)
+pub enum KeyState {
+    /// The key was pressed
+    Pressed,
+
+    /// The key is held long enough to repeat, see
+    /// [`set_key_repeat`](struct.RainCore.html#method.set_key_repeat)
+    Repeated,
+
+    /// The key was released
+    Released,
+}
+
+// turns the keys reported for an update into events, presses come before repeats and releases
+pub fn key_events(pressed: &[Key], pressed_or_repeated: &[Key], released: &[Key]) -> Vec<(Key, KeyState)> {
+    let presses = pressed.iter().map(|&key| (key, KeyState::Pressed));
+    let repeats = pressed_or_repeated.iter()
+        .filter(|key| !pressed.contains(key))
+        .map(|&key| (key, KeyState::Repeated));
+    let releases = released.iter().map(|&key| (key, KeyState::Released));
+    presses.chain(repeats).chain(releases).collect()
+}
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+/// Mouse input passed to [`on_mouse_event`](trait.RainApp.html#method.on_mouse_event)
+pub enum MouseEvent {
+    /// The button was pressed
+    ButtonDown(MouseButton),
+
+    /// The button was released
+    ButtonUp(MouseButton),
+
+    /// The mouse moved to the position on the screen, see
+    /// [`get_mouse_pos`](struct.RainCore.html#method.get_mouse_pos)
+    Moved(f32, f32),
+
+    /// The scroll wheel moved horizontally and vertically
+    Wheel(f32, f32),
+}
+
+// turns the mouse state of the last and this update into events, movement comes first so
+// clicks happen at the new position
+pub fn mouse_events(previous: [bool; 3],
+                    buttons: [bool; 3],
+                    last: Option<(f32, f32)>,
+                    pos: Option<(f32, f32)>,
+                    wheel: Option<(f32, f32)>) -> Vec<MouseEvent> {
+    let mut events = Vec::new();
+    if let Some((x, y)) = pos.filter(|&pos| last != Some(pos)) {
+        events.push(MouseEvent::Moved(x, y));
+    }
+    for (i, &button) in MOUSE_BUTTONS.iter().enumerate() {
+        match (previous[i], buttons[i]) {
+            (false, true) => events.push(MouseEvent::ButtonDown(button)),
+            (true, false) => events.push(MouseEvent::ButtonUp(button)),
+            _ => {}
+        }
+    }
+    if let Some((x, y)) = wheel.filter(|&wheel| wheel != (0.0, 0.0)) {
+        events.push(MouseEvent::Wheel(x, y));
+    }
+    events
+}
+
+// drag with a mouse button, it becomes active once the mouse moved far enough from where the
+// button was pressed. started and ended are only set in the update it happened
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct Drag {
+    pub button: MouseButton,
+    pub origin: (f32, f32),
+    pub pos: (f32, f32),
+    pub active: bool,
+    pub started: bool,
+    pub ended: bool,
+}
+
+impl Drag {
+    pub fn new(button: MouseButton, pos: (f32, f32)) -> Self {
+        Drag { button, origin: pos, pos, active: false, started: false, ended: false }
+    }
+
+    // advances the drag to the next update, returns false when the drag is over. drags that
+    // never became active end without being reported
+    pub fn update(&mut self, down: bool, pos: Option<(f32, f32)>, threshold: f32) -> bool {
+        self.started = false;
+        if self.ended { return false; }
+        if !down {
+            self.ended = self.active;
+            return self.active;
+        }
+
+        if let Some(pos) = pos {
+            self.pos = pos;
+        }
+        let (dx, dy) = (self.pos.0 - self.origin.0, self.pos.1 - self.origin.1);
+        if !self.active && (dx * dx + dy * dy).sqrt() >= threshold {
+            self.active = true;
+            self.started = true;
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_bind() {
+        let mut input = InputMap::new();
+        input.bind("jump", Binding::Key(Key::Space));
+        input.bind("jump", Binding::Mouse(MouseButton::Left));
+        input.bind("jump", Binding::Key(Key::Space));
+        assert_eq!(input.bindings("jump"), &[Binding::Key(Key::Space), Binding::Mouse(MouseButton::Left)]);
+
+        input.unbind("jump", Binding::Key(Key::Space));
+        assert_eq!(input.bindings("jump"), &[Binding::Mouse(MouseButton::Left)]);
+        assert_eq!(input.actions().collect::<Vec<_>>(), vec!["jump"]);
+        input.clear("jump");
+        assert!(input.bindings("jump").is_empty());
+        assert!(input.bindings("fire").is_empty());
+    }
+
+    #[test]
+    fn test_save() {
+        let mut input = InputMap::new();
+        input.bind("jump", Binding::Key(Key::Space));
+        input.bind("jump", Binding::Mouse(MouseButton::Left));
+        input.bind("fire", Binding::Key(Key::NumPad0));
+        #[cfg(feature = "gamepad")]
+        input.bind("fire", Binding::Gamepad(1, GamepadButton::RightShoulder));
+        input.bind("up", Binding::Physical(PhysicalKey(Key::W)));
+        input.bind("unused", Binding::Key(Key::A));
+        input.unbind("unused", Binding::Key(Key::A));
+
+        let text = input.to_string();
+        #[cfg(feature = "gamepad")]
+        assert_eq!(text, "fire = Key NumPad0, Gamepad 1 RightShoulder\njump = Key Space, Mouse Left\n\
+                          unused =\nup = Physical W\n");
+        assert_eq!(text.parse::<InputMap>().unwrap(), input);
+    }
+
+    #[test]
+    fn test_parse() {
+        let input: InputMap = "\n  move left = Key A,Key Left \n".parse().unwrap();
+        assert_eq!(input.bindings("move left"), &[Binding::Key(Key::A), Binding::Key(Key::Left)]);
+        assert!(matches!("jump".parse::<InputMap>(), Err(InputMapError::Invalid(1, _))));
+        assert!(matches!("a = Key A\nb = Key Nope".parse::<InputMap>(), Err(InputMapError::Invalid(2, _))));
+        assert!("a = Mouse Left Right".parse::<InputMap>().is_err());
+        let input: InputMap = "up = Physical W, Key Up".parse().unwrap();
+        assert_eq!(input.bindings("up"), &[Binding::Physical(PhysicalKey(Key::W)), Binding::Key(Key::Up)]);
+        assert!("up = Physical Nope".parse::<InputMap>().is_err());
+        assert!(" = Key A".parse::<InputMap>().is_err());
+
+        assert!(KEYS.iter().all(|&key| key_from_name(&format!("{:?}", key)) == Some(key)));
+        assert_eq!(key_from_name("Unknown"), None);
+    }
+
+    #[test]
+    fn test_key_events() {
+        let events = key_events(&[Key::A], &[Key::A, Key::Left], &[Key::Space]);
+        assert_eq!(events, vec![(Key::A, KeyState::Pressed), (Key::Left, KeyState::Repeated), (Key::Space, KeyState::Released)]);
+        assert!(key_events(&[], &[], &[]).is_empty());
+    }
+
+    #[test]
+    fn test_mouse_events() {
+        let events = mouse_events([true, false, false], [false, false, true], Some((1.0, 2.0)), Some((3.0, 2.0)), Some((0.0, -1.0)));
+        assert_eq!(events, vec![
+            MouseEvent::Moved(3.0, 2.0),
+            MouseEvent::ButtonUp(MouseButton::Left),
+            MouseEvent::ButtonDown(MouseButton::Right),
+            MouseEvent::Wheel(0.0, -1.0),
+        ]);
+        assert!(mouse_events([false; 3], [false; 3], Some((1.0, 2.0)), Some((1.0, 2.0)), Some((0.0, 0.0))).is_empty());
+        assert!(mouse_events([false; 3], [false; 3], Some((1.0, 2.0)), None, None).is_empty());
+    }
+
+    #[test]
+    fn test_drag() {
+        let mut drag = Drag::new(MouseButton::Left, (10.0, 10.0));
+        assert!(drag.update(true, Some((12.0, 12.0)), 4.0));
+        assert!(!drag.active);
+        assert!(drag.update(true, Some((13.0, 14.0)), 4.0));
+        assert!(drag.active && drag.started);
+        // the last position is kept while the mouse is outside the window
+        assert!(drag.update(true, None, 4.0));
+        assert_eq!((drag.pos, drag.started), ((13.0, 14.0), false));
+        assert!(drag.update(false, Some((20.0, 20.0)), 4.0));
+        assert!(drag.ended);
+        assert!(!drag.update(false, None, 4.0));
+
+        // released before reaching the threshold
+        let mut click = Drag::new(MouseButton::Right, (0.0, 0.0));
+        assert!(click.update(true, Some((1.0, 0.0)), 4.0));
+        assert!(!click.update(false, None, 4.0));
+        assert!(!click.ended);
+    }
+}
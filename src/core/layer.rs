@@ -0,0 +1,16 @@
+use crate::core::RenderTarget;
+
+// render target that is drawn on top of or below the screen every frame
+pub struct Layer {
+    pub name: String,
+    pub target: RenderTarget,
+    pub order: i32,
+    pub visible: bool,
+    pub opacity: f32,
+}
+
+impl Layer {
+    pub fn new(name: &str, order: i32, width: usize, height: usize) -> Self {
+        Layer { name: name.to_string(), target: RenderTarget::new(width, height), order, visible: true, opacity: 1.0 }
+    }
+}
@@ -0,0 +1,178 @@
+// bresenham line clipped against a rectangle before rasterizing
+//
+// the line is stepped along its major axis, the offset along the minor axis at step i is
+// floor(i * m / n) which gives the same pixels as the bresenham crate, so the visible range
+// of steps can be solved for directly instead of walking the parts outside of the rectangle
+pub struct ClippedLine {
+    x: i64,
+    y: i64,
+    major: (i64, i64),
+    minor: (i64, i64),
+    n: i64,
+    m: i64,
+    rem: i64,
+    first: i64,
+    step: i64,
+    end: i64,
+}
+
+impl ClippedLine {
+    // pixels from start up to (but not including) end that are inside of the rectangle
+    // at (bx, by) with the specified dimensions
+    pub fn new(start: (i32, i32), end: (i32, i32), bx: i32, by: i32, width: i32, height: i32) -> Self {
+        let (dx, dy) = ((end.0 - start.0) as i64, (end.1 - start.1) as i64);
+        let (major, minor) = octant_axes(dx, dy);
+        let n = dx * major.0 + dy * major.1;
+        let m = dx * minor.0 + dy * minor.1;
+
+        // range of steps inside the rectangle on each axis
+        let (x0, y0) = (start.0 as i64, start.1 as i64);
+        let (mut first, mut last) = (0, n - 1);
+        for &(p0, major, minor, lo, hi) in [
+            (x0, major.0, minor.0, bx as i64, bx as i64 + width as i64 - 1),
+            (y0, major.1, minor.1, by as i64, by as i64 + height as i64 - 1),
+        ].iter() {
+            let (a, b) = if major != 0 {
+                step_range(p0, major, lo, hi)
+            } else {
+                minor_range(p0, minor, lo, hi, n, m)
+            };
+            first = first.max(a);
+            last = last.min(b);
+        }
+
+        let mut line = ClippedLine { x: x0, y: y0, major, minor, n, m, rem: 0, first, step: first, end: last + 1 };
+        if first <= last && first > 0 {
+            let offset = first * m / n;
+            line.rem = first * m - offset * n;
+            line.x += major.0 * first + minor.0 * offset;
+            line.y += major.1 * first + minor.1 * offset;
+        }
+        line
+    }
+
+    // index of the first visible pixel along the whole line
+    pub fn first_step(&self) -> u32 {
+        self.first.max(0) as u32
+    }
+
+    // number of pixels in the line without clipping
+    pub fn unclipped_len(&self) -> u32 {
+        self.n as u32
+    }
+}
+
+impl Iterator for ClippedLine {
+    type Item = (i32, i32);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.step >= self.end { return None; }
+        let p = (self.x as i32, self.y as i32);
+
+        self.step += 1;
+        self.x += self.major.0;
+        self.y += self.major.1;
+        self.rem += self.m;
+        if self.rem >= self.n {
+            self.rem -= self.n;
+            self.x += self.minor.0;
+            self.y += self.minor.1;
+        }
+        Some(p)
+    }
+}
+
+// unit steps along the major and minor axis, picked the same way as the bresenham crate
+// so ties are resolved identically
+fn octant_axes(dx: i64, dy: i64) -> ((i64, i64), (i64, i64)) {
+    let (mut ox, mut oy, mut octant) = (dx, dy, 0);
+    if oy < 0 {
+        ox = -ox;
+        oy = -oy;
+        octant += 4;
+    }
+    if ox < 0 {
+        let tmp = ox;
+        ox = oy;
+        oy = -tmp;
+        octant += 2;
+    }
+    if ox < oy {
+        octant += 1;
+    }
+
+    match octant {
+        0 => ((1, 0), (0, 1)),
+        1 => ((0, 1), (1, 0)),
+        2 => ((0, 1), (-1, 0)),
+        3 => ((-1, 0), (0, 1)),
+        4 => ((-1, 0), (0, -1)),
+        5 => ((0, -1), (-1, 0)),
+        6 => ((0, -1), (1, 0)),
+        _ => ((1, 0), (0, -1)),
+    }
+}
+
+// steps where p0 + dir * i is in lo..=hi
+fn step_range(p0: i64, dir: i64, lo: i64, hi: i64) -> (i64, i64) {
+    if dir > 0 { (lo - p0, hi - p0) } else { (p0 - hi, p0 - lo) }
+}
+
+// steps where p0 + dir * floor(i * m / n) is in lo..=hi
+fn minor_range(p0: i64, dir: i64, lo: i64, hi: i64, n: i64, m: i64) -> (i64, i64) {
+    // allowed range of the offset along the minor axis
+    let (a, b) = step_range(p0, dir, lo, hi);
+    if m == 0 {
+        return if a <= 0 && 0 <= b { (i64::MIN, i64::MAX) } else { (1, 0) };
+    }
+
+    // floor(i * m / n) >= a  <=>  i >= ceil(a * n / m)
+    // floor(i * m / n) <= b  <=>  i < ceil((b + 1) * n / m)
+    (div_ceil(a * n, m), div_ceil((b + 1) * n, m) - 1)
+}
+
+fn div_ceil(a: i64, b: i64) -> i64 {
+    let d = a.div_euclid(b);
+    if d * b == a { d } else { d + 1 }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use bresenham::Bresenham;
+
+    fn bresenham(start: (i32, i32), end: (i32, i32)) -> Vec<(i32, i32)> {
+        Bresenham::new((start.0 as isize, start.1 as isize), (end.0 as isize, end.1 as isize))
+            .map(|(x, y)| (x as i32, y as i32))
+            .collect()
+    }
+
+    #[test]
+    fn test_matches_bresenham() {
+        for &end in [(7, 3), (3, 7), (-3, 7), (-7, 3), (-7, -3), (-3, -7), (3, -7), (7, -3),
+                     (5, 5), (-5, 5), (0, 6), (6, 0), (0, -6), (-6, 0), (0, 0)].iter() {
+            let line: Vec<_> = ClippedLine::new((1, 2), end, -100, -100, 200, 200).collect();
+            assert_eq!(line, bresenham((1, 2), end), "line to {:?}", end);
+        }
+    }
+
+    #[test]
+    fn test_clipped() {
+        let lines = [((-20, -5), (30, 12)), ((25, -3), (-8, 14)), ((4, -40), (6, 50)), ((-50, 3), (50, 4))];
+        for &(start, end) in lines.iter() {
+            let expected: Vec<_> = bresenham(start, end).into_iter()
+                .filter(|&(x, y)| (0..10).contains(&x) && (0..8).contains(&y))
+                .collect();
+            let line = ClippedLine::new(start, end, 0, 0, 10, 8);
+            let first = line.first_step() as usize;
+            assert_eq!(bresenham(start, end)[first..].first(), expected.first());
+            assert_eq!(line.collect::<Vec<_>>(), expected, "line {:?} {:?}", start, end);
+        }
+    }
+
+    #[test]
+    fn test_outside() {
+        assert_eq!(ClippedLine::new((-5, -5), (-1, 20), 0, 0, 10, 10).count(), 0);
+        assert_eq!(ClippedLine::new((0, 20), (9, 11), 0, 0, 10, 10).count(), 0);
+    }
+}
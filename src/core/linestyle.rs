@@ -0,0 +1,74 @@
+#[derive(Debug, PartialEq, Copy, Clone, Default)]
+/// Pattern used when drawing styled lines
+pub enum LineStyle {
+    /// Continuous line
+    #[default]
+    Solid,
+
+    /// Dashes of `dash` pixels separated by `gap` pixels
+    Dashed {
+        /// Length of a dash in pixels
+        dash: u32,
+
+        /// Length of the gap between dashes in pixels
+        gap: u32
+    },
+
+    /// Single pixel dots separated by `gap` pixels
+    Dotted {
+        /// Length of the gap between dots in pixels
+        gap: u32
+    },
+}
+
+impl LineStyle {
+    /// Checks if the pixel at `step` along the line should be drawn
+    ///
+    /// ### Example
+    /// ```
+    ///# use rain2d::core::LineStyle;
+    /// let style = LineStyle::Dashed { dash: 4, gap: 2 };
+    /// assert!(style.is_drawn(3));
+    /// assert!(!style.is_drawn(4));
+    /// ```
+    pub fn is_drawn(&self, step: u32) -> bool {
+        match *self {
+            LineStyle::Solid => true,
+            LineStyle::Dashed { dash, gap } => {
+                let period = dash.saturating_add(gap);
+                period == 0 || step % period < dash
+            }
+            LineStyle::Dotted { gap } => step.is_multiple_of(gap.saturating_add(1)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_solid() {
+        assert!((0..10).all(|i| LineStyle::Solid.is_drawn(i)));
+    }
+
+    #[test]
+    fn test_dashed() {
+        let style = LineStyle::Dashed { dash: 3, gap: 2 };
+        let pattern: Vec<bool> = (0..10).map(|i| style.is_drawn(i)).collect();
+        assert_eq!(pattern, vec![true, true, true, false, false, true, true, true, false, false]);
+
+        let style = LineStyle::Dashed { dash: u32::MAX, gap: 1 };
+        assert!(style.is_drawn(0) && style.is_drawn(u32::MAX - 1));
+    }
+
+    #[test]
+    fn test_dotted() {
+        let style = LineStyle::Dotted { gap: 2 };
+        let pattern: Vec<bool> = (0..6).map(|i| style.is_drawn(i)).collect();
+        assert_eq!(pattern, vec![true, false, false, true, false, false]);
+
+        let style = LineStyle::Dotted { gap: u32::MAX };
+        assert!(style.is_drawn(0) && !style.is_drawn(1));
+    }
+}
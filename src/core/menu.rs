@@ -0,0 +1,132 @@
+// menus are kept by the engine so they can be added again when the window is recreated
+
+use minifb::{MENU_KEY_ALT, MENU_KEY_COMMAND, MENU_KEY_CTRL, MENU_KEY_SHIFT, MENU_KEY_WIN};
+
+use crate::core::Key;
+
+#[derive(Debug, PartialEq, Clone)]
+/// Menu in the menu bar of the window, add it with [`add_menu`]
+///
+/// Pressing an item calls [`on_menu`] with its id. Menus are shown on windows and macos, other
+/// platforms don't have a native menu bar
+///
+/// [`add_menu`]: struct.RainCore.html#method.add_menu
+/// [`on_menu`]: trait.RainApp.html#method.on_menu
+///
+/// ### Example
+/// ```no_run
+/// # use rain2d::core::*;
+/// # let mut core = RainCore::init("example app", 640, 360, true);
+/// const OPEN: usize = 1;
+/// const SAVE: usize = 2;
+/// const QUIT: usize = 3;
+///
+/// let mut file = Menu::new("File");
+/// file.add_item("Open", OPEN);
+/// file.add_item_with_shortcut("Save", SAVE, Key::S, &[Key::LeftCtrl]);
+/// file.add_separator();
+/// file.add_item("Quit", QUIT);
+/// core.add_menu(file);
+/// ```
+pub struct Menu {
+    name: String,
+    entries: Vec<MenuEntry>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+enum MenuEntry {
+    Item { label: String, id: usize, shortcut: Option<(Key, usize)> },
+    Separator,
+    SubMenu(Menu),
+}
+
+impl Menu {
+    /// Creates an empty menu
+    pub fn new(name: &str) -> Self {
+        Menu { name: name.to_string(), entries: Vec::new() }
+    }
+
+    /// Adds an item that calls [`on_menu`](trait.RainApp.html#method.on_menu) with the id
+    pub fn add_item(&mut self, label: &str, id: usize) {
+        self.entries.push(MenuEntry::Item { label: label.to_string(), id, shortcut: None });
+    }
+
+    /// Adds an item that can also be pressed with the key while the modifiers are down, modifiers
+    /// are the shift, control, alt and super keys
+    pub fn add_item_with_shortcut(&mut self, label: &str, id: usize, key: Key, modifiers: &[Key]) {
+        let shortcut = Some((key, modifier_flags(modifiers)));
+        self.entries.push(MenuEntry::Item { label: label.to_string(), id, shortcut });
+    }
+
+    /// Adds a line between items
+    pub fn add_separator(&mut self) {
+        self.entries.push(MenuEntry::Separator);
+    }
+
+    /// Adds a menu that opens from this one
+    pub fn add_sub_menu(&mut self, menu: Menu) {
+        self.entries.push(MenuEntry::SubMenu(menu));
+    }
+
+    // creates the minifb menu
+    pub(crate) fn build(&self) -> Option<minifb::Menu> {
+        let mut menu = minifb::Menu::new(&self.name).ok()?;
+        for entry in &self.entries {
+            match entry {
+                MenuEntry::Item { label, id, shortcut } => {
+                    let mut item = menu.add_item(label, *id);
+                    if let Some((key, modifiers)) = *shortcut {
+                        item = item.shortcut(key, modifiers);
+                    }
+                    item.build();
+                }
+                MenuEntry::Separator => menu.add_separator(),
+                MenuEntry::SubMenu(sub_menu) => {
+                    if let Some(built) = sub_menu.build() {
+                        menu.add_sub_menu(&sub_menu.name, &built);
+                    }
+                }
+            }
+        }
+        Some(menu)
+    }
+}
+
+// modifier flags of minifb, the super key is command on macos and the windows key elsewhere
+fn modifier_flags(modifiers: &[Key]) -> usize {
+    let super_key = if cfg!(target_os = "macos") { MENU_KEY_COMMAND } else { MENU_KEY_WIN };
+    modifiers.iter().fold(0, |flags, key| flags | match key {
+        Key::LeftShift | Key::RightShift => MENU_KEY_SHIFT,
+        Key::LeftCtrl | Key::RightCtrl => MENU_KEY_CTRL,
+        Key::LeftAlt | Key::RightAlt => MENU_KEY_ALT,
+        Key::LeftSuper | Key::RightSuper => super_key,
+        _ => 0,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_modifier_flags() {
+        assert_eq!(modifier_flags(&[]), 0);
+        assert_eq!(modifier_flags(&[Key::LeftCtrl, Key::RightShift]), MENU_KEY_CTRL | MENU_KEY_SHIFT);
+        assert_eq!(modifier_flags(&[Key::LeftAlt, Key::RightAlt, Key::A]), MENU_KEY_ALT);
+    }
+
+    #[test]
+    fn test_menu() {
+        let mut edit = Menu::new("Edit");
+        edit.add_item_with_shortcut("Undo", 1, Key::Z, &[Key::LeftCtrl]);
+        let mut recent = Menu::new("Recent");
+        recent.add_item("level1.map", 2);
+        edit.add_separator();
+        edit.add_sub_menu(recent.clone());
+        assert_eq!(edit.entries, vec![
+            MenuEntry::Item { label: "Undo".to_string(), id: 1, shortcut: Some((Key::Z, MENU_KEY_CTRL)) },
+            MenuEntry::Separator,
+            MenuEntry::SubMenu(recent),
+        ]);
+    }
+}
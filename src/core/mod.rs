@@ -1,28 +1,117 @@
 #![warn(missing_docs)]
+#![allow(clippy::too_many_arguments)]
 //! rain2d core functionality
 
-use minifb::{Window, WindowOptions, KeyRepeat, MouseMode};
-use bresenham::Bresenham;
+use minifb::{Window, KeyRepeat, MenuHandle};
 use std::{
     time::Duration,
     time::Instant,
-    mem::swap
+    mem::{swap, replace},
+    path::PathBuf,
+    thread,
+    f32::consts::TAU
 };
 
+pub use crate::core::animation::{Animation, LoopMode};
+pub use crate::core::blend::*;
+pub use crate::core::camera::Camera2D;
 pub use crate::core::color::*;
+pub use crate::core::drawlist::DrawList;
+pub use crate::core::font::{Font, FontError};
+#[cfg(feature = "gamepad")]
+pub use crate::core::gamepad::{Gamepad, GamepadAxis, GamepadButton};
+pub use crate::core::input::{Binding, InputMap, InputMapError, KeyState, MouseEvent, PhysicalKey, key_from_name};
+pub use crate::core::linestyle::*;
+pub use crate::core::menu::Menu;
+pub use crate::core::palette::{Palette, bayer};
+pub use crate::core::path::Path;
+pub use crate::core::rect::Rect;
+pub use crate::core::pattern::*;
+pub use crate::core::transform::Transform;
+pub use crate::core::rendertarget::RenderTarget;
+pub use crate::core::scaling::ScaleMode;
+pub use crate::core::sprite::{Sprite, Filter, Flip, ImageError};
+pub use crate::core::spritebatch::SpriteBatch;
+pub use crate::core::text::{TextAlign, TextStyle, VerticalAlign};
+pub use crate::core::window::WindowConfig;
 
 /// Reexported from minifb
 ///
+/// On windows and macos keys are physical positions named after the US layout, so `Key::W` is
+/// the key above `Key::S` on every layout. On linux they follow the layout because minifb only
+/// reports the symbol of a key there, use [`PhysicalKey`](struct.PhysicalKey.html) for positions
 pub use minifb::Key as Key;
 
 /// Reexported from minifb
 ///
 pub use minifb::MouseButton as MouseButton;
 
-use crate::core::rendertarget::*;
+/// Reexported from minifb
+///
+pub use minifb::CursorStyle as CursorStyle;
+
+/// Reexported from minifb
+///
+pub use minifb::MouseMode as MouseMode;
+
+/// Reexported from minifb, `Scale` in minifb
+///
+pub use minifb::Scale as WindowScale;
+
+#[cfg(feature = "clipboard")]
+use crate::core::clipboard::Clipboard;
+use crate::core::curve::*;
+use crate::core::filedrop::FileDrop;
+use crate::core::font::{FontKind, Pen};
+#[cfg(feature = "gamepad")]
+use crate::core::gamepad::Gamepads;
+use crate::core::input::Drag;
+use crate::core::layer::Layer;
+use crate::core::line::ClippedLine;
+use crate::core::textinput::TextInput;
 
+mod animation;
+mod bitmapfont;
+mod blend;
+mod bmp;
+mod camera;
+#[cfg(feature = "clipboard")]
+mod clipboard;
 mod color;
+mod curve;
+mod drawlist;
+mod filedrop;
+mod font;
+#[cfg(feature = "gamepad")]
+mod gamepad;
+#[cfg(feature = "gif")]
+mod gif;
+#[cfg(feature = "png")]
+mod inflate;
+mod input;
+mod layer;
+mod line;
+mod linestyle;
+mod menu;
+mod palette;
+mod path;
+mod pattern;
+#[cfg(feature = "png")]
+mod png;
+#[cfg(feature = "truetype")]
+mod raster;
+mod rect;
+mod scaling;
+mod transform;
 mod rendertarget;
+mod sprite;
+mod spritebatch;
+mod text;
+mod textinput;
+pub mod texture;
+#[cfg(feature = "truetype")]
+mod truetype;
+mod window;
 
 #[allow(unused_variables)]
 /// Trait used to call event functions from main loop
@@ -65,12 +154,111 @@ pub trait RainApp {
     /// `dt` is the time since the last update
     fn on_update(&mut self, rain: &mut RainCore, dt: Duration) {}
 
+    /// Called before the next update when the window was resized
+    ///
+    /// Without a logical size the screen already has the new size, see
+    /// [`set_logical_size`](struct.RainCore.html#method.set_logical_size)
+    fn on_resize(&mut self, width: usize, height: usize) {}
+
+    /// Called before the next update for every key that was pressed, repeated or released
+    ///
+    /// Keys are only checked once per update so changes within an update are reported in the
+    /// order pressed, repeated, released
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # struct App { typed: Vec<Key> }
+    /// impl RainApp for App {
+    ///     fn on_key_event(&mut self, key: Key, state: KeyState) {
+    ///         if state != KeyState::Released {
+    ///             self.typed.push(key);
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    fn on_key_event(&mut self, key: Key, state: KeyState) {}
+
+    /// Called before the next update for every change of the mouse position, buttons and scroll
+    /// wheel, in that order
+    ///
+    /// The mouse is only checked once per update so buttons that are pressed and released
+    /// between two updates aren't reported
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # struct App { zoom: f32 }
+    /// impl RainApp for App {
+    ///     fn on_mouse_event(&mut self, event: MouseEvent) {
+    ///         if let MouseEvent::Wheel(_, y) = event {
+    ///             self.zoom *= 1.1f32.powf(y);
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    fn on_mouse_event(&mut self, event: MouseEvent) {}
+
+    /// Called before the next update when a gamepad was connected, see
+    /// [`gamepad`](struct.RainCore.html#method.gamepad)
+    ///
+    /// A gamepad keeps its index until it's disconnected, so it can be used as player number
+    #[cfg(feature = "gamepad")]
+    fn on_gamepad_connected(&mut self, index: usize) {}
+
+    /// Called before the next update when a gamepad was disconnected
+    ///
+    /// Used to pause the game until the player reconnects
+    #[cfg(feature = "gamepad")]
+    fn on_gamepad_disconnected(&mut self, index: usize) {}
+
+    /// Called before the next update when an item of a [`Menu`](struct.Menu.html) was pressed
+    fn on_menu(&mut self, id: usize) {}
+
+    /// Called before the next update when the window got focus
+    fn on_focus_gained(&mut self) {}
+
+    /// Called before the next update when the window lost focus
+    ///
+    /// Used to pause the game while the window is in the background
+    fn on_focus_lost(&mut self) {}
+
+    /// Called before the next update when the mouse moved into the window
+    fn on_mouse_enter(&mut self) {}
+
+    /// Called before the next update when the mouse left the window
+    fn on_mouse_leave(&mut self) {}
+
+    /// Called before the next update for every file dropped on the window
+    ///
+    /// Supported on X11, windows and macos, wayland doesn't report drops
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # use std::path::PathBuf;
+    /// struct Viewer { image: Option<Sprite> }
+    ///
+    /// impl RainApp for Viewer {
+    ///     fn on_file_dropped(&mut self, path: PathBuf) {
+    ///         if let Ok(sprite) = Sprite::from_bmp(&path) {
+    ///             self.image = Some(sprite);
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    fn on_file_dropped(&mut self, path: PathBuf) {}
+
     /// Called before the application exits
     ///
     /// Used to clean up before exiting the main application
     fn on_exit(&mut self) {}
 }
 
+/// Function that gets the location and color of a pixel and returns the color to show, see
+/// [`set_post_process`](struct.RainCore.html#method.set_post_process)
+pub type PostProcess = Box<dyn FnMut(i32, i32, Color) -> Color>;
+
 /// Engine state
 pub struct RainCore {
     /// Sets if the application should exit when the escape key is pressed
@@ -79,11 +267,87 @@ pub struct RainCore {
     active: bool,
     window_title: String,
     window: Option<Window>,
+    window_config: WindowConfig,
+    file_drop: Option<FileDrop>,
+    // size of a screen pixel in window pixels, set by the scale of the window config
+    window_scale: usize,
+    // desktop size while fullscreen and the size to go back to
+    fullscreen: Option<(usize, usize)>,
+    window_position: Option<(isize, isize)>,
+    // menus in the menu bar with their handles in the current window
+    menus: Vec<Menu>,
+    menu_handles: Vec<MenuHandle>,
+    // how much the desktop scales windows up, read when the window is created
+    dpi_scale: f32,
+    // pixels of the screen per window pixel, above 1 when rendering at native resolution on a
+    // desktop that measures windows in scaled units
+    pixel_ratio: f32,
+    windowed_size: (usize, usize),
     screen_width: usize,
     screen_height: usize,
     render_target: RenderTarget,
+    // scales the screen to the window when it has a logical size, output holds the scaled frame
+    scale_mode: Option<ScaleMode>,
+    output: RenderTarget,
+    layers: Vec<Layer>,
+    active_layer: Option<usize>,
+    composite: RenderTarget,
+    // size of a pixel in pixels of the render target, the frame is downsampled before presenting
+    supersample: usize,
+    // size of the blocks thin outlines are drawn with while supersampling, see draw_logical
+    block: i32,
+    downsampled: RenderTarget,
+    // target that holds the frame shown by the last present
+    frame: Frame,
+    palette: Option<Palette>,
+    post_process: Option<PostProcess>,
+    dither: Option<Palette>,
+    camera: Option<Camera2D>,
+    // transform set by the user, transform combines it with the camera and is used for drawing
+    local_transform: Transform,
+    transform: Transform,
+    transform_stack: Vec<Transform>,
+    // frame rate the main loop is limited to and when the last frame was due
+    target_fps: Option<u32>,
+    update_rate: Option<Duration>,
+    frame_deadline: Option<Instant>,
     frame_timer: f32,
     frame_count: u32,
+    // frames shown in the last second, added to the title when title_fps is set
+    fps: u32,
+    title_fps: bool,
+    #[cfg(feature = "gamepad")]
+    gamepads: Gamepads,
+    text_input: TextInput,
+    #[cfg(feature = "clipboard")]
+    clipboard: Clipboard,
+    input_map: InputMap,
+    // keys by their position that are down in this and the last update
+    physical_keys: Vec<Key>,
+    physical_last: Vec<Key>,
+    // state of the left, middle and right mouse button in this and the last update
+    mouse_buttons: [bool; 3],
+    mouse_previous: [bool; 3],
+    // mouse position in this and the last update
+    mouse_pos: Option<(f32, f32)>,
+    mouse_last: Option<(f32, f32)>,
+    focused: bool,
+    mouse_inside: bool,
+    cursor_visible: bool,
+    cursor_style: CursorStyle,
+    // if the cursor should be grabbed and if it is, it's released while the window has no focus
+    cursor_grab: bool,
+    cursor_grabbed: bool,
+    // where a grabbed cursor was moved to, the next mouse delta starts there
+    mouse_warped: Option<(f32, f32)>,
+    mouse_mode: MouseMode,
+    drag: Option<Drag>,
+    // how long pressed actions are kept and when every kept action was pressed
+    input_buffer: Option<Duration>,
+    buffered: Vec<(String, Instant)>,
+    drag_threshold: f32,
+    // time before a held key repeats and time between repeats
+    key_repeat: (Duration, Duration),
 }
 
 impl RainCore {
@@ -103,16 +367,89 @@ impl RainCore {
     ///         true);
     /// ```
     pub fn init(window_title: &str, width: usize, height: usize, exit_on_esc: bool) -> Self {
+        RainCore::init_with_config(window_title, width, height, exit_on_esc, WindowConfig::default())
+    }
+
+    /// Initializes the engine like [`init`](#method.init) with options for the window
+    ///
+    /// ### Example
+    /// ```no_run
+    /// use rain2d::core::*;
+    ///
+    /// let config = WindowConfig { borderless: true, topmost: true, ..WindowConfig::default() };
+    /// let mut core = RainCore::init_with_config("example app", 640, 360, true, config);
+    /// ```
+    pub fn init_with_config(window_title: &str,
+                            width: usize,
+                            height: usize,
+                            exit_on_esc: bool,
+                            config: WindowConfig) -> Self {
         RainCore {
             exit_on_esc,
             active: true,
             window_title: window_title.to_string(),
             window: None,
+            window_config: config,
+            file_drop: None,
+            window_scale: 1,
+            fullscreen: None,
+            window_position: None,
+            menus: Vec::new(),
+            menu_handles: Vec::new(),
+            dpi_scale: 1.0,
+            pixel_ratio: 1.0,
+            windowed_size: (width, height),
             render_target: RenderTarget::new(width, height),
+            scale_mode: None,
+            output: RenderTarget::new(0, 0),
+            layers: Vec::new(),
+            active_layer: None,
+            composite: RenderTarget::new(0, 0),
+            supersample: 1,
+            block: 1,
+            downsampled: RenderTarget::new(0, 0),
+            frame: Frame::Screen,
+            palette: None,
+            post_process: None,
+            dither: None,
+            camera: None,
+            local_transform: Transform::IDENTITY,
             screen_width: width,
             screen_height: height,
+            transform: Transform::IDENTITY,
+            transform_stack: Vec::new(),
+            target_fps: None,
+            update_rate: Some(Duration::from_millis(4)),
+            frame_deadline: None,
             frame_timer: 1.0,
             frame_count: 0,
+            fps: 0,
+            title_fps: true,
+            #[cfg(feature = "gamepad")]
+            gamepads: Gamepads::default(),
+            text_input: TextInput::default(),
+            #[cfg(feature = "clipboard")]
+            clipboard: Clipboard::default(),
+            input_map: InputMap::new(),
+            physical_keys: Vec::new(),
+            physical_last: Vec::new(),
+            mouse_buttons: [false; 3],
+            mouse_previous: [false; 3],
+            mouse_pos: None,
+            mouse_last: None,
+            focused: true,
+            mouse_inside: false,
+            cursor_visible: true,
+            cursor_grab: false,
+            cursor_grabbed: false,
+            mouse_warped: None,
+            cursor_style: CursorStyle::Arrow,
+            mouse_mode: MouseMode::Pass,
+            drag: None,
+            input_buffer: None,
+            buffered: Vec::new(),
+            drag_threshold: 4.0,
+            key_repeat: (Duration::from_millis(250), Duration::from_millis(50)),
         }
     }
 
@@ -135,10 +472,8 @@ impl RainCore {
     /// core.run(&mut ExampleApp {});
     /// ```
     pub fn run(&mut self, app: &mut dyn RainApp) {
-        self.window = Some(Window::new(&self.window_title,
-                                 self.screen_width,
-                                 self.screen_height,
-                                 WindowOptions::default()).unwrap());
+        self.windowed_size = (self.screen_width, self.screen_height);
+        self.create_window();
 
         app.on_start();
 
@@ -148,16 +483,73 @@ impl RainCore {
             let elapsed = current_time - last_time;
             last_time = current_time;
 
+            // react to the window being resized
+            let size = self.window.as_ref().map(|window| window.get_size());
+            if let Some((width, height)) = size.map(|(w, h)| self.window_size_to_screen(w, h)) {
+                if self.resize_window(width, height) {
+                    app.on_resize(width, height);
+                }
+            }
+            let state = self.window.as_mut()
+                .map(|window| (window.is_active(), window.get_mouse_pos(MouseMode::Discard).is_some()));
+            if let Some((focused, mouse_inside)) = state {
+                self.update_window_state(app, focused, mouse_inside);
+            }
+
+            #[cfg(feature = "gamepad")]
+            for (index, connected) in self.gamepads.update() {
+                if connected {
+                    app.on_gamepad_connected(index);
+                } else {
+                    app.on_gamepad_disconnected(index);
+                }
+            }
+            if let Some(id) = self.window.as_mut().and_then(|window| window.is_menu_pressed()) {
+                app.on_menu(id);
+            }
+            for path in self.file_drop.as_mut().map(FileDrop::take).unwrap_or_default() {
+                app.on_file_dropped(path);
+            }
+            if let Some(window) = &self.window {
+                if window.is_key_pressed(Key::Backspace, KeyRepeat::Yes) {
+                    self.text_input.push(textinput::BACKSPACE);
+                }
+                let keys = |keys: Option<Vec<Key>>| keys.unwrap_or_default();
+                let events = input::key_events(&keys(window.get_keys_pressed(KeyRepeat::No)),
+                                               &keys(window.get_keys_pressed(KeyRepeat::Yes)),
+                                               &keys(window.get_keys_released()));
+                for (key, state) in events {
+                    app.on_key_event(key, state);
+                }
+                self.mouse_previous = self.mouse_buttons;
+                self.mouse_buttons = MOUSE_BUTTONS.map(|button| window.get_mouse_down(button));
+                // the keymap of X11 holds keys pressed in other windows too
+                let physical = match self.focused {
+                    true => window::physical_keys(window).or_else(|| window.get_keys()).unwrap_or_default(),
+                    false => Vec::new(),
+                };
+                self.physical_last = replace(&mut self.physical_keys, physical);
+            }
+            self.mouse_last = self.mouse_warped.take().or(self.mouse_pos);
+            self.mouse_pos = self.get_mouse_pos_mode(MouseMode::Pass);
+            self.update_cursor_grab();
+            self.update_drag();
+            self.update_input_buffer(Instant::now());
+            let wheel = self.get_scroll_wheel();
+            for event in input::mouse_events(self.mouse_previous, self.mouse_buttons, self.mouse_last, self.mouse_pos, wheel) {
+                app.on_mouse_event(event);
+            }
+
             // update state
             app.on_update(self, elapsed);
 
-            // draw to screen
-            if let Some(window) = &mut self.window {
-                window.update_with_buffer(&self.render_target.data,
-                                               self.render_target.width,
-                                               self.render_target.height).unwrap();
-
+            // draw to screen, the window only processes events when nothing changed
+            if self.take_dirty() || self.post_process.is_some() {
+                self.present();
+            } else if let Some(window) = &mut self.window {
+                window.update();
             }
+            self.wait_for_frame();
 
             // update frame count
             self.frame_timer += elapsed.as_secs_f32();
@@ -165,9 +557,9 @@ impl RainCore {
             if self.frame_timer >= 1.0 {
                 self.frame_timer -= 1.0;
 
-                if let Some(window) = &mut self.window {
-                    let title = format!("{} - FPS: {}", self.window_title, self.frame_count);
-                    window.set_title(&title);
+                self.fps = self.frame_count;
+                if self.title_fps {
+                    self.update_title();
                 }
 
                 self.frame_count = 0;
@@ -185,6 +577,51 @@ impl RainCore {
             }
         }
 
+        self.set_cursor_grab(false);
+        self.update_cursor_grab();
+        app.on_exit();
+    }
+
+    /// Runs the application for a number of frames without opening a window, for tests and
+    /// machines without a display
+    ///
+    /// Every frame gets the same time step of [`target fps`], or 60 frames per second without
+    /// it, and is rendered as usual without waiting. There is no keyboard, mouse or gamepad
+    /// input. Stops early when [`exit`] is called
+    ///
+    /// [`target fps`]: #method.set_target_fps
+    /// [`exit`]: #method.exit
+    ///
+    /// ### Example
+    /// ```
+    /// # use rain2d::core::*;
+    /// # use std::time::Duration;
+    /// struct App { frames: u32 }
+    ///
+    /// impl RainApp for App {
+    ///     fn on_update(&mut self, rain: &mut RainCore, dt: Duration) {
+    ///         self.frames += 1;
+    ///         rain.clear(BLACK);
+    ///         rain.fill_rect(0, 0, 4, 4, RED);
+    ///     }
+    /// }
+    ///
+    /// let mut core = RainCore::init("test", 64, 64, false);
+    /// let mut app = App { frames: 0 };
+    /// core.run_frames(3, &mut app);
+    /// assert_eq!(app.frames, 3);
+    /// assert_eq!(core.get_frame().get_pixel(1, 1), Some(RED));
+    /// ```
+    pub fn run_frames(&mut self, frames: usize, app: &mut dyn RainApp) {
+        let dt = Duration::from_secs(1) / self.target_fps.unwrap_or(60);
+        app.on_start();
+        for _ in 0..frames {
+            if !self.active { break; }
+            app.on_update(self, dt);
+            if self.take_dirty() || self.post_process.is_some() {
+                self.present();
+            }
+        }
         app.on_exit();
     }
 
@@ -215,356 +652,5856 @@ impl RainCore {
         self.active = false;
     }
 
-    /// Checks if the key is currently down
+    /// Draws on a screen of `width` by `height` pixels that is scaled to fill the window, the
+    /// window keeps its size
+    ///
+    /// Mouse positions are converted to the logical size, everything on the screen and on layers is
+    /// kept as far as it fits
     ///
     /// ### Example
     /// ```no_run
     /// # use rain2d::core::*;
-    /// # let core = RainCore::init("example app", 640, 360, true);
-    /// if core.key_down(Key::Space) {
-    ///     println!("Spacebar down");
-    /// }
+    /// let mut core = RainCore::init("example app", 1280, 720, true);
+    /// // every pixel is drawn as a 4x4 square
+    /// core.set_logical_size(320, 180, ScaleMode::Integer);
     /// ```
-    pub fn key_down(&self, key: Key) -> bool {
-        if let Some(window) = &self.window {
-            return window.is_key_down(key);
-        }
-        false
-    }
-
-    /// Checks if the key was pressed (not held) since the last update
-    pub fn key_pressed(&self, key: Key) -> bool {
-        if let Some(window) = &self.window {
-            return window.is_key_pressed(key, KeyRepeat::No);
-        }
-        false
+    pub fn set_logical_size(&mut self, width: usize, height: usize, mode: ScaleMode) {
+        self.scale_mode = Some(mode);
+        self.resize_screen(width, height);
+        self.render_target.dirty = true;
     }
 
-    /// Checks if the key was released since the last update
-    pub fn key_released(&self, key: Key) -> bool {
-        if let Some(window) = &self.window {
-            window.is_key_released(key);
-        }
-        false
+    /// Gets the size of the screen that is drawn on
+    pub fn get_logical_size(&self) -> (usize, usize) {
+        (self.render_target.width / self.supersample, self.render_target.height / self.supersample)
     }
 
-    /// Gets all keys that are currently down
+    /// Draws everything at twice the resolution and averages every 2x2 block when the screen is
+    /// shown, smoothing the edges of all primitives at once
+    ///
+    /// Drawing four times as many pixels is slower, which suits screenshots and slower paced apps.
+    /// Coordinates stay the same, but the screen of [`pixels`] and layers has twice the size.
+    /// Everything drawn before is scaled to the new size
+    ///
+    /// [`pixels`]: #method.pixels
     ///
     /// ### Example
     /// ```no_run
     /// # use rain2d::core::*;
     /// # let mut core = RainCore::init("example app", 640, 360, true);
-    /// core.get_keys().map(|keys| {
-    ///     for key in keys {
-    ///         match key {
-    ///             Key::A => println!("A key down"),
-    ///             Key::Escape => core.exit(),
-    ///             _ => (),
-    ///         }
-    ///     }
-    /// });
+    /// core.set_supersampling(true);
+    /// core.fill_circle(320, 180, 100, WHITE);
     /// ```
-    pub fn get_keys(&self) -> Option<Vec<Key>> {
-        if let Some(window) = &self.window {
-            return window.get_keys();
+    pub fn set_supersampling(&mut self, enabled: bool) {
+        let supersample = if enabled { 2 } else { 1 };
+        if supersample == self.supersample { return; }
+        let ((width, height), clip_rect) = (self.get_logical_size(), self.get_clip_rect());
+
+        // keep what was drawn by drawing it at the new size
+        let old = self.supersample;
+        let rescale = |target: &mut RenderTarget| {
+            let mut scaled = RenderTarget::new(width * supersample, height * supersample);
+            if supersample > old {
+                let viewport = Rect::new(0, 0, scaled.width as i32, scaled.height as i32);
+                scaling::scale_into(target, &mut scaled, viewport);
+            } else {
+                scaling::downsample(target, &mut scaled, old);
+            }
+            target.resize(scaled.width, scaled.height);
+            target.data = scaled.data;
+        };
+        rescale(&mut self.render_target);
+        for layer in &mut self.layers {
+            rescale(&mut layer.target);
         }
-        None
+
+        self.supersample = supersample;
+        self.set_clip_rect(clip_rect);
+        self.update_transform();
     }
 
-    /// Get mouse position relative to the window, (0, 0) in upper left corner
+    /// Sets the title of the window
     ///
     /// ### Example
     /// ```no_run
     /// # use rain2d::core::*;
-    /// # let core = RainCore::init("example app", 640, 360, true);
-    /// if let Some((x, y)) = core.get_mouse_pos() {
-    ///     println!("x: {}, y: {}", x, y);
-    /// }
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// # let score = 1200;
+    /// core.set_title(&format!("example app - score: {}", score));
     /// ```
-    pub fn get_mouse_pos(&self) -> Option<(f32, f32)> {
-        if let Some(window) = &self.window {
-            return window.get_mouse_pos(MouseMode::Pass);
+    pub fn set_title(&mut self, title: &str) {
+        if title == self.window_title { return; }
+        self.window_title = title.to_string();
+        self.update_title();
+    }
+
+    /// Sets if the frame rate is added to the title, like "example app - FPS: 60", on by default
+    pub fn set_title_fps(&mut self, show: bool) {
+        self.title_fps = show;
+        self.update_title();
+    }
+
+    /// Gets the number of frames shown in the last second
+    pub fn get_fps(&self) -> u32 {
+        self.fps
+    }
+
+    // title with the frame rate, once it has been measured
+    fn title(&self) -> String {
+        if self.title_fps && self.fps > 0 {
+            format!("{} - FPS: {}", self.window_title, self.fps)
+        } else {
+            self.window_title.clone()
         }
-        None
     }
 
-    /// Checks if the button is currently down
-    ///
-    /// ### Example
-    /// ```no_run
-    /// # use rain2d::core::*;
-    /// # let core = RainCore::init("example app", 640, 360, true);
-    /// if core.mouse_button_down(MouseButton::Left) {
-    ///     println!("Left mouse button down");
-    /// }
-    /// ```
-    pub fn mouse_button_down(&self, button: MouseButton) -> bool {
-        if let Some(window) = &self.window {
-            return window.get_mouse_down(button);
+    fn update_title(&mut self) {
+        let title = self.title();
+        if let Some(window) = &mut self.window {
+            window.set_title(&title);
         }
-        false
     }
 
-    /// Get current scroll wheel movement
+    /// Limits how many frames are shown per second, with `None` the [`update rate`] is used
+    ///
+    /// The main loop sleeps between frames and waits the last millisecond without sleeping so the
+    /// frame rate stays accurate. The update rate is ignored while there is a target frame rate
+    ///
+    /// [`update rate`]: #method.set_update_rate
     ///
     /// ### Example
     /// ```no_run
     /// # use rain2d::core::*;
-    /// # let core = RainCore::init("example app", 640, 360, true);
-    /// if let Some((x, y)) = core.get_scroll_wheel() {
-    ///     println!("x: {}, y: {}", x, y);
-    /// }
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// core.set_target_fps(Some(60));
     /// ```
-    pub fn get_scroll_wheel(&self) -> Option<(f32,f32)> {
-        if let Some(window) = &self.window {
-            return window.get_scroll_wheel();
+    pub fn set_target_fps(&mut self, fps: Option<u32>) {
+        self.target_fps = fps.filter(|&fps| fps > 0);
+        self.frame_deadline = None;
+        let rate = self.window_update_rate();
+        if let Some(window) = &mut self.window {
+            window.limit_update_rate(rate);
         }
-        None
     }
 
-    /// Clears the screen with the provided color
+    /// Gets the frame rate the main loop is limited to, see
+    /// [`set_target_fps`](#method.set_target_fps)
+    pub fn get_target_fps(&self) -> Option<u32> {
+        self.target_fps
+    }
+
+    /// Sets the shortest time between two frames, defaults to 4 ms which is at most 250 frames
+    /// per second. `None` shows frames as fast as possible for the lowest latency
+    ///
+    /// minifb sleeps when a frame is shown too early, [`set_target_fps`] is more accurate and
+    /// replaces the update rate while it's set. There is no vsync so frames can tear either way
+    ///
+    /// [`set_target_fps`]: #method.set_target_fps
     ///
     /// ### Example
     /// ```no_run
     /// # use rain2d::core::*;
     /// # let mut core = RainCore::init("example app", 640, 360, true);
-    /// core.clear(NONE);
+    /// // benchmark without any limit
+    /// core.set_update_rate(None);
     /// ```
-    pub fn clear(&mut self, color: Color) {
-        self.render_target.clear(color);
+    pub fn set_update_rate(&mut self, rate: Option<Duration>) {
+        self.update_rate = rate;
+        let rate = self.window_update_rate();
+        if let Some(window) = &mut self.window {
+            window.limit_update_rate(rate);
+        }
     }
 
-    /// Draws a pixel if the location is in bounds
+    /// Gets the shortest time between two frames, see [`set_update_rate`](#method.set_update_rate)
+    pub fn get_update_rate(&self) -> Option<Duration> {
+        self.update_rate
+    }
+
+    // update rate of minifb, which is turned off when the main loop limits the frame rate itself
+    fn window_update_rate(&self) -> Option<Duration> {
+        match self.target_fps {
+            Some(_) => None,
+            None => self.update_rate,
+        }
+    }
+
+    // waits until the next frame is due when there is a target frame rate
+    fn wait_for_frame(&mut self) {
+        let fps = match self.target_fps {
+            Some(fps) => fps,
+            None => return,
+        };
+        let deadline = next_frame_deadline(self.frame_deadline, Instant::now(), fps);
+        self.frame_deadline = Some(deadline);
+
+        // sleeping can take a lot longer than asked for, so the end is spent spinning
+        let spin = Duration::from_millis(1);
+        loop {
+            let left = deadline.saturating_duration_since(Instant::now());
+            if left.is_zero() { break; }
+            if left > spin {
+                thread::sleep(left - spin);
+            } else {
+                std::hint::spin_loop();
+            }
+        }
+    }
+
+    /// Switches between a borderless window covering the desktop and a normal window, the
+    /// window is recreated and the screen keeps its contents
+    ///
+    /// Returns whether the window is now in the requested mode. Going fullscreen fails when the
+    /// size of the desktop can't be found: minifb can't make windows fullscreen itself, so the
+    /// window is sized to the X11 screen on linux, the primary monitor on windows and the main
+    /// display on macos. Wayland has no way to ask for it and always returns `false`. Fullscreen
+    /// windows aren't scaled, use [`set_logical_size`] to keep the size of the screen
+    ///
+    /// [`set_logical_size`]: #method.set_logical_size
     ///
     /// ### Example
     /// ```no_run
     /// # use rain2d::core::*;
     /// # let mut core = RainCore::init("example app", 640, 360, true);
-    /// core.draw(10, 10, WHITE);
+    /// if core.key_chord_pressed(&[Key::LeftAlt, Key::Enter]) {
+    ///     let fullscreen = core.is_fullscreen();
+    ///     if !core.set_fullscreen(!fullscreen) {
+    ///         eprintln!("fullscreen isn't supported here");
+    ///     }
+    /// }
     /// ```
-    pub fn draw(&mut self, x: i32, y: i32, color: Color) {
-        self.render_target.set_pixel(x, y, color);
+    pub fn set_fullscreen(&mut self, fullscreen: bool) -> bool {
+        if fullscreen == self.fullscreen.is_some() { return true; }
+        if fullscreen {
+            let desktop = match window::desktop_size(self.window.as_ref()) {
+                Some(size) => size,
+                None => return false,
+            };
+            if self.window.is_some() {
+                self.windowed_size = (self.screen_width, self.screen_height);
+            }
+            self.fullscreen = Some(desktop);
+        } else {
+            self.fullscreen = None;
+        }
+        if self.window.is_some() {
+            self.create_window();
+        }
+        true
     }
 
-    /// Draws a line from `(x1, y1)` to `(x2, y2)`
+    /// Gets how much the desktop scales windows up for high resolution displays, 1.0 when it
+    /// doesn't or the engine can't tell
+    ///
+    /// The screen, window size and mouse position are all measured in the same pixels. By default
+    /// the desktop stretches the window by this factor when it's shown, use it to pick a larger
+    /// screen size or [`WindowScale`](enum.WindowScale.html) so text stays sharp and readable,
+    /// or render at [`native_resolution`] instead. Read from `GDK_SCALE` or the X11 font dpi on
+    /// linux, the dpi of the desktop on windows and the backing scale of the window on macos
+    ///
+    /// [`native_resolution`]: struct.WindowConfig.html#structfield.native_resolution
     ///
     /// ### Example
     /// ```no_run
     /// # use rain2d::core::*;
     /// # let mut core = RainCore::init("example app", 640, 360, true);
-    /// core.draw_line(10, 10, 100, 50, WHITE);
+    /// let ui_scale = core.get_scale_factor().round().max(1.0);
+    /// core.draw_text_ttf(10, 10, "Menu", &Font::builtin(), 8.0 * ui_scale, WHITE);
     /// ```
-    pub fn draw_line(&mut self, mut x1: i32, mut y1: i32, mut x2: i32, mut y2: i32, color: Color) {
+    pub fn get_scale_factor(&self) -> f32 {
+        self.dpi_scale
+    }
+
+    /// Checks if the window is fullscreen, see [`set_fullscreen`](#method.set_fullscreen)
+    pub fn is_fullscreen(&self) -> bool {
+        self.fullscreen.is_some()
+    }
 
-        // vertical line
-        if x2 - x1 == 0 {
-            if y2 < y1 { swap(&mut y1, &mut y2); }
-            for y in y1..y2 {
-                self.draw(x1, y, color);
+    // opens the window or replaces it when switching between fullscreen and windowed
+    fn create_window(&mut self) {
+        let mut options = self.window_config.options();
+        // the screen is measured in pixels of the display at native resolution
+        let native = self.window_config.native_resolution;
+        let ratio = if native { window::enable_native_resolution(None) } else { 1.0 };
+        let (width, height) = match self.fullscreen {
+            Some(size) => {
+                options.borderless = true;
+                options.title = false;
+                options.resize = false;
+                options.scale = WindowScale::X1;
+                size
             }
-            return;
+            None => {
+                let (width, height) = self.windowed_size;
+                ((width as f32 / ratio).round() as usize, (height as f32 / ratio).round() as usize)
+            }
+        };
+
+        // the old window has to be closed first
+        if self.cursor_grabbed {
+            if let Some(window) = &self.window {
+                window::confine_cursor(window, false);
+            }
+            self.cursor_grabbed = false;
+        }
+        self.file_drop = None;
+        self.window = None;
+        let mut window = Window::new(&self.title(), width, height, options).unwrap();
+        self.dpi_scale = window::scale_factor(Some(&window));
+        self.pixel_ratio = if native { window::enable_native_resolution(Some(&window)) } else { 1.0 };
+        if self.fullscreen.is_some() {
+            window.set_position(0, 0);
+        } else if let Some((x, y)) = self.window_position {
+            window.set_position(x, y);
         }
+        // the window is larger than the screen when it's scaled
+        self.window_scale = (window.get_size().0 / width.max(1)).max(1);
+        window.set_input_callback(Box::new(self.text_input.clone()));
+        window.set_cursor_visibility(self.cursor_visible);
+        window.set_cursor_style(self.cursor_style);
+        window.set_key_repeat_delay(self.key_repeat.0.as_secs_f32());
+        window.set_key_repeat_rate(self.key_repeat.1.as_secs_f32());
+        window.limit_update_rate(self.window_update_rate());
+        self.menu_handles = self.menus.iter()
+            .filter_map(|menu| menu.build())
+            .map(|menu| window.add_menu(&menu))
+            .collect();
+        self.file_drop = Some(FileDrop::new(&window));
+        self.window = Some(window);
+        self.render_target.dirty = true;
+    }
 
-        // horizontal line
-        if y2 - y1 == 0 {
-            if x2 < x1 { swap(&mut x1, &mut x2); }
-            for x in x1..x2 {
-                self.draw(x, y1, color);
+    /// Adds a menu to the menu bar of the window, see [`Menu`](struct.Menu.html)
+    pub fn add_menu(&mut self, menu: Menu) {
+        if let Some(window) = &mut self.window {
+            if let Some(built) = menu.build() {
+                self.menu_handles.push(window.add_menu(&built));
             }
-            return;
         }
+        self.menus.push(menu);
+    }
 
-        for (x,y) in Bresenham::new((x1 as isize, y1 as isize), (x2 as isize, y2 as isize)) {
-            self.draw(x as i32, y as i32, color);
+    /// Removes all menus from the menu bar
+    pub fn clear_menus(&mut self) {
+        if let Some(window) = &mut self.window {
+            for handle in self.menu_handles.drain(..) {
+                window.remove_menu(handle);
+            }
         }
+        self.menu_handles.clear();
+        self.menus.clear();
     }
 
-    /// Draws a circle at `(x, y)` with radius `r`
+    /// Moves the top left corner of the window to the position on the desktop, windows that
+    /// aren't open yet are opened there
     ///
     /// ### Example
     /// ```no_run
     /// # use rain2d::core::*;
     /// # let mut core = RainCore::init("example app", 640, 360, true);
-    /// core.draw_circle(100, 100, 10, WHITE);
+    /// // palette window next to the main window
+    /// core.set_window_position(700, 100);
     /// ```
-    pub fn draw_circle(&mut self, x: i32, y: i32, r: i32, color: Color) {
-        let mut x0 = 0;
-        let mut y0 = r;
-        let mut d = 3 - 2 * r;
-        if r <= 0 { return; }
-
-        while y0 >= x0 {
-            self.draw(x + x0, y - y0, color);
-            self.draw(x + y0, y - x0, color);
-            self.draw(x + y0, y + x0, color);
-            self.draw(x + x0, y + y0, color);
-            self.draw(x - x0, y - y0, color);
-            self.draw(x - y0, y - x0, color);
-            self.draw(x - y0, y + x0, color);
-            self.draw(x - x0, y + y0, color);
-            if d < 0 { d += 4 * x0 + 6; x0 += 1; }
-            else { x0 += 1; y0 -= 1; d += 4 * (x0 - y0) + 10; }
+    pub fn set_window_position(&mut self, x: isize, y: isize) {
+        self.window_position = Some((x, y));
+        if self.fullscreen.is_some() { return; }
+        if let Some(window) = &mut self.window {
+            window.set_position(x, y);
         }
     }
 
-    /// Draws a filled in circle at `(x, y)` with radius `r`
+    /// Gets the position last set with [`set_window_position`], `None` when the desktop picked
+    /// it
+    ///
+    /// minifb can't tell where the window is, so moving it by hand isn't noticed
+    ///
+    /// [`set_window_position`]: #method.set_window_position
+    pub fn get_window_position(&self) -> Option<(isize, isize)> {
+        self.window_position
+    }
+
+    /// Gets the size of the window, scaled windows are measured in scaled pixels, see
+    /// [`WindowConfig::scale`](struct.WindowConfig.html#structfield.scale). At
+    /// [native resolution](struct.WindowConfig.html#structfield.native_resolution) it's
+    /// measured in pixels of the display
+    pub fn get_window_size(&self) -> (usize, usize) {
+        (self.screen_width, self.screen_height)
+    }
+
+    /// Checks if the window has focus
     ///
     /// ### Example
     /// ```no_run
     /// # use rain2d::core::*;
-    /// # let mut core = RainCore::init("example app", 640, 360, true);
-    /// core.fill_circle(100, 100, 10, WHITE);
+    /// # let core = RainCore::init("example app", 640, 360, true);
+    /// # let mut paused = false;
+    /// if !core.is_focused() {
+    ///     paused = true;
+    /// }
     /// ```
-    pub fn fill_circle(&mut self, x: i32, y: i32, r: i32, color: Color) {
-        let mut x0 = 0;
-        let mut y0 = r;
-        let mut d = 3 - 2 * r;
-        if r <= 0 { return; }
+    pub fn is_focused(&self) -> bool {
+        self.focused
+    }
 
-        while y0 >= x0 {
-            self.draw_line(x - x0, y - y0, x + x0, y - y0, color);
-            self.draw_line(x - y0, y - x0, x + y0, y - x0, color);
-            self.draw_line(x - x0, y + y0, x + x0, y + y0, color);
-            self.draw_line(x - y0, y + x0, x + y0, y + x0, color);
-            if d < 0 { d += 4 * x0 + 6; x0 += 1; }
-            else { x0 += 1; y0 -= 1; d += 4 * (x0 - y0) + 10; }
+    // tells the app when the window gained or lost focus and when the mouse entered or left it
+    fn update_window_state(&mut self, app: &mut dyn RainApp, focused: bool, mouse_inside: bool) {
+        if focused != self.focused {
+            self.focused = focused;
+            if focused { app.on_focus_gained() } else { app.on_focus_lost() }
+        }
+        if mouse_inside != self.mouse_inside {
+            self.mouse_inside = mouse_inside;
+            if mouse_inside { app.on_mouse_enter() } else { app.on_mouse_leave() }
         }
     }
 
-    /// Draws a rectangle at `(x, y)` with specified dimensions
+    // updates the window size, the screen follows it unless it has a logical size. returns true
+    // if the size changed, minimized windows keep their last size
+    fn resize_window(&mut self, width: usize, height: usize) -> bool {
+        if width == 0 || height == 0 || (width, height) == (self.screen_width, self.screen_height) {
+            return false;
+        }
+        self.screen_width = width;
+        self.screen_height = height;
+        // confined to the old size of the window
+        self.cursor_grabbed = false;
+        if self.scale_mode.is_none() {
+            self.resize_screen(width, height);
+        }
+        self.render_target.dirty = true;
+        true
+    }
+
+    // part of the window that shows the screen, None if the screen isn't scaled
+    fn viewport(&self) -> Option<Rect> {
+        let (width, height) = self.get_logical_size();
+        match self.scale_mode {
+            Some(mode) if (width, height) != (self.screen_width, self.screen_height) => {
+                Some(mode.viewport(width, height, self.screen_width, self.screen_height))
+            }
+            _ => None,
+        }
+    }
+
+    // size of the screen for a window measured in window pixels
+    fn window_size_to_screen(&self, width: usize, height: usize) -> (usize, usize) {
+        let (width, height) = (width / self.window_scale, height / self.window_scale);
+        ((width as f32 * self.pixel_ratio).round() as usize, (height as f32 * self.pixel_ratio).round() as usize)
+    }
+
+    // converts a position in window pixels to the screen
+    fn window_to_screen(&self, x: f32, y: f32) -> (f32, f32) {
+        let (x, y) = (x * self.pixel_ratio, y * self.pixel_ratio);
+        let (width, height) = self.get_logical_size();
+        match self.viewport() {
+            Some(v) if !v.is_empty() => (
+                (x - v.x as f32) * width as f32 / v.width as f32,
+                (y - v.y as f32) * height as f32 / v.height as f32,
+            ),
+            _ => (x, y),
+        }
+    }
+
+    // checks if the screen or a layer changed since the last call
+    fn take_dirty(&mut self) -> bool {
+        let targets = self.layers.iter_mut().map(|layer| &mut layer.target);
+        let mut dirty = false;
+        for target in targets.chain(Some(&mut self.render_target)) {
+            dirty |= replace(&mut target.dirty, false);
+        }
+        dirty
+    }
+
+    // resizes the screen and every layer
+    fn resize_screen(&mut self, width: usize, height: usize) {
+        let (width, height) = (width * self.supersample, height * self.supersample);
+        self.render_target.resize(width, height);
+        for layer in &mut self.layers {
+            layer.target.resize(width, height);
+        }
+        self.update_transform();
+    }
+
+    /// Gets the characters typed since the last call, using the keyboard layout and modifiers of
+    /// the system
+    ///
+    /// Backspace removes the character typed before it, when there is none it is included as
+    /// `'\u{8}'` to remove a character from the text that is being edited
     ///
     /// ### Example
     /// ```no_run
     /// # use rain2d::core::*;
     /// # let mut core = RainCore::init("example app", 640, 360, true);
-    /// core.draw_rect(100, 100, 50, 50, WHITE);
+    /// # let mut name = String::new();
+    /// // every frame
+    /// for c in core.take_text_input().chars() {
+    ///     match c {
+    ///         '\u{8}' => { name.pop(); }
+    ///         c => name.push(c),
+    ///     }
+    /// }
+    /// core.draw_text(10, 10, &name, WHITE);
     /// ```
-    pub fn draw_rect(&mut self, x: i32, y: i32, width: i32, height: i32, color: Color) {
-        self.draw_line(x, y, x + width, y, color);
-        self.draw_line(x + width, y, x + width, y + height, color);
-        self.draw_line(x + width, y + height, x, y + height, color);
-        self.draw_line(x, y + height, x, y, color);
+    pub fn take_text_input(&mut self) -> String {
+        self.text_input.take()
     }
 
-    /// Draws a filled in rectangle at `(x, y)` with specified dimensions
+    /// Checks if the key is currently down
     ///
     /// ### Example
     /// ```no_run
     /// # use rain2d::core::*;
-    /// # let mut core = RainCore::init("example app", 640, 360, true);
-    /// core.fill_rect(100, 100, 50, 50, WHITE);
+    /// # let core = RainCore::init("example app", 640, 360, true);
+    /// if core.key_down(Key::Space) {
+    ///     println!("Spacebar down");
+    /// }
     /// ```
-    pub fn fill_rect(&mut self, x: i32, y: i32, width: i32, height: i32, color: Color) {
-        let x_max = x + width;
-        let y_max = y + height;
-        for i in x..x_max {
-            for j in y..y_max {
-                self.draw(i, j, color);
-            }
+    pub fn key_down(&self, key: Key) -> bool {
+        if let Some(window) = &self.window {
+            return window.is_key_down(key);
         }
+        false
     }
 
-    /// Draws a triangle with vertices `(x1, y1)`, `(x2, y2)` and `(x3, y3)`
+    /// Checks if the key was pressed (not held) since the last update
+    pub fn key_pressed(&self, key: Key) -> bool {
+        if let Some(window) = &self.window {
+            return window.is_key_pressed(key, KeyRepeat::No);
+        }
+        false
+    }
+
+    /// Checks if the key was pressed since the last update or is repeating because it's held,
+    /// like typing in a text field, see [`set_key_repeat`](#method.set_key_repeat)
     ///
     /// ### Example
     /// ```no_run
     /// # use rain2d::core::*;
-    /// # let mut core = RainCore::init("example app", 640, 360, true);
-    /// core.draw_triangle(25, 100, 75, 100, 50, 0, WHITE);
+    /// # let core = RainCore::init("example app", 640, 360, true);
+    /// # let mut selected = 0;
+    /// if core.key_pressed_repeat(Key::Down) {
+    ///     selected += 1;
+    /// }
     /// ```
-    pub fn draw_triangle(&mut self, x1: i32, y1: i32, x2: i32, y2: i32, x3: i32, y3: i32, color: Color) {
-        self.draw_line(x1, y1, x2, y2, color);
-        self.draw_line(x2, y2, x3, y3, color);
-        self.draw_line(x3, y3, x1, y1, color);
+    pub fn key_pressed_repeat(&self, key: Key) -> bool {
+        if let Some(window) = &self.window {
+            return window.is_key_pressed(key, KeyRepeat::Yes);
+        }
+        false
     }
 
-    /// Draws a filled in triangle with vertices `(x1, y1)`, `(x2, y2)` and `(x3, y3)`
+    /// Sets how long a key has to be held before it repeats and the time between repeats,
+    /// defaults to 250 ms and 50 ms
     ///
     /// ### Example
     /// ```no_run
     /// # use rain2d::core::*;
+    /// # use std::time::Duration;
     /// # let mut core = RainCore::init("example app", 640, 360, true);
-    /// core.fill_triangle(25, 100, 75, 100, 50, 0, WHITE);
+    /// // slower menu navigation
+    /// core.set_key_repeat(Duration::from_millis(400), Duration::from_millis(100));
     /// ```
-    // http://www.sunshine2k.de/coding/java/TriangleRasterization/TriangleRasterization.html
-    pub fn fill_triangle(&mut self, mut x1: i32, mut y1: i32, mut x2: i32, mut y2: i32, mut x3: i32, mut y3: i32, color: Color) {
-        // sort vertices
-        if y1 > y2 { swap(&mut x1, &mut x2); swap(&mut y1, &mut y2); }
-        if y1 > y3 { swap(&mut x1, &mut x3); swap(&mut y1, &mut y3); }
-        if y2 > y3 { swap(&mut x2, &mut x3); swap(&mut y2, &mut y3); }
-
-        // flat bottom triangle
-        if y2 == y3 {
-            self.fill_triangle_bottom(x1, y1, x2, y2, x3, y3, color);
+    pub fn set_key_repeat(&mut self, delay: Duration, rate: Duration) {
+        self.key_repeat = (delay, rate);
+        if let Some(window) = &mut self.window {
+            window.set_key_repeat_delay(delay.as_secs_f32());
+            window.set_key_repeat_rate(rate.as_secs_f32());
         }
-        // flat top triangle
-        else if y1 == y2 {
-            self.fill_triangle_top(x1, y1, x2, y2, x3, y3, color);
+    }
+
+    /// Gets the delay and rate of repeating keys, see [`set_key_repeat`](#method.set_key_repeat)
+    pub fn get_key_repeat(&self) -> (Duration, Duration) {
+        self.key_repeat
+    }
+
+    /// Checks if the key was released since the last update
+    pub fn key_released(&self, key: Key) -> bool {
+        if let Some(window) = &self.window {
+            return window.is_key_released(key);
         }
-        // split triangle and fill sides
-        else {
-            let x4 = x1 + f32::round(((y2 - y1) as f32 / (y3 - y1) as f32) * (x3 - x1) as f32) as i32;
-            self.fill_triangle_bottom(x1, y1, x2, y2, x4, y2, color);
-            self.fill_triangle_top(x2, y2, x4, y2, x3, y3, color);
+        false
+    }
+
+    /// Checks if the key at the position on the keyboard is currently down, see
+    /// [`PhysicalKey`](struct.PhysicalKey.html)
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let core = RainCore::init("example app", 640, 360, true);
+    /// // the same keys on qwerty, azerty and dvorak
+    /// let left = core.physical_key_down(PhysicalKey(Key::A));
+    /// let right = core.physical_key_down(PhysicalKey(Key::D));
+    /// ```
+    pub fn physical_key_down(&self, key: PhysicalKey) -> bool {
+        self.physical_keys.contains(&key.0)
+    }
+
+    /// Checks if the key at the position on the keyboard was pressed since the last update
+    pub fn physical_key_pressed(&self, key: PhysicalKey) -> bool {
+        self.physical_keys.contains(&key.0) && !self.physical_last.contains(&key.0)
+    }
+
+    /// Checks if the key at the position on the keyboard was released since the last update
+    pub fn physical_key_released(&self, key: PhysicalKey) -> bool {
+        !self.physical_keys.contains(&key.0) && self.physical_last.contains(&key.0)
+    }
+
+    /// Checks if a combination of keys was pressed since the last update, all keys have to be down
+    /// and at least one of them has to be pressed in this update so holding the chord doesn't
+    /// repeat it
+    ///
+    /// The left and right shift, control, alt and super keys can be used interchangeably
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let core = RainCore::init("example app", 640, 360, true);
+    /// if core.key_chord_pressed(&[Key::LeftCtrl, Key::S]) {
+    ///     println!("save");
+    /// }
+    /// ```
+    pub fn key_chord_pressed(&self, keys: &[Key]) -> bool {
+        !keys.is_empty()
+            && keys.iter().all(|&key| chord_key(key, |key| self.key_down(key)))
+            && keys.iter().any(|&key| chord_key(key, |key| self.key_pressed(key)))
+    }
+
+    /// Checks if the mouse button was pressed since the last update while all keys are down
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let core = RainCore::init("example app", 640, 360, true);
+    /// if core.mouse_chord_pressed(&[Key::LeftShift], MouseButton::Left) {
+    ///     println!("add to selection");
+    /// }
+    /// ```
+    pub fn mouse_chord_pressed(&self, keys: &[Key], button: MouseButton) -> bool {
+        self.mouse_button_pressed(button) && keys.iter().all(|&key| chord_key(key, |key| self.key_down(key)))
+    }
+
+    /// Gets all keys that are currently down
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// core.get_keys().map(|keys| {
+    ///     for key in keys {
+    ///         match key {
+    ///             Key::A => println!("A key down"),
+    ///             Key::Escape => core.exit(),
+    ///             _ => (),
+    ///         }
+    ///     }
+    /// });
+    /// ```
+    pub fn get_keys(&self) -> Option<Vec<Key>> {
+        if let Some(window) = &self.window {
+            return window.get_keys();
         }
+        None
+    }
+
+    /// Get mouse position relative to the window, (0, 0) in upper left corner
+    ///
+    /// Positions outside of the screen are handled as set by
+    /// [`set_mouse_mode`](#method.set_mouse_mode)
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let core = RainCore::init("example app", 640, 360, true);
+    /// if let Some((x, y)) = core.get_mouse_pos() {
+    ///     println!("x: {}, y: {}", x, y);
+    /// }
+    /// ```
+    pub fn get_mouse_pos(&self) -> Option<(f32, f32)> {
+        self.get_mouse_pos_mode(self.mouse_mode)
     }
 
-    fn fill_triangle_bottom(&mut self, x1: i32, y1: i32, x2: i32, y2: i32, x3: i32, y3: i32, color: Color) {
-        // calculate slope
-        let s1 = (x2 - x1) as f32 / (y2 - y1) as f32;
-        let s2 = (x3 - x1) as f32 / (y3 - y1) as f32;
+    /// Get mouse position relative to the window with the mode used for positions outside of
+    /// the screen, see [`set_mouse_mode`](#method.set_mouse_mode)
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let core = RainCore::init("example app", 640, 360, true);
+    /// // only hover buttons while the mouse is on the screen
+    /// if let Some((x, y)) = core.get_mouse_pos_mode(MouseMode::Discard) {
+    ///     println!("hovering {}, {}", x, y);
+    /// }
+    /// ```
+    pub fn get_mouse_pos_mode(&self, mode: MouseMode) -> Option<(f32, f32)> {
+        let window = self.window.as_ref()?;
+        let (x, y) = window.get_mouse_pos(MouseMode::Pass)?;
+        let (x, y) = self.window_to_screen(x, y);
+        self.apply_mouse_mode(x, y, mode)
+    }
 
-        let mut x1 = x1 as f32;
-        let mut x2 = x1 as f32;
+    /// Sets how [`get_mouse_pos`] handles positions outside of the screen, defaults to
+    /// `MouseMode::Pass` which returns them unchanged
+    ///
+    /// `MouseMode::Clamp` moves them to the closest pixel on the screen and `MouseMode::Discard`
+    /// returns `None`. With a logical size the bars around the screen count as outside
+    ///
+    /// [`get_mouse_pos`]: #method.get_mouse_pos
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// core.set_mouse_mode(MouseMode::Clamp);
+    /// ```
+    pub fn set_mouse_mode(&mut self, mode: MouseMode) {
+        self.mouse_mode = mode;
+    }
 
-        // draw scanlines, adjust ends of lines according to slopes
-        for y in y1..=y2 {
-            self.draw_line(f32::round(x1) as i32, y, f32::round(x2) as i32, y, color);
-            x1 += s1;
-            x2 += s2;
+    // applies the mouse mode to screen coordinates, the screen is used instead of the window so
+    // letterboxing bars are outside
+    fn apply_mouse_mode(&self, x: f32, y: f32, mode: MouseMode) -> Option<(f32, f32)> {
+        let (width, height) = self.get_logical_size();
+        let (width, height) = (width as f32, height as f32);
+        match mode {
+            MouseMode::Pass => Some((x, y)),
+            // an empty screen clamps to 0, clamp would panic on a maximum below the minimum
+            MouseMode::Clamp => Some((x.max(0.0).min((width - 1.0).max(0.0)), y.max(0.0).min((height - 1.0).max(0.0)))),
+            MouseMode::Discard if x < 0.0 || y < 0.0 || x >= width || y >= height => None,
+            MouseMode::Discard => Some((x, y)),
         }
     }
 
-    fn fill_triangle_top(&mut self, x1: i32, y1: i32, x2: i32, y2: i32, x3: i32, y3: i32, color: Color) {
-        // calculate slopes
-        let s1 = (x3 - x1) as f32 / (y3 - y1) as f32;
-        let s2 = (x3 - x2) as f32 / (y3 - y2) as f32;
+    /// Gets the mouse position in world coordinates, the same as the screen position when there
+    /// is no camera, see [`set_camera`](#method.set_camera)
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// # let enemies = vec![Rect::new(100, 40, 16, 16)];
+    /// core.set_camera(Some(Camera2D::new(320.0, 180.0)));
+    /// if let Some((x, y)) = core.get_mouse_world_pos() {
+    ///     let hovered = enemies.iter().find(|rect| rect.contains(x as i32, y as i32));
+    /// }
+    /// ```
+    pub fn get_mouse_world_pos(&self) -> Option<(f32, f32)> {
+        self.get_mouse_pos().map(|(x, y)| self.screen_to_world(x, y))
+    }
+
+    /// Gets the gamepad with the index, returns `None` if it isn't connected
+    ///
+    /// Gamepads can be connected and disconnected at any time, new gamepads are found within a
+    /// second. Only linux is supported for now
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let core = RainCore::init("example app", 640, 360, true);
+    /// for i in 0..4 {
+    ///     if let Some(pad) = core.gamepad(i) {
+    ///         println!("player {}: {:?}", i + 1, pad.left_stick());
+    ///     }
+    /// }
+    /// ```
+    #[cfg(feature = "gamepad")]
+    pub fn gamepad(&self, index: usize) -> Option<&Gamepad> {
+        self.gamepads.get(index)
+    }
+
+    /// Gets the gamepad with the index so its deadzone can be changed
+    #[cfg(feature = "gamepad")]
+    pub fn gamepad_mut(&mut self, index: usize) -> Option<&mut Gamepad> {
+        self.gamepads.get_mut(index)
+    }
 
-        let mut x1 = x3 as f32;
-        let mut x2 = x3 as f32;
+    /// Checks if the gamepad with the index is connected
+    #[cfg(feature = "gamepad")]
+    pub fn gamepad_connected(&self, index: usize) -> bool {
+        self.gamepad(index).is_some()
+    }
 
-        // draw scanlines, adjust ends of lines according to slopes
-        for y in (y1..=y3).rev() {
-            self.draw_line(f32::round(x1) as i32, y, f32::round(x2) as i32, y, color);
-            x1 -= s1;
-            x2 -= s2;
+    /// Shows or hides the cursor while it is over the window
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// // draw a custom cursor instead
+    /// core.set_cursor_visible(false);
+    /// if let Some((x, y)) = core.get_mouse_pos() {
+    ///     core.fill_circle(x as i32, y as i32, 3, WHITE);
+    /// }
+    /// ```
+    pub fn set_cursor_visible(&mut self, visible: bool) {
+        self.cursor_visible = visible;
+        if let Some(window) = &mut self.window {
+            window.set_cursor_visibility(visible);
         }
     }
-}
 
-#[cfg(test)]
-mod test {
-    use super::*;
+    /// Checks if the cursor is shown
+    pub fn is_cursor_visible(&self) -> bool {
+        self.cursor_visible
+    }
 
-    fn create_core(w: usize, h: usize) -> RainCore {
-        RainCore::init("", w, h, false)
+    /// Grabs the cursor so it can't leave the window while the window has focus, for controls
+    /// that must not let the pointer escape to another monitor
+    ///
+    /// A hidden cursor is also moved back to the center of the window every update, so
+    /// [`get_mouse_delta`] keeps reporting relative motion when the mouse is moved further than
+    /// the edge. Returns `false` when the cursor can't be grabbed, which is the case on wayland.
+    /// On macos the cursor is moved back when it leaves the window instead of being stopped
+    ///
+    /// [`get_mouse_delta`]: #method.get_mouse_delta
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// # let mut angle = 0.0;
+    /// // drag to rotate
+    /// if core.mouse_button_pressed(MouseButton::Left) {
+    ///     core.set_cursor_visible(false);
+    ///     core.set_cursor_grab(true);
+    /// } else if core.mouse_button_released(MouseButton::Left) {
+    ///     core.set_cursor_visible(true);
+    ///     core.set_cursor_grab(false);
+    /// }
+    /// if core.is_cursor_grabbed() {
+    ///     angle += core.get_mouse_delta().0 * 0.01;
+    /// }
+    /// ```
+    pub fn set_cursor_grab(&mut self, grab: bool) -> bool {
+        if grab && !window::can_grab_cursor(self.window.as_ref()) {
+            return false;
+        }
+        self.cursor_grab = grab;
+        true
     }
 
-    #[test]
-    fn test_clear() {
-        let mut core = create_core(10, 10);
+    /// Checks if the cursor is grabbed, see [`set_cursor_grab`](#method.set_cursor_grab)
+    pub fn is_cursor_grabbed(&self) -> bool {
+        self.cursor_grab
+    }
 
-        core.clear(WHITE);
-        for p in core.render_target.data {
-            assert_eq!(p, WHITE.into());
+    // confines the cursor while it's grabbed and the window has focus, a hidden cursor is moved
+    // back to the center of the window and a visible one back inside it
+    fn update_cursor_grab(&mut self) {
+        let window = match &self.window {
+            Some(window) => window,
+            None => return,
+        };
+        let grab = self.cursor_grab && self.focused;
+        if grab != self.cursor_grabbed {
+            self.cursor_grabbed = window::confine_cursor(window, grab) && grab;
+        }
+        if !self.cursor_grabbed { return; }
+
+        // minifb measures the mouse in scaled pixels
+        let scale = self.window_scale as f32;
+        let (x, y) = match window.get_mouse_pos(MouseMode::Pass) {
+            Some((x, y)) => (x * scale, y * scale),
+            None => return,
+        };
+        let (width, height) = window.get_size();
+        let (width, height) = (width as f32, height as f32);
+        let target = if self.cursor_visible {
+            (x.max(0.0).min((width - 1.0).max(0.0)), y.max(0.0).min((height - 1.0).max(0.0)))
+        } else {
+            ((width / 2.0).floor(), (height / 2.0).floor())
+        };
+        if target != (x, y) && window::warp_cursor(window, (x, y), target) {
+            self.mouse_warped = Some(self.window_to_screen(target.0 / scale, target.1 / scale));
         }
     }
 
-    #[test]
-    fn test_draw() {
-        let mut core = create_core(10, 10);
+    /// Sets the style of the cursor while it is over the window
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// # let text_box = Rect::new(10, 10, 100, 20);
+    /// let over_text = core.get_mouse_pos()
+    ///     .is_some_and(|(x, y)| text_box.contains(x as i32, y as i32));
+    /// core.set_cursor_style(if over_text { CursorStyle::Ibeam } else { CursorStyle::Arrow });
+    /// ```
+    pub fn set_cursor_style(&mut self, style: CursorStyle) {
+        if style == self.cursor_style { return; }
+        self.cursor_style = style;
+        if let Some(window) = &mut self.window {
+            window.set_cursor_style(style);
+        }
+    }
 
-        core.draw(5, 3, WHITE);
-        assert_eq!(core.render_target.get_pixel(5, 3), Some(WHITE));
+    /// Gets the style of the cursor
+    pub fn get_cursor_style(&self) -> CursorStyle {
+        self.cursor_style
+    }
+
+    /// Gets the text on the system clipboard, returns `None` if it's empty or can't be read,
+    /// available with the `clipboard` feature
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// if core.key_chord_pressed(&[Key::LeftCtrl, Key::V]) {
+    ///     if let Some(seed) = core.clipboard_text().and_then(|text| text.trim().parse::<u64>().ok()) {
+    ///         println!("seed: {}", seed);
+    ///     }
+    /// }
+    /// ```
+    #[cfg(feature = "clipboard")]
+    pub fn clipboard_text(&mut self) -> Option<String> {
+        self.clipboard.get()
+    }
+
+    /// Puts text on the system clipboard, see [`clipboard_text`](#method.clipboard_text)
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// if core.key_chord_pressed(&[Key::LeftCtrl, Key::C]) {
+    ///     core.set_clipboard_text("LEVEL-4-XKCD").unwrap();
+    /// }
+    /// ```
+    #[cfg(feature = "clipboard")]
+    pub fn set_clipboard_text(&mut self, text: &str) -> std::io::Result<()> {
+        self.clipboard.set(text)
+    }
+
+    /// Gets how far the mouse moved since the last update, the movement is also tracked outside
+    /// of the window when the system reports it
+    ///
+    /// The movement stops at the edge of the desktop, or at the edge of the window while the
+    /// cursor is grabbed. Hide and [grab](#method.set_cursor_grab) the cursor for movement
+    /// without edges
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// # let mut camera = Camera2D::default();
+    /// // drag the camera
+    /// if core.mouse_button_down(MouseButton::Right) {
+    ///     let (dx, dy) = core.get_mouse_delta();
+    ///     camera.position.0 -= dx / camera.zoom;
+    ///     camera.position.1 -= dy / camera.zoom;
+    /// }
+    /// ```
+    pub fn get_mouse_delta(&self) -> (f32, f32) {
+        match (self.mouse_last, self.mouse_pos) {
+            (Some((x1, y1)), Some((x2, y2))) => (x2 - x1, y2 - y1),
+            _ => (0.0, 0.0),
+        }
+    }
+
+    /// Checks if the button is currently down
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let core = RainCore::init("example app", 640, 360, true);
+    /// if core.mouse_button_down(MouseButton::Left) {
+    ///     println!("Left mouse button down");
+    /// }
+    /// ```
+    pub fn mouse_button_down(&self, button: MouseButton) -> bool {
+        if let Some(window) = &self.window {
+            return window.get_mouse_down(button);
+        }
+        false
+    }
+
+    /// Checks if the button was pressed since the last update
+    pub fn mouse_button_pressed(&self, button: MouseButton) -> bool {
+        let i = mouse_button_index(button);
+        self.mouse_buttons[i] && !self.mouse_previous[i]
+    }
+
+    /// Checks if the button was released since the last update
+    pub fn mouse_button_released(&self, button: MouseButton) -> bool {
+        let i = mouse_button_index(button);
+        !self.mouse_buttons[i] && self.mouse_previous[i]
+    }
+
+    /// Checks if dragging with the button started in this update, dragging starts when the mouse
+    /// moves further than the [`drag threshold`] while the button is down
+    ///
+    /// [`drag threshold`]: #method.set_drag_threshold
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// // rubber band selection
+    /// if let Some((x, y)) = core.drag_origin() {
+    ///     let (dx, dy) = core.drag_delta().unwrap();
+    ///     core.draw_rect(x as i32, y as i32, dx as i32, dy as i32, WHITE);
+    /// }
+    /// if core.drag_ended(MouseButton::Left) {
+    ///     println!("select");
+    /// }
+    /// ```
+    pub fn drag_started(&self, button: MouseButton) -> bool {
+        self.drag.is_some_and(|drag| drag.button == button && drag.started)
+    }
+
+    /// Checks if the mouse is being dragged with the button
+    pub fn is_dragging(&self, button: MouseButton) -> bool {
+        self.drag.is_some_and(|drag| drag.button == button && drag.active && !drag.ended)
+    }
+
+    /// Checks if dragging with the button ended in this update because it was released
+    pub fn drag_ended(&self, button: MouseButton) -> bool {
+        self.drag.is_some_and(|drag| drag.button == button && drag.ended)
+    }
+
+    /// Gets where the mouse was when the button of the current drag was pressed, the drag
+    /// is still returned in the update it ended
+    pub fn drag_origin(&self) -> Option<(f32, f32)> {
+        self.drag.filter(|drag| drag.active).map(|drag| drag.origin)
+    }
+
+    /// Gets how far the mouse moved since the button of the current drag was pressed
+    pub fn drag_delta(&self) -> Option<(f32, f32)> {
+        self.drag.filter(|drag| drag.active).map(|drag| (drag.pos.0 - drag.origin.0, drag.pos.1 - drag.origin.1))
+    }
+
+    /// Sets how far the mouse has to move in pixels before dragging starts, defaults to 4
+    pub fn set_drag_threshold(&mut self, threshold: f32) {
+        self.drag_threshold = threshold;
+    }
+
+    // starts, moves and ends the drag, only one button can drag at a time
+    fn update_drag(&mut self) {
+        if let Some(drag) = &mut self.drag {
+            let down = self.mouse_buttons[mouse_button_index(drag.button)];
+            if !drag.update(down, self.mouse_pos, self.drag_threshold) {
+                self.drag = None;
+            }
+        }
+        if self.drag.is_none() {
+            let pressed = MOUSE_BUTTONS.iter().copied().find(|&button| self.mouse_button_pressed(button));
+            if let (Some(button), Some(pos)) = (pressed, self.mouse_pos) {
+                let mut drag = Drag::new(button, pos);
+                drag.update(true, Some(pos), self.drag_threshold);
+                self.drag = Some(drag);
+            }
+        }
+    }
+
+    /// Replaces the actions used by [`action_down`], [`action_pressed`] and [`action_released`]
+    ///
+    /// [`action_down`]: #method.action_down
+    /// [`action_pressed`]: #method.action_pressed
+    /// [`action_released`]: #method.action_released
+    pub fn set_input_map(&mut self, input_map: InputMap) {
+        self.input_map = input_map;
+    }
+
+    /// Gets the actions so bindings can be changed
+    pub fn get_input_map_mut(&mut self) -> &mut InputMap {
+        &mut self.input_map
+    }
+
+    /// Checks if any binding of the action is currently down, see [`InputMap`]
+    ///
+    /// [`InputMap`]: struct.InputMap.html
+    pub fn action_down(&self, action: &str) -> bool {
+        self.input_map.bindings(action).iter().any(|&binding| match binding {
+            Binding::Key(key) => self.key_down(key),
+            Binding::Physical(key) => self.physical_key_down(key),
+            Binding::Mouse(button) => self.mouse_buttons[mouse_button_index(button)],
+            #[cfg(feature = "gamepad")]
+            Binding::Gamepad(i, button) => self.gamepad(i).is_some_and(|pad| pad.button_down(button)),
+        })
+    }
+
+    /// Checks if a binding of the action was pressed since the last update while no other binding
+    /// was already down
+    pub fn action_pressed(&self, action: &str) -> bool {
+        let bindings = self.input_map.bindings(action);
+        let pressed = bindings.iter().any(|&binding| match binding {
+            Binding::Key(key) => self.key_pressed(key),
+            Binding::Physical(key) => self.physical_key_pressed(key),
+            Binding::Mouse(button) => self.mouse_button_pressed(button),
+            #[cfg(feature = "gamepad")]
+            Binding::Gamepad(i, button) => self.gamepad(i).is_some_and(|pad| pad.button_pressed(button)),
+        });
+        pressed && !self.action_held(bindings)
+    }
+
+    /// Checks if the last binding of the action that was down was released since the last update
+    pub fn action_released(&self, action: &str) -> bool {
+        let bindings = self.input_map.bindings(action);
+        let released = bindings.iter().any(|&binding| match binding {
+            Binding::Key(key) => self.key_released(key),
+            Binding::Physical(key) => self.physical_key_released(key),
+            Binding::Mouse(button) => self.mouse_button_released(button),
+            #[cfg(feature = "gamepad")]
+            Binding::Gamepad(i, button) => self.gamepad(i).is_some_and(|pad| pad.button_released(button)),
+        });
+        released && !self.action_down(action)
+    }
+
+    // checks if any of the bindings was already down before this update
+    fn action_held(&self, bindings: &[Binding]) -> bool {
+        bindings.iter().any(|&binding| match binding {
+            Binding::Key(key) => self.key_down(key) && !self.key_pressed(key),
+            Binding::Physical(key) => self.physical_last.contains(&key.0),
+            Binding::Mouse(button) => self.mouse_previous[mouse_button_index(button)],
+            #[cfg(feature = "gamepad")]
+            Binding::Gamepad(i, button) => {
+                self.gamepad(i).is_some_and(|pad| pad.button_down(button) && !pad.button_pressed(button))
+            }
+        })
+    }
+
+    /// Keeps pressed actions for the duration so they can be used later with
+    /// [`consume_buffered`](#method.consume_buffered), `None` turns buffering off
+    pub fn set_input_buffer(&mut self, duration: Option<Duration>) {
+        self.input_buffer = duration;
+        if duration.is_none() {
+            self.buffered.clear();
+        }
+    }
+
+    /// Checks if the action was pressed within the time and removes that press, so a jump
+    /// pressed just before landing still happens when the player lands
+    ///
+    /// Only works with [`set_input_buffer`](#method.set_input_buffer), presses older than the
+    /// buffer are forgotten
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # use std::time::Duration;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// # let on_ground = true;
+    /// core.set_input_buffer(Some(Duration::from_millis(200)));
+    ///
+    /// // every frame
+    /// if on_ground && core.consume_buffered("jump", Duration::from_millis(120)) {
+    ///     println!("jump");
+    /// }
+    /// ```
+    pub fn consume_buffered(&mut self, action: &str, within: Duration) -> bool {
+        let now = Instant::now();
+        let press = self.buffered.iter()
+            .position(|(name, time)| name == action && now.saturating_duration_since(*time) <= within);
+        if let Some(i) = press {
+            self.buffered.remove(i);
+        }
+        press.is_some()
+    }
+
+    // forgets old presses and remembers the actions pressed in this update
+    fn update_input_buffer(&mut self, now: Instant) {
+        let duration = match self.input_buffer {
+            Some(duration) => duration,
+            None => return,
+        };
+        self.buffered.retain(|(_, time)| now.saturating_duration_since(*time) <= duration);
+        let pressed: Vec<String> = self.input_map.actions()
+            .filter(|action| self.action_pressed(action))
+            .map(str::to_string)
+            .collect();
+        self.buffered.extend(pressed.into_iter().map(|action| (action, now)));
+    }
+
+    /// Get current scroll wheel movement
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let core = RainCore::init("example app", 640, 360, true);
+    /// if let Some((x, y)) = core.get_scroll_wheel() {
+    ///     println!("x: {}, y: {}", x, y);
+    /// }
+    /// ```
+    pub fn get_scroll_wheel(&self) -> Option<(f32,f32)> {
+        if let Some(window) = &self.window {
+            return window.get_scroll_wheel();
+        }
+        None
+    }
+
+    /// Sets how colors are combined with the existing contents of the screen for all
+    /// following draw calls, defaults to [`BlendMode::Alpha`]
+    ///
+    /// [`BlendMode::Alpha`]: enum.BlendMode.html#variant.Alpha
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// core.set_blend_mode(BlendMode::Additive);
+    /// core.fill_circle(100, 100, 10, Color::rgb(255, 120, 0));
+    /// core.set_blend_mode(BlendMode::Alpha);
+    /// ```
+    pub fn set_blend_mode(&mut self, mode: BlendMode) {
+        self.render_target.blend_mode = mode;
+    }
+
+    /// Gets the current blend mode
+    pub fn get_blend_mode(&self) -> BlendMode {
+        self.render_target.blend_mode
+    }
+
+    /// Restricts all following draw calls to the rectangle in screen coordinates, `None`
+    /// allows drawing on the whole screen again
+    ///
+    /// The transform doesn't apply to the clip rect. Inside [`draw_to_target`] the clip rect
+    /// of the render target is used instead
+    ///
+    /// [`draw_to_target`]: #method.draw_to_target
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// # let scroll = 0;
+    /// // only the part of the list inside the panel is visible
+    /// core.set_clip_rect(Some(Rect::new(20, 20, 200, 100)));
+    /// for i in 0..50 {
+    ///     core.draw_text(24, 24 + i * 10 - scroll, &format!("item {}", i), WHITE);
+    /// }
+    /// core.set_clip_rect(None);
+    /// ```
+    pub fn set_clip_rect(&mut self, clip_rect: Option<Rect>) {
+        let s = self.supersample as i32;
+        self.render_target.clip_rect = clip_rect.map(|r| Rect::new(r.x * s, r.y * s, r.width * s, r.height * s));
+    }
+
+    /// Gets the current clip rect
+    pub fn get_clip_rect(&self) -> Option<Rect> {
+        let s = self.supersample as i32;
+        self.render_target.clip_rect.map(|r| Rect::new(r.x / s, r.y / s, r.width / s, r.height / s))
+    }
+
+    /// Adds a transparent layer with the size of the screen, or changes the order of the layer
+    /// if it already exists
+    ///
+    /// Layers are drawn on the window every frame sorted by `order`, the screen itself has
+    /// order 0 and is drawn below layers with the same order. Use [`set_draw_layer`] to draw
+    /// on the layer
+    ///
+    /// [`set_draw_layer`]: #method.set_draw_layer
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// core.add_layer("background", -1);
+    /// core.add_layer("ui", 1);
+    ///
+    /// core.set_draw_layer(Some("ui"));
+    /// core.clear(NONE);
+    /// core.draw_text(10, 10, "score: 0", WHITE);
+    ///
+    /// // back to the screen
+    /// core.set_draw_layer(None);
+    /// ```
+    pub fn add_layer(&mut self, name: &str, order: i32) {
+        match self.layer_index(name) {
+            Some(_) => self.set_layer_order(name, order),
+            None => {
+                let (width, height) = (self.render_target.width, self.render_target.height);
+                self.layers.push(Layer::new(name, order, width, height));
+            }
+        }
+    }
+
+    /// Removes the layer, drawing goes to the screen if the layer was being drawn on
+    pub fn remove_layer(&mut self, name: &str) {
+        let i = match self.layer_index(name) {
+            Some(i) => i,
+            None => return,
+        };
+        if self.active_layer == Some(i) {
+            self.set_draw_layer(None);
+        }
+        self.layers.remove(i);
+        self.render_target.dirty = true;
+        if let Some(active) = &mut self.active_layer {
+            if *active > i { *active -= 1; }
+        }
+    }
+
+    /// Makes all following draw calls draw on the layer, `None` draws on the screen again
+    ///
+    /// The blend mode and clip rect stay the same, names of layers that don't exist are ignored
+    pub fn set_draw_layer(&mut self, name: Option<&str>) {
+        let index = match name {
+            Some(name) => match self.layer_index(name) {
+                Some(i) => Some(i),
+                None => return,
+            },
+            None => None,
+        };
+        if index == self.active_layer { return; }
+
+        let (blend_mode, clip_rect) = (self.render_target.blend_mode, self.render_target.clip_rect);
+        self.swap_layer();
+        self.active_layer = index;
+        self.swap_layer();
+        self.render_target.blend_mode = blend_mode;
+        self.render_target.clip_rect = clip_rect;
+    }
+
+    /// Shows or hides the layer, hidden layers keep their contents
+    pub fn set_layer_visible(&mut self, name: &str, visible: bool) {
+        if let Some(i) = self.layer_index(name) {
+            self.layers[i].visible = visible;
+            self.layers[i].target.dirty = true;
+        }
+    }
+
+    /// Sets the opacity of the layer from 0.0 for invisible to 1.0 for opaque
+    pub fn set_layer_opacity(&mut self, name: &str, opacity: f32) {
+        if let Some(i) = self.layer_index(name) {
+            self.layers[i].opacity = opacity.clamp(0.0, 1.0);
+            self.layers[i].target.dirty = true;
+        }
+    }
+
+    /// Changes the order of the layer, see [`add_layer`]
+    ///
+    /// [`add_layer`]: #method.add_layer
+    pub fn set_layer_order(&mut self, name: &str, order: i32) {
+        if let Some(i) = self.layer_index(name) {
+            self.layers[i].order = order;
+            self.layers[i].target.dirty = true;
+        }
+    }
+
+    fn layer_index(&self, name: &str) -> Option<usize> {
+        self.layers.iter().position(|layer| layer.name == name)
+    }
+
+    // swaps the active layer with the screen, the screen is swapped back in when called again
+    fn swap_layer(&mut self) {
+        if let Some(i) = self.active_layer {
+            swap(&mut self.render_target, &mut self.layers[i].target);
+        }
+    }
+
+    /// Turns on indexed mode where every pixel stores a palette index that is mapped to a color
+    /// of the palette when the screen is shown, `None` turns it off
+    ///
+    /// Draw with [`Palette::index`] and convert sprites with [`Palette::index_sprite`], blending
+    /// mixes indices so only opaque colors should be used. Changing the palette with
+    /// [`get_palette_mut`] changes every pixel that uses the changed index
+    ///
+    /// [`Palette::index`]: struct.Palette.html#method.index
+    /// [`Palette::index_sprite`]: struct.Palette.html#method.index_sprite
+    /// [`get_palette_mut`]: #method.get_palette_mut
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// core.set_palette(Some(Palette::new(&[Color::rgb(15, 56, 15), Color::rgb(48, 98, 48),
+    ///                                      Color::rgb(139, 172, 15), Color::rgb(155, 188, 15)])));
+    /// core.clear(Palette::index(0));
+    /// core.fill_circle(320, 180, 50, Palette::index(3));
+    ///
+    /// // flash the circle
+    /// if let Some(palette) = core.get_palette_mut() {
+    ///     palette.set(3, WHITE);
+    /// }
+    /// ```
+    pub fn set_palette(&mut self, palette: Option<Palette>) {
+        self.palette = palette;
+        self.render_target.dirty = true;
+    }
+
+    /// Gets the palette used in indexed mode
+    pub fn get_palette(&self) -> Option<&Palette> {
+        self.palette.as_ref()
+    }
+
+    /// Gets the palette used in indexed mode so it can be changed
+    pub fn get_palette_mut(&mut self) -> Option<&mut Palette> {
+        self.render_target.dirty = true;
+        self.palette.as_mut()
+    }
+
+    /// Sets a function that changes every pixel of the frame right before it is shown, `None`
+    /// removes it
+    ///
+    /// The function gets the location and color of every pixel after combining the layers and
+    /// returns the color to show, what was drawn is kept for the next frame. The frame is shown
+    /// every update while it is set
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// // vignette
+    /// core.set_post_process(Some(Box::new(|x, y, c| {
+    ///     let (dx, dy) = (x as f32 / 320.0 - 1.0, y as f32 / 180.0 - 1.0);
+    ///     let v = 1.0 - (dx * dx + dy * dy) * 0.3;
+    ///     Color::rgba((c.r as f32 * v) as u8, (c.g as f32 * v) as u8, (c.b as f32 * v) as u8, c.a)
+    /// })));
+    /// ```
+    pub fn set_post_process(&mut self, post_process: Option<PostProcess>) {
+        self.post_process = post_process;
+    }
+
+    /// Reduces every frame to the colors of the palette with ordered dithering right before it is
+    /// shown, after post processing. `None` shows all colors again
+    ///
+    /// Use [`RenderTarget::dither`] to dither only a part of the screen
+    ///
+    /// [`RenderTarget::dither`]: struct.RenderTarget.html#method.dither
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// // 1-bit look
+    /// core.set_dither(Some(Palette::new(&[BLACK, WHITE])));
+    /// ```
+    pub fn set_dither(&mut self, palette: Option<Palette>) {
+        self.dither = palette;
+        self.render_target.dirty = true;
+    }
+
+    // shows the screen, layers and post processing scaled to the window
+    fn present(&mut self) {
+        self.composite_layers();
+        self.frame = if self.layers.is_empty() { Frame::Screen } else { Frame::Composite };
+        let processed = self.palette.is_some() || self.post_process.is_some() || self.dither.is_some();
+        if self.supersample > 1 {
+            // the downsampled frame takes the place of the composite target
+            let frame = if self.layers.is_empty() { &self.render_target } else { &self.composite };
+            scaling::downsample(frame, &mut self.downsampled, self.supersample);
+            swap(&mut self.composite, &mut self.downsampled);
+            self.frame = Frame::Composite;
+        }
+        if processed {
+            if self.layers.is_empty() && self.supersample == 1 {
+                let screen = &self.render_target;
+                self.composite.resize(screen.width, screen.height);
+                self.composite.data.copy_from_slice(&screen.data);
+            }
+            if let Some(palette) = &self.palette {
+                self.composite.map_pixels(|_, _, c| palette.map(c));
+            }
+            if let Some(post_process) = &mut self.post_process {
+                self.composite.map_pixels(post_process);
+            }
+            if let Some(dither) = &self.dither {
+                self.composite.dither(dither);
+            }
+            self.frame = Frame::Composite;
+        }
+        if let Some(viewport) = self.viewport() {
+            self.output.resize(self.screen_width, self.screen_height);
+            let frame = if self.frame == Frame::Screen { &self.render_target } else { &self.composite };
+            scaling::scale_into(frame, &mut self.output, viewport);
+            self.frame = Frame::Output;
+        }
+        let frame = match self.frame {
+            Frame::Screen => &self.render_target,
+            Frame::Composite => &self.composite,
+            Frame::Output => &self.output,
+        };
+        if let Some(window) = &mut self.window {
+            window.update_with_buffer(&frame.data,
+                                           frame.width,
+                                           frame.height).unwrap();
+
+        }
+    }
+
+    /// Gets the frame that was last shown in the window, with layers, post processing and scaling
+    /// applied. Mostly useful to check the result of [`run_frames`](#method.run_frames)
+    pub fn get_frame(&self) -> &RenderTarget {
+        match self.frame {
+            Frame::Screen => &self.render_target,
+            Frame::Composite => &self.composite,
+            Frame::Output => &self.output,
+        }
+    }
+
+    // combines the screen and the visible layers into the composite target
+    fn composite_layers(&mut self) {
+        if self.layers.is_empty() { return; }
+        self.swap_layer();
+
+        let (width, height) = (self.render_target.width, self.render_target.height);
+        if self.composite.width != width || self.composite.height != height {
+            self.composite = RenderTarget::new(width, height);
+        }
+        self.composite.clear(NONE);
+
+        // stable sort keeps the screen below layers with order 0
+        let mut stack = vec![(0, &self.render_target, 1.0)];
+        stack.extend(self.layers.iter().filter(|l| l.visible).map(|l| (l.order, &l.target, l.opacity)));
+        stack.sort_by_key(|&(order, ..)| order);
+        for (_, target, opacity) in stack {
+            let tint = Color::rgba(255, 255, 255, (opacity * 255.0).round() as u8);
+            self.composite.blit_tinted(0, 0, target, tint);
+        }
+
+        self.swap_layer();
+    }
+
+    /// Saves the current transform, restore it with [`pop_transform`]
+    ///
+    /// [`pop_transform`]: #method.pop_transform
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// // tank
+    /// core.push_transform();
+    /// core.translate(200.0, 150.0);
+    /// core.fill_rect(-20, -10, 40, 20, GREEN);
+    ///
+    /// // turret, positioned relative to the tank
+    /// core.push_transform();
+    /// core.rotate(0.3);
+    /// core.fill_rect(0, -2, 25, 4, WHITE);
+    /// core.pop_transform();
+    ///
+    /// core.pop_transform();
+    /// ```
+    pub fn push_transform(&mut self) {
+        self.transform_stack.push(self.local_transform);
+    }
+
+    /// Restores the last transform saved with [`push_transform`],
+    /// resets the transform if there is nothing to restore
+    ///
+    /// [`push_transform`]: #method.push_transform
+    pub fn pop_transform(&mut self) {
+        let transform = self.transform_stack.pop().unwrap_or(Transform::IDENTITY);
+        self.set_transform(transform);
+    }
+
+    /// Moves everything drawn after this call by `(x, y)`
+    ///
+    /// Like [`rotate`] and [`scale`] this is relative to the current transform
+    ///
+    /// [`rotate`]: #method.rotate
+    /// [`scale`]: #method.scale
+    pub fn translate(&mut self, x: f32, y: f32) {
+        self.set_transform(Transform::translation(x, y).then(self.local_transform));
+    }
+
+    /// Rotates everything drawn after this call around the current origin,
+    /// `angle` is in radians, increasing clockwise
+    ///
+    /// Circles and ellipses are only moved and scaled, they keep their orientation
+    pub fn rotate(&mut self, angle: f32) {
+        self.set_transform(Transform::rotation(angle).then(self.local_transform));
+    }
+
+    /// Scales everything drawn after this call relative to the current origin
+    ///
+    /// Radii of circles are scaled by the average of both factors
+    pub fn scale(&mut self, sx: f32, sy: f32) {
+        self.set_transform(Transform::scaling(sx, sy).then(self.local_transform));
+    }
+
+    /// Resets the transform, the saved transforms are kept
+    pub fn reset_transform(&mut self) {
+        self.set_transform(Transform::IDENTITY);
+    }
+
+    /// Replaces the current transform
+    pub fn set_transform(&mut self, transform: Transform) {
+        self.local_transform = transform;
+        self.update_transform();
+    }
+
+    /// Gets the current transform, without the camera
+    pub fn get_transform(&self) -> Transform {
+        self.local_transform
+    }
+
+    /// Sets the camera used to draw everything in world coordinates, `None` draws in screen
+    /// coordinates
+    ///
+    /// The camera applies after the transform, so the transform places things in the world
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// # let (player_x, player_y) = (1000.0, 500.0);
+    /// core.set_camera(Some(Camera2D { position: (player_x, player_y), zoom: 2.0, ..Camera2D::default() }));
+    /// core.fill_circle(player_x as i32, player_y as i32, 8, GREEN);
+    ///
+    /// // the ui stays in place
+    /// core.set_camera(None);
+    /// core.draw_text(10, 10, "HP 100", WHITE);
+    /// ```
+    pub fn set_camera(&mut self, camera: Option<Camera2D>) {
+        self.camera = camera;
+        self.update_transform();
+    }
+
+    /// Gets the current camera
+    pub fn get_camera(&self) -> Option<Camera2D> {
+        self.camera
+    }
+
+    /// Converts world coordinates to screen coordinates using the camera
+    pub fn world_to_screen(&self, x: f32, y: f32) -> (f32, f32) {
+        self.camera_view().apply(x, y)
+    }
+
+    /// Converts screen coordinates to world coordinates using the camera, useful to find
+    /// what's under the mouse
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// if let Some((x, y)) = core.get_mouse_pos() {
+    ///     let (world_x, world_y) = core.screen_to_world(x, y);
+    /// }
+    /// ```
+    pub fn screen_to_world(&self, x: f32, y: f32) -> (f32, f32) {
+        self.camera_view().inverse().map_or((x, y), |inverse| inverse.apply(x, y))
+    }
+
+    // transform from world to screen coordinates
+    fn camera_view(&self) -> Transform {
+        let (width, height) = self.get_logical_size();
+        self.camera.map_or(Transform::IDENTITY, |camera| camera.view(width as f32, height as f32))
+    }
+
+    // transform from world to render target coordinates
+    fn update_transform(&mut self) {
+        let s = self.supersample as f32;
+        self.transform = self.local_transform.then(self.camera_view()).then(Transform::scaling(s, s));
+    }
+
+    /// Clears the screen with the provided color
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// core.clear(NONE);
+    /// ```
+    pub fn clear(&mut self, color: Color) {
+        self.render_target.clear(color);
+    }
+
+    /// Iterates over every pixel of the screen with its location, row by row
+    ///
+    /// Like all pixel access this uses screen coordinates, the transform and camera don't apply
+    pub fn pixels(&self) -> impl Iterator<Item = (i32, i32, Color)> + '_ {
+        self.render_target.pixels()
+    }
+
+    /// Iterates over every pixel of the screen with its location row by row so it can be changed,
+    /// see [`RenderTarget::pixels_mut`]
+    ///
+    /// [`RenderTarget::pixels_mut`]: struct.RenderTarget.html#method.pixels_mut
+    pub fn pixels_mut(&mut self) -> impl Iterator<Item = (i32, i32, &mut u32)> + '_ {
+        self.render_target.pixels_mut()
+    }
+
+    /// Replaces every pixel of the screen inside the clip rect with the result of `f`, much faster
+    /// than calling [`draw`] for every pixel
+    ///
+    /// [`draw`]: #method.draw
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// # let time = 0.0;
+    /// // plasma
+    /// core.map_pixels(|x, y, _| {
+    ///     let v = ((x as f32 * 0.05 + time).sin() + (y as f32 * 0.03 - time).cos()) * 63.0 + 128.0;
+    ///     Color::rgb(v as u8, 64, 255 - v as u8)
+    /// });
+    /// ```
+    pub fn map_pixels<F: FnMut(i32, i32, Color) -> Color>(&mut self, f: F) {
+        self.render_target.map_pixels(f);
+    }
+
+    /// Draws a pixel if the location is in bounds
+    ///
+    /// Colors with an alpha value below 255 are blended with the existing contents of the screen
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// core.draw(10, 10, WHITE);
+    /// ```
+    pub fn draw(&mut self, x: i32, y: i32, color: Color) {
+        let (sx, sy) = self.transform.scale_factors();
+        if sx > 1.0 || sy > 1.0 {
+            // keeps the pixels connected when scaled up
+            return self.fill_rect(x, y, 1, 1, color);
+        }
+        let (x, y) = self.transform_point(x, y);
+        self.plot(x, y, color);
+    }
+
+    /// Draws the pixel closest to `(x, y)`
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// core.draw_f(10.4, 10.6, WHITE);
+    /// ```
+    pub fn draw_f(&mut self, x: f32, y: f32, color: Color) {
+        self.draw(round(x), round(y), color);
+    }
+
+    /// Fills the area of connected pixels with the same color as `(x, y)`
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// core.draw_circle(100, 100, 20, WHITE);
+    /// core.flood_fill(100, 100, RED);
+    /// ```
+    pub fn flood_fill(&mut self, x: i32, y: i32, color: Color) {
+        let (x, y) = self.transform_point(x, y);
+        self.render_target.flood_fill(x, y, color);
+    }
+
+    /// Draws the sprite with its upper left corner at `(x, y)`
+    ///
+    /// Transparent pixels are skipped and translucent pixels are blended using the current blend mode,
+    /// use [`BlendMode::Replace`] to copy them without blending
+    ///
+    /// [`BlendMode::Replace`]: enum.BlendMode.html#variant.Replace
+    ///
+    /// ### Example
+    #[cfg_attr(feature = "png", doc = "```no_run")]
+    #[cfg_attr(not(feature = "png"), doc = "```ignore")]
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// let player = Sprite::from_png("assets/player.png").unwrap();
+    /// core.draw_sprite(100, 100, &player);
+    /// ```
+    pub fn draw_sprite(&mut self, x: i32, y: i32, sprite: &Sprite) {
+        if !self.transform.is_identity() {
+            return self.draw_sprite_scaled(x, y, sprite, 1.0, 1.0, Filter::Nearest);
+        }
+
+        self.render_target.blit_sprite(x, y, sprite, Flip::None, WHITE);
+    }
+
+    /// Draws the sprite with its upper left corner at `(x, y)`, every pixel is multiplied with `tint`
+    ///
+    /// ### Example
+    #[cfg_attr(feature = "png", doc = "```no_run")]
+    #[cfg_attr(not(feature = "png"), doc = "```ignore")]
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// let enemy = Sprite::from_png("assets/enemy.png").unwrap();
+    ///
+    /// // half transparent and red
+    /// core.draw_sprite_tinted(100, 100, &enemy, Color::rgba(255, 0, 0, 128));
+    /// ```
+    pub fn draw_sprite_tinted(&mut self, x: i32, y: i32, sprite: &Sprite, tint: Color) {
+        if !self.transform.is_identity() {
+            let mapping = Transform::translation(x as f32, y as f32).then(self.transform);
+            return self.draw_sprite_mapped(sprite, mapping, Filter::Nearest, tint);
+        }
+
+        self.render_target.blit_sprite(x, y, sprite, Flip::None, tint);
+    }
+
+    /// Draws the part of the sprite inside `src` with its upper left corner at `(x, y)`
+    ///
+    /// The part of `src` outside of the sprite is skipped, the rest stays at the same position
+    ///
+    /// ### Example
+    #[cfg_attr(feature = "png", doc = "```no_run")]
+    #[cfg_attr(not(feature = "png"), doc = "```ignore")]
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// # let health = 0.5;
+    /// let bar = Sprite::from_png("assets/health.png").unwrap();
+    ///
+    /// // only reveal the filled part of the bar
+    /// let filled = (bar.width as f32 * health) as i32;
+    /// core.draw_sprite_part(10, 10, &bar, Rect::new(0, 0, filled, bar.height as i32));
+    /// ```
+    pub fn draw_sprite_part(&mut self, x: i32, y: i32, sprite: &Sprite, src: Rect) {
+        let bounds = Rect::new(0, 0, sprite.width as i32, sprite.height as i32);
+        let part = match src.intersect(bounds) {
+            Some(part) => part,
+            None => return,
+        };
+        let (x, y) = (x + part.x - src.x, y + part.y - src.y);
+
+        if !self.transform.is_identity() {
+            let part_sprite = sprite.crop(part.x as usize, part.y as usize, part.width as usize, part.height as usize);
+            let mapping = Transform::translation(x as f32, y as f32).then(self.transform);
+            return self.draw_sprite_mapped(&part_sprite, mapping, Filter::Nearest, WHITE);
+        }
+
+        self.render_target.blit_sprite_part(x, y, sprite, part, Flip::None, WHITE);
+    }
+
+    /// Draws the sprite mirrored with its upper left corner at `(x, y)`
+    ///
+    /// Scaled and rotated sprites can be mirrored with a negative [`scale`]
+    ///
+    /// [`scale`]: #method.scale
+    ///
+    /// ### Example
+    #[cfg_attr(feature = "png", doc = "```no_run")]
+    #[cfg_attr(not(feature = "png"), doc = "```ignore")]
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// # let facing_left = true;
+    /// let player = Sprite::from_png("assets/player.png").unwrap();
+    /// let flip = if facing_left { Flip::Horizontal } else { Flip::None };
+    /// core.draw_sprite_flipped(100, 100, &player, flip);
+    /// ```
+    pub fn draw_sprite_flipped(&mut self, x: i32, y: i32, sprite: &Sprite, flip: Flip) {
+        if !self.transform.is_identity() {
+            let (w, h) = (sprite.width as f32, sprite.height as f32);
+            let (sx, tx) = if flip.horizontal() { (-1.0, w) } else { (1.0, 0.0) };
+            let (sy, ty) = if flip.vertical() { (-1.0, h) } else { (1.0, 0.0) };
+            let mapping = Transform::scaling(sx, sy).then(Transform::translation(x as f32 + tx, y as f32 + ty));
+            return self.draw_sprite_mapped(sprite, mapping.then(self.transform), Filter::Nearest, WHITE);
+        }
+
+        self.render_target.blit_sprite(x, y, sprite, flip, WHITE);
+    }
+
+    /// Draws the sprite with its upper left corner at `(x, y)`, scaled by `sx` horizontally
+    /// and `sy` vertically
+    ///
+    /// ### Example
+    #[cfg_attr(feature = "png", doc = "```no_run")]
+    #[cfg_attr(not(feature = "png"), doc = "```ignore")]
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// let player = Sprite::from_png("assets/player.png").unwrap();
+    /// core.draw_sprite_scaled(100, 100, &player, 4.0, 4.0, Filter::Nearest);
+    /// ```
+    pub fn draw_sprite_scaled(&mut self, x: i32, y: i32, sprite: &Sprite, sx: f32, sy: f32, filter: Filter) {
+        if sx <= 0.0 || sy <= 0.0 { return; }
+        if !self.transform.is_identity() {
+            let mapping = Transform::scaling(sx, sy).then(Transform::translation(x as f32, y as f32));
+            return self.draw_sprite_mapped(sprite, mapping.then(self.transform), filter, WHITE);
+        }
+
+        // destination rectangle clipped to the screen, sizes beyond i32 are clamped
+        let width = round((sprite.width as f32 * sx).min(i32::MAX as f32));
+        let height = round((sprite.height as f32 * sy).min(i32::MAX as f32));
+
+        // sample the mip level closest to the drawn size instead
+        let source = sprite.mip_for_scale(sx.max(sy));
+        let (mx, my) = (source.width as f32 / sprite.width as f32, source.height as f32 / sprite.height as f32);
+        let (x1, y1) = (x.max(0), y.max(0));
+        let x2 = x.saturating_add(width).min(self.render_target.width as i32);
+        let y2 = y.saturating_add(height).min(self.render_target.height as i32);
+
+        // sample at the center of every destination pixel
+        for py in y1..y2 {
+            let v = (py as f32 - y as f32 + 0.5) / sy * my;
+            for px in x1..x2 {
+                let u = (px as f32 - x as f32 + 0.5) / sx * mx;
+                self.render_target.set_pixel(px, py, source.sample(u, v, filter));
+            }
+        }
+    }
+
+    /// Draws the sprite with its upper left corner at `(x, y)` rotated by `angle` around `pivot`
+    ///
+    /// `angle` is in radians, increasing clockwise, `pivot` is relative to `(x, y)`
+    ///
+    /// ### Example
+    #[cfg_attr(feature = "png", doc = "```no_run")]
+    #[cfg_attr(not(feature = "png"), doc = "```ignore")]
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// let ship = Sprite::from_png("assets/ship.png").unwrap();
+    ///
+    /// // rotate around the center
+    /// let pivot = (ship.width as f32 / 2.0, ship.height as f32 / 2.0);
+    /// core.draw_sprite_rotated(100, 100, &ship, 0.8, pivot);
+    /// ```
+    pub fn draw_sprite_rotated(&mut self, x: i32, y: i32, sprite: &Sprite, angle: f32, pivot: (f32, f32)) {
+        let (px, py) = (x as f32 + pivot.0, y as f32 + pivot.1);
+        let mapping = Transform::translation(-pivot.0, -pivot.1)
+            .then(Transform::rotation(angle))
+            .then(Transform::translation(px, py));
+        self.draw_sprite_mapped(sprite, mapping.then(self.transform), Filter::Nearest, WHITE);
+    }
+
+    // draws the sprite by mapping every covered pixel back onto the sprite,
+    // mapping transforms sprite coordinates to screen coordinates
+    fn draw_sprite_mapped(&mut self, sprite: &Sprite, mapping: Transform, filter: Filter, tint: Color) {
+        let (sx, sy) = mapping.scale_factors();
+        let source = sprite.mip_for_scale(sx.max(sy));
+        if source.width == 0 || source.height == 0 { return; }
+        let mapping = Transform::scaling(
+            sprite.width as f32 / source.width as f32,
+            sprite.height as f32 / source.height as f32,
+        ).then(mapping);
+        let sprite = source;
+
+        let inverse = match mapping.inverse() {
+            Some(inverse) => inverse,
+            None => return,
+        };
+        let (w, h) = (sprite.width as f32, sprite.height as f32);
+        let corners = [mapping.apply(0.0, 0.0), mapping.apply(w, 0.0), mapping.apply(w, h), mapping.apply(0.0, h)];
+
+        let target = &mut self.render_target;
+        scan_contours(&[&corners], |x1, x2, y| {
+            for x in x1..=x2 {
+                let (u, v) = inverse.apply(x as f32 + 0.5, y as f32 + 0.5);
+                target.set_pixel(x, y, sprite.sample(u, v, filter).modulate(tint));
+            }
+        });
+    }
+
+    /// Draws the current frame of the animation with its upper left corner at `(x, y)`
+    ///
+    /// ### Example
+    #[cfg_attr(feature = "png", doc = "```no_run")]
+    #[cfg_attr(not(feature = "png"), doc = "```ignore")]
+    /// # use rain2d::core::*;
+    /// # use std::time::Duration;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// let sheet = Sprite::from_png("assets/coin.png").unwrap();
+    /// let mut coin = Animation::from_sheet(&sheet, 8, 8, Duration::from_millis(80));
+    ///
+    /// coin.update(Duration::from_millis(16));
+    /// core.draw_animation(50, 50, &coin);
+    /// ```
+    pub fn draw_animation(&mut self, x: i32, y: i32, animation: &Animation) {
+        if let Some(frame) = animation.current_frame() {
+            self.draw_sprite(x, y, frame);
+        }
+    }
+
+    /// Draws a 1 bit image with its upper left corner at `(x, y)`, set bits are drawn in `color`
+    /// and cleared bits are skipped
+    ///
+    /// Every row starts on a new byte with the leftmost pixel in the most significant bit,
+    /// rows missing from `data` are skipped
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// const HEART: [u8; 6] = [
+    ///     0b01101100,
+    ///     0b11111110,
+    ///     0b11111110,
+    ///     0b01111100,
+    ///     0b00111000,
+    ///     0b00010000,
+    /// ];
+    /// core.draw_bitmap(10, 10, &HEART, 7, 6, RED);
+    /// ```
+    pub fn draw_bitmap(&mut self, x: i32, y: i32, data: &[u8], width: usize, height: usize, color: Color) {
+        let stride = width.div_ceil(8);
+        if stride == 0 { return; }
+        let transformed = !self.transform.is_identity();
+
+        for (row, bytes) in data.chunks_exact(stride).take(height).enumerate() {
+            for col in 0..width {
+                if bytes[col / 8] & (0x80 >> (col % 8)) == 0 { continue; }
+                let (px, py) = (x + col as i32, y + row as i32);
+                if transformed {
+                    // keeps the pixels connected when scaled up
+                    self.fill_rect(px, py, 1, 1, color);
+                } else {
+                    self.render_target.set_pixel(px, py, color);
+                }
+            }
+        }
+    }
+
+    /// Runs `draw` with every primitive drawing on `target` instead of the screen
+    ///
+    /// `draw` starts without a transform or camera, both are restored afterwards
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// let mut minimap = RenderTarget::new(64, 64);
+    /// core.draw_to_target(&mut minimap, |core| {
+    ///     core.clear(BLACK);
+    ///     core.scale(0.1, 0.1);
+    ///     core.fill_circle(320, 180, 20, RED);
+    /// });
+    /// core.draw_target(566, 10, &minimap);
+    /// ```
+    pub fn draw_to_target<F: FnOnce(&mut RainCore)>(&mut self, target: &mut RenderTarget, draw: F) {
+        let (camera, transform) = (self.camera.take(), self.local_transform);
+        let supersample = replace(&mut self.supersample, 1);
+        swap(&mut self.render_target, target);
+        self.reset_transform();
+        draw(self);
+        swap(&mut self.render_target, target);
+        self.camera = camera;
+        self.supersample = supersample;
+        self.set_transform(transform);
+    }
+
+    /// Copies the part of the screen, or the layer that is drawn on, inside `rect` into a new render
+    /// target, parts outside of the screen are transparent
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// // draw a complex emblem once
+    /// core.fill_star(32, 32, 30, 12, 5, 0.0, YELLOW);
+    /// core.draw_circle(32, 32, 30, WHITE);
+    /// let emblem = core.snapshot_region(Rect::new(0, 0, 64, 64));
+    ///
+    /// // and stamp it everywhere
+    /// core.clear(NONE);
+    /// for i in 0..10 {
+    ///     core.draw_target(i * 64, 100, &emblem);
+    /// }
+    /// ```
+    pub fn snapshot_region(&self, rect: Rect) -> RenderTarget {
+        let s = self.supersample as i32;
+        let (width, height) = (rect.width.max(0) as usize, rect.height.max(0) as usize);
+        let mut snapshot = RenderTarget::new(width * s as usize, height * s as usize);
+        snapshot.blend_mode = BlendMode::Replace;
+        let src = Rect::new(rect.x * s, rect.y * s, rect.width * s, rect.height * s);
+        snapshot.blit(0, 0, &self.render_target, Some(src));
+        if s > 1 {
+            let mut downsampled = RenderTarget::new(width, height);
+            scaling::downsample(&snapshot, &mut downsampled, s as usize);
+            snapshot = downsampled;
+        }
+        snapshot.blend_mode = BlendMode::default();
+        snapshot
+    }
+
+    /// Draws the render target with its upper left corner at `(x, y)`, translucent pixels
+    /// use the blend mode
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// let mut ui = RenderTarget::new(640, 360);
+    /// core.draw_to_target(&mut ui, |core| core.draw_text(10, 10, "paused", WHITE));
+    /// core.draw_target(0, 0, &ui);
+    /// ```
+    pub fn draw_target(&mut self, x: i32, y: i32, target: &RenderTarget) {
+        if !self.transform.is_identity() {
+            let mapping = Transform::translation(x as f32, y as f32).then(self.transform);
+            return self.draw_sprite_mapped(&target.to_sprite(), mapping, Filter::Nearest, WHITE);
+        }
+
+        self.render_target.blit(x, y, target, None);
+    }
+
+    /// Draws text with the built-in 5x7 font with its upper left corner at `(x, y)`
+    ///
+    /// Every character is 6 pixels wide and every line 8 pixels high, `\n` starts a new line and
+    /// characters that aren't printable ascii are shown as `?`
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// # let score = 100;
+    /// core.draw_text(10, 10, &format!("score: {}", score), WHITE);
+    /// ```
+    pub fn draw_text(&mut self, x: i32, y: i32, text: &str, color: Color) {
+        self.draw_text_scaled(x, y, text, 1, color);
+    }
+
+    /// Draws text with the built-in font like [`draw_text`], every pixel of the font becomes
+    /// a square of `scale` by `scale` pixels
+    ///
+    /// [`draw_text`]: #method.draw_text
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// core.draw_text_scaled(200, 150, "GAME OVER", 4, RED);
+    /// ```
+    pub fn draw_text_scaled(&mut self, x: i32, y: i32, text: &str, scale: i32, color: Color) {
+        if scale <= 0 { return; }
+        self.draw_text_ttf(x, y, text, &Font::builtin(), (font::LINE_HEIGHT * scale) as f32, color);
+    }
+
+    /// Draws text with a font with its upper left corner at `(x, y)`, `size` is the
+    /// height of the font in pixels
+    ///
+    /// Edges of TrueType fonts are anti-aliased and blended with the existing contents of
+    /// the screen, `\n` starts a new line
+    ///
+    /// ### Example
+    #[cfg_attr(feature = "truetype", doc = "```no_run")]
+    #[cfg_attr(not(feature = "truetype"), doc = "```ignore")]
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// let font = Font::from_file("assets/font.ttf").unwrap();
+    /// core.draw_text_ttf(10, 10, "Hello world", &font, 32.0, WHITE);
+    /// ```
+    pub fn draw_text_ttf(&mut self, x: i32, y: i32, text: &str, font: &Font, size: f32, color: Color) {
+        let mut pen = Pen::new(x as f32, y as f32);
+        self.draw_text_run(text, font, size, x as f32, &mut pen, color);
+    }
+
+    /// Draws a sequence of differently colored spans as one piece of text with its upper left
+    /// corner at `(x, y)`
+    ///
+    /// Every span continues where the previous one ended including kerning, `\n` starts a new
+    /// line. The spans take up the same space as their joined text in [`measure_text`]
+    ///
+    /// [`measure_text`]: #method.measure_text
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// let spans = [("Press ", WHITE), ("[SPACE]", YELLOW), (" to jump", WHITE)];
+    /// core.draw_text_spans(10, 10, &spans, &Font::builtin(), 16.0);
+    /// ```
+    pub fn draw_text_spans(&mut self, x: i32, y: i32, spans: &[(&str, Color)], font: &Font, size: f32) {
+        let mut pen = Pen::new(x as f32, y as f32);
+        for &(text, color) in spans {
+            self.draw_text_run(text, font, size, x as f32, &mut pen, color);
+        }
+    }
+
+    // draws text starting at the pen and moves the pen past it, new lines start at left
+    fn draw_text_run(&mut self, text: &str, font: &Font, size: f32, left: f32, pen: &mut Pen, color: Color) {
+        let scale = font.scale(size);
+        match &font.kind {
+            FontKind::Builtin => {
+                let scale = scale as i32;
+                font.layout(text, size, left, pen, |c, x, y| {
+                    let (x, y, glyph) = (x.round() as i32, y.round() as i32, font::glyph(c));
+                    if scale == 1 {
+                        return self.draw_bitmap(x, y, glyph, font::GLYPH_WIDTH, font::GLYPH_HEIGHT, color);
+                    }
+                    for (row, &bits) in glyph.iter().enumerate() {
+                        for col in 0..font::GLYPH_WIDTH {
+                            if bits & (0x80 >> col) == 0 { continue; }
+                            self.fill_rect(x + col as i32 * scale, y + row as i32 * scale, scale, scale, color);
+                        }
+                    }
+                });
+            }
+            FontKind::Bitmap(bitmap) => {
+                // pixel fonts stay sharp at whole multiples of their size
+                let filter = if scale.fract() == 0.0 { Filter::Nearest } else { Filter::Bilinear };
+                font.layout(text, size, left, pen, |c, x, y| {
+                    let glyph = match bitmap.glyph(c) {
+                        Some(glyph) => glyph,
+                        None => return,
+                    };
+                    let (x, y) = ((x + glyph.offset.0 * scale).round(), (y + glyph.offset.1 * scale).round());
+                    if scale == 1.0 {
+                        self.draw_sprite_tinted(x as i32, y as i32, &glyph.sprite, color);
+                    } else {
+                        let mapping = Transform::scaling(scale, scale).then(Transform::translation(x, y));
+                        self.draw_sprite_mapped(&glyph.sprite, mapping.then(self.transform), filter, color);
+                    }
+                });
+            }
+            #[cfg(feature = "truetype")]
+            FontKind::TrueType(ttf) => {
+                // font units have y going up from the baseline, overlapping glyphs are
+                // rasterized together so they are only blended once
+                let mut contours = Vec::new();
+                let transform = self.transform;
+                font.layout(text, size, left, pen, |c, x, y| {
+                    let placement = Transform::scaling(scale, -scale).then(Transform::translation(x, y + ttf.ascent() * scale));
+                    contours.extend(ttf.outline(ttf.glyph_index(c), placement.then(transform)));
+                });
+
+                let target = &mut self.render_target;
+                raster::coverage(&contours, |x, y, coverage| target.blend_pixel(x, y, color, coverage));
+            }
+        }
+    }
+
+    /// Draws text with a font like [`draw_text_ttf`] with the outline and shadow of `style`
+    /// behind it
+    ///
+    /// The outline and shadow are measured in pixels on the screen and are drawn once,
+    /// so transparent colors blend evenly
+    ///
+    /// [`draw_text_ttf`]: #method.draw_text_ttf
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// let style = TextStyle { outline: Some((BLACK, 1)), ..TextStyle::default() };
+    /// core.draw_text_styled(10, 10, "HP 100", &Font::builtin(), 8.0, &style, GREEN);
+    /// ```
+    pub fn draw_text_styled(&mut self, x: i32, y: i32, text: &str, font: &Font, size: f32, style: &TextStyle, color: Color) {
+        let thickness = style.outline.map_or(0, |(_, thickness)| thickness.max(0));
+        let (dx, dy) = style.shadow.map_or((0, 0), |(_, dx, dy)| (dx, dy));
+        if thickness == 0 && style.shadow.is_none() {
+            return self.draw_text_ttf(x, y, text, font, size, color);
+        }
+
+        // glyphs can reach outside of the measured size, the font size is used as padding
+        let (w, h) = self.measure_text(text, font, size);
+        let pad = size.ceil().max(0.0);
+        let (x0, y0, x1, y1) = (x as f32 - pad, y as f32 - pad, (x + w) as f32 + pad, (y + h) as f32 + pad);
+        let corners = [(x0, y0), (x1, y0), (x0, y1), (x1, y1)].map(|(px, py)| self.transform.apply(px, py));
+        let (min_x, min_y) = corners.iter().fold((f32::MAX, f32::MAX), |(mx, my), &(px, py)| (mx.min(px), my.min(py)));
+        let (max_x, max_y) = corners.iter().fold((f32::MIN, f32::MIN), |(mx, my), &(px, py)| (mx.max(px), my.max(py)));
+
+        // glyphs just outside the screen can still have their outline or shadow on it
+        let reach = thickness + dx.abs().max(dy.abs());
+        let (width, height) = (self.render_target.width as i32, self.render_target.height as i32);
+        let screen = Rect::new(-reach, -reach, width + 2 * reach, height + 2 * reach);
+        let (min_x, min_y) = (min_x.floor() as i32, min_y.floor() as i32);
+        let bounds = Rect::new(min_x, min_y, max_x.ceil() as i32 - min_x, max_y.ceil() as i32 - min_y);
+        let bounds = match bounds.intersect(screen) {
+            Some(bounds) => bounds,
+            None => return,
+        };
+
+        // draw the text on its own to get the coverage of every pixel
+        let mut mask = RenderTarget::new(bounds.width as usize, bounds.height as usize);
+        swap(&mut mask, &mut self.render_target);
+        let offset = self.transform.then(Transform::translation(-bounds.x as f32, -bounds.y as f32));
+        let transform = replace(&mut self.transform, offset);
+        self.draw_text_ttf(x, y, text, font, size, WHITE);
+        self.transform = transform;
+        swap(&mut mask, &mut self.render_target);
+        let glyph = |px: i32, py: i32| mask.get_pixel(px - bounds.x, py - bounds.y).map_or(0.0, |c| c.a as f32 / 255.0);
+
+        // the outline covers every pixel within its thickness of the text
+        let area = Rect::new(bounds.x - thickness, bounds.y - thickness, bounds.width + 2 * thickness, bounds.height + 2 * thickness);
+        let mut shape = Vec::with_capacity((area.width * area.height) as usize);
+        for py in area.y..area.bottom() {
+            for px in area.x..area.right() {
+                let mut coverage = glyph(px, py);
+                for oy in -thickness..=thickness {
+                    for ox in -thickness..=thickness {
+                        if ox * ox + oy * oy <= thickness * thickness {
+                            coverage = coverage.max(glyph(px + ox, py + oy));
+                        }
+                    }
+                }
+                shape.push(coverage);
+            }
+        }
+
+        let target = &mut self.render_target;
+        let mut draw_shape = |color: Color, dx: i32, dy: i32| {
+            for (i, &coverage) in shape.iter().enumerate().filter(|(_, &c)| c > 0.0) {
+                let (px, py) = (area.x + i as i32 % area.width, area.y + i as i32 / area.width);
+                target.blend_pixel(px + dx, py + dy, color, coverage);
+            }
+        };
+        if let Some((shadow, dx, dy)) = style.shadow {
+            draw_shape(shadow, dx, dy);
+        }
+        if let Some((outline, _)) = style.outline.filter(|_| thickness > 0) {
+            draw_shape(outline, 0, 0);
+        }
+        for py in bounds.y..bounds.bottom() {
+            for px in bounds.x..bounds.right() {
+                let coverage = glyph(px, py);
+                if coverage > 0.0 {
+                    target.blend_pixel(px, py, color, coverage);
+                }
+            }
+        }
+    }
+
+    /// Draws text inside `rect` with `font`, lines are wrapped to fit in the width of the rect and
+    /// aligned with `align` and `valign`
+    ///
+    /// Lines break at `\n` and at spaces, words that are wider than the rect are broken between
+    /// two characters. Lines are spaced by the line height of the font, text that doesn't fit
+    /// in the height of the rect is drawn outside of it
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// let dialogue = Rect::new(20, 260, 600, 80);
+    /// core.fill_rect(dialogue.x, dialogue.y, dialogue.width, dialogue.height, BLACK);
+    /// core.draw_text_wrapped(dialogue, "It's dangerous to go alone! Take this.", &Font::builtin(), 16.0,
+    ///                        TextAlign::Center, VerticalAlign::Middle, WHITE);
+    /// ```
+    pub fn draw_text_wrapped(&mut self, rect: Rect, text: &str, font: &Font, size: f32, align: TextAlign, valign: VerticalAlign, color: Color) {
+        let lines = text::wrap(text, font, size, rect.width as f32);
+        let line_height = font.line_height(size);
+        let free_height = rect.height as f32 - lines.len() as f32 * line_height;
+        let top = rect.y as f32 + match valign {
+            VerticalAlign::Top => 0.0,
+            VerticalAlign::Middle => free_height / 2.0,
+            VerticalAlign::Bottom => free_height,
+        };
+
+        for (i, (line, width)) in lines.into_iter().enumerate() {
+            let left = rect.x as f32 + match align {
+                TextAlign::Left => 0.0,
+                TextAlign::Center => (rect.width as f32 - width) / 2.0,
+                TextAlign::Right => rect.width as f32 - width,
+            };
+            let y = top + i as f32 * line_height;
+            self.draw_text_ttf(left.round() as i32, y.round() as i32, line, font, size, color);
+        }
+    }
+
+    /// Gets the width and height in pixels that `text` takes up when drawn with `font` at `size`
+    ///
+    /// The width is the widest line including the space after its last character, the height
+    /// is the number of lines times the line height of the font
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// # let score = 100;
+    /// // right align the score
+    /// let font = Font::builtin();
+    /// let text = format!("{}", score);
+    /// let (w, _) = core.measure_text(&text, &font, 16.0);
+    /// core.draw_text_ttf(630 - w, 10, &text, &font, 16.0, WHITE);
+    /// ```
+    pub fn measure_text(&self, text: &str, font: &Font, size: f32) -> (i32, i32) {
+        if text.is_empty() { return (0, 0); }
+        let width = text.split('\n').map(|line| font.line_width(line, size)).fold(0.0, f32::max);
+        let height = text.split('\n').count() as f32 * font.line_height(size);
+        (width.ceil() as i32, height.ceil() as i32)
+    }
+
+    /// Draws a line from `(x1, y1)` to `(x2, y2)`
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// core.draw_line(10, 10, 100, 50, WHITE);
+    /// ```
+    pub fn draw_line(&mut self, x1: i32, y1: i32, x2: i32, y2: i32, color: Color) {
+        if self.supersample > 1 && self.block == 1 {
+            return self.draw_logical(|core| core.draw_line(x1, y1, x2, y2, color));
+        }
+        if !self.transform.is_identity() {
+            let ((x1, y1), (x2, y2)) = (self.transform_point(x1, y1), self.transform_point(x2, y2));
+            return self.untransformed(|core| core.draw_line(x1, y1, x2, y2, color));
+        }
+
+        // the end point is never drawn, regardless of direction
+        let (width, height) = self.plot_size();
+        for (x, y) in ClippedLine::new((x1, y1), (x2, y2), 0, 0, width, height) {
+            self.plot(x, y, color);
+        }
+    }
+
+    /// Draws a line from `(x1, y1)` to `(x2, y2)`, the end points are rounded to the closest pixel
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// core.draw_line_f(10.5, 10.2, 100.7, 50.1, WHITE);
+    /// ```
+    pub fn draw_line_f(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, color: Color) {
+        self.draw_line(round(x1), round(y1), round(x2), round(y2), color);
+    }
+
+    /// Draws a line from `(x1, y1)` to `(x2, y2)` using the specified style
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// core.draw_line_styled(10, 10, 100, 50, LineStyle::Dashed { dash: 4, gap: 2 }, WHITE);
+    /// ```
+    pub fn draw_line_styled(&mut self, x1: i32, y1: i32, x2: i32, y2: i32, style: LineStyle, color: Color) {
+        self.draw_line_pattern(x1, y1, x2, y2, style, &mut 0, color);
+    }
+
+    // draws a styled line, step is the position in the pattern and is updated so patterns
+    // continue across connected lines
+    fn draw_line_pattern(&mut self, x1: i32, y1: i32, x2: i32, y2: i32, style: LineStyle, step: &mut u32, color: Color) {
+        if self.supersample > 1 && self.block == 1 {
+            return self.draw_logical(|core| core.draw_line_pattern(x1, y1, x2, y2, style, step, color));
+        }
+        if !self.transform.is_identity() {
+            let ((x1, y1), (x2, y2)) = (self.transform_point(x1, y1), self.transform_point(x2, y2));
+            return self.untransformed(|core| core.draw_line_pattern(x1, y1, x2, y2, style, step, color));
+        }
+
+        // pixels outside of the screen still advance the pattern
+        let (width, height) = self.plot_size();
+        let line = ClippedLine::new((x1, y1), (x2, y2), 0, 0, width, height);
+        let (first, len) = (*step + line.first_step(), line.unclipped_len());
+        for (i, (x, y)) in line.enumerate() {
+            if style.is_drawn(first + i as u32) {
+                self.plot(x, y, color);
+            }
+        }
+        *step += len;
+    }
+
+    /// Draws a line from `(x1, y1)` to `(x2, y2)` that is `thickness` pixels wide
+    ///
+    /// Unlike [`draw_line`] both end points are included
+    ///
+    /// [`draw_line`]: #method.draw_line
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// core.draw_line_thick(10, 10, 100, 50, 4, WHITE);
+    /// ```
+    pub fn draw_line_thick(&mut self, x1: i32, y1: i32, x2: i32, y2: i32, thickness: i32, color: Color) {
+        if thickness <= 1 {
+            self.draw_line(x1, y1, x2, y2, color);
+            self.draw(x2, y2, color);
+            return;
+        }
+
+        let (dx, dy) = ((x2 - x1) as f32, (y2 - y1) as f32);
+        let len = f32::sqrt(dx * dx + dy * dy);
+        let (ux, uy) = if len == 0.0 { (1.0, 0.0) } else { (dx / len, dy / len) };
+
+        // offsets along the line (half a pixel to cover the end points) and across it
+        let (ax, ay) = (ux * 0.5, uy * 0.5);
+        let h = thickness as f32 / 2.0;
+        let (nx, ny) = (-uy * h, ux * h);
+
+        // rasterize as a quad through the pixel centers
+        let (cx1, cy1) = (x1 as f32 + 0.5 - ax, y1 as f32 + 0.5 - ay);
+        let (cx2, cy2) = (x2 as f32 + 0.5 + ax, y2 as f32 + 0.5 + ay);
+        self.fill_polygon_f32(&[
+            (cx1 + nx, cy1 + ny),
+            (cx2 + nx, cy2 + ny),
+            (cx2 - nx, cy2 - ny),
+            (cx1 - nx, cy1 - ny),
+        ], color);
+    }
+
+    /// Draws an anti-aliased line from `(x1, y1)` to `(x2, y2)`
+    ///
+    /// The line is blended with the existing contents of the screen,
+    /// unlike [`draw_line`] both end points are included
+    ///
+    /// [`draw_line`]: #method.draw_line
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// core.draw_line_aa(10, 10, 100, 50, WHITE);
+    /// ```
+    // https://en.wikipedia.org/wiki/Xiaolin_Wu%27s_line_algorithm
+    pub fn draw_line_aa(&mut self, mut x1: i32, mut y1: i32, mut x2: i32, mut y2: i32, color: Color) {
+        if !self.transform.is_identity() {
+            let ((x1, y1), (x2, y2)) = (self.transform_point(x1, y1), self.transform_point(x2, y2));
+            return self.untransformed(|core| core.draw_line_aa(x1, y1, x2, y2, color));
+        }
+
+        // always step along the major axis from left to right
+        let steep = (y2 - y1).abs() > (x2 - x1).abs();
+        if steep { swap(&mut x1, &mut y1); swap(&mut x2, &mut y2); }
+        if x1 > x2 { swap(&mut x1, &mut x2); swap(&mut y1, &mut y2); }
+
+        let gradient = if x2 == x1 { 0.0 } else { (y2 - y1) as f32 / (x2 - x1) as f32 };
+
+        // split coverage between the two pixels closest to the line
+        let mut y = y1 as f32;
+        for x in x1..=x2 {
+            let (py, frac) = (f32::floor(y) as i32, y - f32::floor(y));
+            let (upper, lower) = if steep { ((py, x), (py + 1, x)) } else { ((x, py), (x, py + 1)) };
+
+            self.render_target.blend_pixel(upper.0, upper.1, color, 1.0 - frac);
+            if frac > 0.0 {
+                self.render_target.blend_pixel(lower.0, lower.1, color, frac);
+            }
+            y += gradient;
+        }
+    }
+
+    /// Draws an anti-aliased line from `(x1, y1)` to `(x2, y2)` with subpixel precision
+    ///
+    /// Pixels are blended with the existing contents of the screen based on coverage,
+    /// so the line moves smoothly when the end points change by less than a pixel
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// core.draw_line_aa_f(10.5, 10.2, 100.7, 50.1, WHITE);
+    /// ```
+    // https://en.wikipedia.org/wiki/Xiaolin_Wu%27s_line_algorithm
+    pub fn draw_line_aa_f(&mut self, mut x1: f32, mut y1: f32, mut x2: f32, mut y2: f32, color: Color) {
+        if !self.transform.is_identity() {
+            let ((x1, y1), (x2, y2)) = (self.transform.apply(x1, y1), self.transform.apply(x2, y2));
+            return self.untransformed(|core| core.draw_line_aa_f(x1, y1, x2, y2, color));
+        }
+
+        // always step along the major axis from left to right
+        let steep = (y2 - y1).abs() > (x2 - x1).abs();
+        if steep { swap(&mut x1, &mut y1); swap(&mut x2, &mut y2); }
+        if x1 > x2 { swap(&mut x1, &mut x2); swap(&mut y1, &mut y2); }
+
+        let gradient = if x2 == x1 { 0.0 } else { (y2 - y1) / (x2 - x1) };
+        let mut plot = |x: i32, y: f32, coverage: f32| {
+            // split coverage between the two pixels closest to the line
+            let (py, frac) = (f32::floor(y) as i32, y - f32::floor(y));
+            let (upper, lower) = if steep { ((py, x), (py + 1, x)) } else { ((x, py), (x, py + 1)) };
+            self.render_target.blend_pixel(upper.0, upper.1, color, (1.0 - frac) * coverage);
+            self.render_target.blend_pixel(lower.0, lower.1, color, frac * coverage);
+        };
+
+        // end points are weighted by how much of their pixel the line covers
+        let (x_start, x_end) = (f32::round(x1), f32::round(x2));
+        let start_gap = 1.0 - (x1 + 0.5 - f32::floor(x1 + 0.5));
+        let end_gap = x2 + 0.5 - f32::floor(x2 + 0.5);
+        if x_start == x_end {
+            plot(x_start as i32, y1 + gradient * (x_start - x1), x2 - x1);
+            return;
+        }
+        plot(x_start as i32, y1 + gradient * (x_start - x1), start_gap);
+        plot(x_end as i32, y2 + gradient * (x_end - x2), end_gap);
+
+        let mut y = y1 + gradient * (x_start + 1.0 - x1);
+        for x in x_start as i32 + 1..x_end as i32 {
+            plot(x, y, 1.0);
+            y += gradient;
+        }
+    }
+
+    /// Draws an arrow from `(x1, y1)` pointing at `(x2, y2)`, the lines of the head are `head_size` long
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// core.draw_arrow(10, 10, 100, 50, 8, WHITE);
+    /// ```
+    pub fn draw_arrow(&mut self, x1: i32, y1: i32, x2: i32, y2: i32, head_size: i32, color: Color) {
+        self.draw_line(x1, y1, x2, y2, color);
+        self.draw(x2, y2, color);
+        if x1 == x2 && y1 == y2 { return; }
+
+        // head lines are 30 degrees off the shaft, pointing back towards the start
+        let angle = ((y1 - y2) as f32).atan2((x1 - x2) as f32);
+        let spread = std::f32::consts::FRAC_PI_6;
+        for &a in [angle - spread, angle + spread].iter() {
+            let hx = x2 + f32::round(head_size as f32 * a.cos()) as i32;
+            let hy = y2 + f32::round(head_size as f32 * a.sin()) as i32;
+            self.draw_line(hx, hy, x2, y2, color);
+        }
+    }
+
+    /// Draws grid lines across the whole screen, with cells of `cell_width` by `cell_height`
+    /// and lines passing through `(origin_x, origin_y)`
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// core.draw_grid(0, 0, 16, 16, Color::rgb(40, 40, 40));
+    /// ```
+    pub fn draw_grid(&mut self, origin_x: i32, origin_y: i32, cell_width: i32, cell_height: i32, color: Color) {
+        self.draw_grid_major(origin_x, origin_y, cell_width, cell_height, color, 0, color);
+    }
+
+    /// Draws grid lines across the whole screen like [`draw_grid`], every `major_every` lines
+    /// starting at the origin are drawn with `major_color` instead
+    ///
+    /// [`draw_grid`]: #method.draw_grid
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// core.draw_grid_major(320, 180, 16, 16, Color::rgb(40, 40, 40), 4, Color::rgb(90, 90, 90));
+    /// ```
+    pub fn draw_grid_major(&mut self, origin_x: i32, origin_y: i32, cell_width: i32, cell_height: i32,
+                           color: Color, major_every: i32, major_color: Color) {
+        if !self.transform.is_identity() {
+            // the grid always covers the screen
+            return self.untransformed(|core| {
+                core.draw_grid_major(origin_x, origin_y, cell_width, cell_height, color, major_every, major_color)
+            });
+        }
+
+        if cell_width <= 0 || cell_height <= 0 { return; }
+        let (width, height) = (self.render_target.width as i32, self.render_target.height as i32);
+        let is_major = |line: i32| major_every > 0 && line.rem_euclid(major_every) == 0;
+
+        // minor lines first so major lines are drawn on top
+        for &major in [false, true].iter() {
+            let line_color = if major { major_color } else { color };
+
+            let mut x = origin_x.rem_euclid(cell_width);
+            while x < width {
+                if is_major((x - origin_x) / cell_width) == major {
+                    self.draw_line(x, 0, x, height, line_color);
+                }
+                x += cell_width;
+            }
+
+            let mut y = origin_y.rem_euclid(cell_height);
+            while y < height {
+                if is_major((y - origin_y) / cell_height) == major {
+                    self.draw_line(0, y, width, y, line_color);
+                }
+                y += cell_height;
+            }
+        }
+    }
+
+    /// Draws a circle at `(x, y)` with radius `r`
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// core.draw_circle(100, 100, 10, WHITE);
+    /// ```
+    pub fn draw_circle(&mut self, x: i32, y: i32, r: i32, color: Color) {
+        if self.supersample > 1 && self.block == 1 {
+            return self.draw_logical(|core| core.draw_circle(x, y, r, color));
+        }
+        if !self.transform.is_identity() {
+            let ((x, y), r) = (self.transform_point(x, y), self.transform_radius(r));
+            return self.untransformed(|core| core.draw_circle(x, y, r, color));
+        }
+
+        let mut x0 = 0;
+        let mut y0 = r;
+        let mut d = 3 - 2 * r;
+        if r <= 0 { return; }
+
+        while y0 >= x0 {
+            self.draw(x + x0, y - y0, color);
+            self.draw(x + y0, y - x0, color);
+            self.draw(x + y0, y + x0, color);
+            self.draw(x + x0, y + y0, color);
+            self.draw(x - x0, y - y0, color);
+            self.draw(x - y0, y - x0, color);
+            self.draw(x - y0, y + x0, color);
+            self.draw(x - x0, y + y0, color);
+            if d < 0 { d += 4 * x0 + 6; x0 += 1; }
+            else { x0 += 1; y0 -= 1; d += 4 * (x0 - y0) + 10; }
+        }
+    }
+
+    /// Draws a circle at `(x, y)` with radius `r`, all values are rounded to the closest pixel
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// core.draw_circle_f(100.4, 100.6, 10.2, WHITE);
+    /// ```
+    pub fn draw_circle_f(&mut self, x: f32, y: f32, r: f32, color: Color) {
+        self.draw_circle(round(x), round(y), round(r), color);
+    }
+
+    /// Draws a circle at `(x, y)` with radius `r` and an outline that is `thickness` pixels wide
+    ///
+    /// The outline is centered on the radius
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// core.draw_circle_thick(100, 100, 10, 3, WHITE);
+    /// ```
+    pub fn draw_circle_thick(&mut self, x: i32, y: i32, r: i32, thickness: i32, color: Color) {
+        if !self.transform.is_identity() {
+            let ((x, y), r) = (self.transform_point(x, y), self.transform_radius(r));
+            let thickness = self.transform_radius(thickness);
+            return self.untransformed(|core| core.draw_circle_thick(x, y, r, thickness, color));
+        }
+
+        if thickness <= 1 {
+            self.draw_circle(x, y, r, color);
+            return;
+        }
+        if r <= 0 { return; }
+
+        let outer = r as f32 + thickness as f32 / 2.0;
+        let inner = f32::max(r as f32 - thickness as f32 / 2.0, 0.0);
+        let bound = f32::ceil(outer) as i32;
+
+        for dy in -bound..=bound {
+            for dx in -bound..=bound {
+                let d = (dx * dx + dy * dy) as f32;
+                if d >= inner * inner && d < outer * outer {
+                    self.draw(x + dx, y + dy, color);
+                }
+            }
+        }
+    }
+
+    /// Draws an anti-aliased circle at `(x, y)` with radius `r`
+    ///
+    /// Edge pixels are blended with the existing contents of the screen based on coverage
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// core.draw_circle_aa(100, 100, 10, WHITE);
+    /// ```
+    pub fn draw_circle_aa(&mut self, x: i32, y: i32, r: i32, color: Color) {
+        if !self.transform.is_identity() {
+            let ((x, y), r) = (self.transform_point(x, y), self.transform_radius(r));
+            return self.untransformed(|core| core.draw_circle_aa(x, y, r, color));
+        }
+
+        if r <= 0 { return; }
+
+        let bound = r + 1;
+        for dy in -bound..=bound {
+            for dx in -bound..=bound {
+                let d = f32::sqrt((dx * dx + dy * dy) as f32);
+                let coverage = 1.0 - (d - r as f32).abs();
+                if coverage > 0.0 {
+                    self.render_target.blend_pixel(x + dx, y + dy, color, coverage);
+                }
+            }
+        }
+    }
+
+    /// Draws a filled in circle at `(x, y)` with radius `r`
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// core.fill_circle(100, 100, 10, WHITE);
+    /// ```
+    pub fn fill_circle(&mut self, x: i32, y: i32, r: i32, color: Color) {
+        if !self.transform.is_identity() {
+            let ((x, y), r) = (self.transform_point(x, y), self.transform_radius(r));
+            return self.untransformed(|core| core.fill_circle(x, y, r, color));
+        }
+
+        let mut x0 = 0;
+        let mut y0 = r;
+        let mut d = 3 - 2 * r;
+        if r <= 0 { return; }
+
+        while y0 >= x0 {
+            self.draw_line(x - x0, y - y0, x + x0, y - y0, color);
+            self.draw_line(x - y0, y - x0, x + y0, y - x0, color);
+            self.draw_line(x - x0, y + y0, x + x0, y + y0, color);
+            self.draw_line(x - y0, y + x0, x + y0, y + x0, color);
+            if d < 0 { d += 4 * x0 + 6; x0 += 1; }
+            else { x0 += 1; y0 -= 1; d += 4 * (x0 - y0) + 10; }
+        }
+    }
+
+    /// Draws a filled in circle at `(x, y)` with radius `r`, all values are rounded to the closest pixel
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// core.fill_circle_f(100.4, 100.6, 10.2, WHITE);
+    /// ```
+    pub fn fill_circle_f(&mut self, x: f32, y: f32, r: f32, color: Color) {
+        self.fill_circle(round(x), round(y), round(r), color);
+    }
+
+    /// Draws part of a circle at `(x, y)` with radius `r` between `start_angle` and `end_angle`
+    ///
+    /// Angles are in radians, starting at the positive x axis and increasing clockwise
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// core.draw_arc(100, 100, 10, 0.0, std::f32::consts::PI, WHITE);
+    /// ```
+    pub fn draw_arc(&mut self, x: i32, y: i32, r: i32, start_angle: f32, end_angle: f32, color: Color) {
+        if self.supersample > 1 && self.block == 1 {
+            return self.draw_logical(|core| core.draw_arc(x, y, r, start_angle, end_angle, color));
+        }
+        if !self.transform.is_identity() {
+            let ((x, y), r) = (self.transform_point(x, y), self.transform_radius(r));
+            let rotation = self.transform.rotation_angle();
+            return self.untransformed(|core| core.draw_arc(x, y, r, start_angle + rotation, end_angle + rotation, color));
+        }
+
+        let mut x0 = 0;
+        let mut y0 = r;
+        let mut d = 3 - 2 * r;
+        if r <= 0 { return; }
+
+        while y0 >= x0 {
+            let octants = [(x0, -y0), (y0, -x0), (y0, x0), (x0, y0),
+                           (-x0, -y0), (-y0, -x0), (-y0, x0), (-x0, y0)];
+            for &(dx, dy) in octants.iter() {
+                if angle_in_range(dx, dy, start_angle, end_angle) {
+                    self.draw(x + dx, y + dy, color);
+                }
+            }
+            if d < 0 { d += 4 * x0 + 6; x0 += 1; }
+            else { x0 += 1; y0 -= 1; d += 4 * (x0 - y0) + 10; }
+        }
+    }
+
+    /// Draws a filled in pie slice at `(x, y)` with radius `r` between `start_angle` and `end_angle`
+    ///
+    /// Angles are in radians, starting at the positive x axis and increasing clockwise
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// core.fill_pie(100, 100, 10, 0.0, std::f32::consts::FRAC_PI_2, WHITE);
+    /// ```
+    pub fn fill_pie(&mut self, x: i32, y: i32, r: i32, start_angle: f32, end_angle: f32, color: Color) {
+        if !self.transform.is_identity() {
+            let ((x, y), r) = (self.transform_point(x, y), self.transform_radius(r));
+            let rotation = self.transform.rotation_angle();
+            return self.untransformed(|core| core.fill_pie(x, y, r, start_angle + rotation, end_angle + rotation, color));
+        }
+
+        if r <= 0 { return; }
+
+        for dy in -r..=r {
+            for dx in -r..=r {
+                if dx * dx + dy * dy > r * r { continue; }
+                if (dx == 0 && dy == 0) || angle_in_range(dx, dy, start_angle, end_angle) {
+                    self.draw(x + dx, y + dy, color);
+                }
+            }
+        }
+    }
+
+    /// Draws an anti-aliased filled in circle at `(x, y)` with radius `r`
+    ///
+    /// Edge pixels are blended with the existing contents of the screen based on coverage
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// core.fill_circle_aa(100, 100, 10, WHITE);
+    /// ```
+    pub fn fill_circle_aa(&mut self, x: i32, y: i32, r: i32, color: Color) {
+        self.fill_circle_aa_f(x as f32, y as f32, r as f32, color);
+    }
+
+    /// Draws an anti-aliased filled in circle at `(x, y)` with radius `r` with subpixel precision
+    ///
+    /// Edge pixels are blended with the existing contents of the screen based on coverage,
+    /// so the circle moves and grows smoothly when the values change by less than a pixel
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// core.fill_circle_aa_f(100.4, 100.6, 10.2, WHITE);
+    /// ```
+    pub fn fill_circle_aa_f(&mut self, x: f32, y: f32, r: f32, color: Color) {
+        if !self.transform.is_identity() {
+            let (x, y) = self.transform.apply(x, y);
+            let (sx, sy) = self.transform.scale_factors();
+            let r = r * (sx + sy) / 2.0;
+            return self.untransformed(|core| core.fill_circle_aa_f(x, y, r, color));
+        }
+
+        if r <= 0.0 { return; }
+
+        for py in f32::floor(y - r - 1.0) as i32..=f32::ceil(y + r + 1.0) as i32 {
+            for px in f32::floor(x - r - 1.0) as i32..=f32::ceil(x + r + 1.0) as i32 {
+                let (dx, dy) = (px as f32 - x, py as f32 - y);
+                let coverage = r + 0.5 - f32::sqrt(dx * dx + dy * dy);
+                if coverage > 0.0 {
+                    self.render_target.blend_pixel(px, py, color, coverage);
+                }
+            }
+        }
+    }
+
+    /// Draws a filled in circle at `(x, y)` with radius `r` and a radial gradient
+    /// from `inner` at the center to `outer` at the edge
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// core.fill_circle_gradient(100, 100, 30, WHITE, BLACK);
+    /// ```
+    pub fn fill_circle_gradient(&mut self, x: i32, y: i32, r: i32, inner: Color, outer: Color) {
+        if !self.transform.is_identity() {
+            let ((x, y), r) = (self.transform_point(x, y), self.transform_radius(r));
+            return self.untransformed(|core| core.fill_circle_gradient(x, y, r, inner, outer));
+        }
+
+        if r <= 0 { return; }
+
+        for dy in -r..=r {
+            for dx in -r..=r {
+                let d = dx * dx + dy * dy;
+                if d <= r * r {
+                    let t = f32::sqrt(d as f32) / r as f32;
+                    self.draw(x + dx, y + dy, inner.lerp(outer, t));
+                }
+            }
+        }
+    }
+
+    /// Draws a circle at `(x, y)` with radius `r` filled with a repeating pattern
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// core.fill_circle_pattern(100, 100, 20, &FillPattern::Mask(CROSSHATCH, WHITE));
+    /// ```
+    pub fn fill_circle_pattern(&mut self, x: i32, y: i32, r: i32, pattern: &FillPattern) {
+        if !self.transform.is_identity() {
+            let ((x, y), r) = (self.transform_point(x, y), self.transform_radius(r));
+            return self.untransformed(|core| core.fill_circle_pattern(x, y, r, pattern));
+        }
+
+        if r <= 0 { return; }
+
+        for dy in -r..=r {
+            // half width of the row
+            let w = f32::sqrt((r * r - dy * dy) as f32) as i32;
+            self.draw_hline_pattern(x - w, x + w, y + dy, pattern);
+        }
+    }
+
+    /// Draws a filled in ring at `(x, y)` covering the area between `inner_r` and `outer_r`
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// core.fill_ring(100, 100, 15, 20, WHITE);
+    /// ```
+    pub fn fill_ring(&mut self, x: i32, y: i32, mut inner_r: i32, mut outer_r: i32, color: Color) {
+        if !self.transform.is_identity() {
+            let (x, y) = self.transform_point(x, y);
+            let (inner_r, outer_r) = (self.transform_radius(inner_r), self.transform_radius(outer_r));
+            return self.untransformed(|core| core.fill_ring(x, y, inner_r, outer_r, color));
+        }
+
+        if inner_r > outer_r { swap(&mut inner_r, &mut outer_r); }
+        if outer_r <= 0 { return; }
+        let inner_r = inner_r.max(0);
+
+        for dy in -outer_r..=outer_r {
+            // half widths of the outer circle and of the hole on this row
+            let outer_w = f32::sqrt((outer_r * outer_r - dy * dy) as f32) as i32;
+            if dy.abs() < inner_r {
+                let inner_w = f32::ceil(f32::sqrt((inner_r * inner_r - dy * dy) as f32)) as i32;
+                self.draw_hline(x - outer_w, x - inner_w, y + dy, color);
+                self.draw_hline(x + inner_w, x + outer_w, y + dy, color);
+            } else {
+                self.draw_hline(x - outer_w, x + outer_w, y + dy, color);
+            }
+        }
+    }
+
+    /// Draws an ellipse at `(x, y)` with horizontal radius `rx` and vertical radius `ry`
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// core.draw_ellipse(100, 100, 20, 10, WHITE);
+    /// ```
+    pub fn draw_ellipse(&mut self, x: i32, y: i32, rx: i32, ry: i32, color: Color) {
+        if self.supersample > 1 && self.block == 1 {
+            return self.draw_logical(|core| core.draw_ellipse(x, y, rx, ry, color));
+        }
+        if !self.transform.is_identity() {
+            let (x, y) = self.transform_point(x, y);
+            let (sx, sy) = self.transform.scale_factors();
+            let (rx, ry) = (round(rx as f32 * sx), round(ry as f32 * sy));
+            return self.untransformed(|core| core.draw_ellipse(x, y, rx, ry, color));
+        }
+
+        if rx <= 0 || ry <= 0 { return; }
+
+        for (x0, y0) in ellipse_quadrant(rx, ry) {
+            self.draw(x + x0, y - y0, color);
+            self.draw(x - x0, y - y0, color);
+            self.draw(x + x0, y + y0, color);
+            self.draw(x - x0, y + y0, color);
+        }
+    }
+
+    /// Draws a filled in ellipse at `(x, y)` with horizontal radius `rx` and vertical radius `ry`
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// core.fill_ellipse(100, 100, 20, 10, WHITE);
+    /// ```
+    pub fn fill_ellipse(&mut self, x: i32, y: i32, rx: i32, ry: i32, color: Color) {
+        if !self.transform.is_identity() {
+            let (x, y) = self.transform_point(x, y);
+            let (sx, sy) = self.transform.scale_factors();
+            let (rx, ry) = (round(rx as f32 * sx), round(ry as f32 * sy));
+            return self.untransformed(|core| core.fill_ellipse(x, y, rx, ry, color));
+        }
+
+        if rx <= 0 || ry <= 0 { return; }
+
+        for (x0, y0) in ellipse_quadrant(rx, ry) {
+            self.draw_hline(x - x0, x + x0, y - y0, color);
+            self.draw_hline(x - x0, x + x0, y + y0, color);
+        }
+    }
+
+    /// Draws a rectangle at `(x, y)` with specified dimensions
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// core.draw_rect(100, 100, 50, 50, WHITE);
+    /// ```
+    pub fn draw_rect(&mut self, x: i32, y: i32, width: i32, height: i32, color: Color) {
+        self.draw_line(x, y, x + width, y, color);
+        self.draw_line(x + width, y, x + width, y + height, color);
+        self.draw_line(x + width, y + height, x, y + height, color);
+        self.draw_line(x, y + height, x, y, color);
+    }
+
+    /// Draws a rectangle at `(x, y)` with specified dimensions, the corners are rounded to the closest pixel
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// core.draw_rect_f(100.4, 100.6, 50.5, 50.5, WHITE);
+    /// ```
+    pub fn draw_rect_f(&mut self, x: f32, y: f32, width: f32, height: f32, color: Color) {
+        let (x1, y1) = (round(x), round(y));
+        let (x2, y2) = (round(x + width), round(y + height));
+        self.draw_rect(x1, y1, x2 - x1, y2 - y1, color);
+    }
+
+    /// Draws a rectangle at `(x, y)` with specified dimensions using the specified line style
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// core.draw_rect_styled(100, 100, 50, 50, LineStyle::Dashed { dash: 4, gap: 4 }, WHITE);
+    /// ```
+    pub fn draw_rect_styled(&mut self, x: i32, y: i32, width: i32, height: i32, style: LineStyle, color: Color) {
+        let mut step = 0;
+        self.draw_line_pattern(x, y, x + width, y, style, &mut step, color);
+        self.draw_line_pattern(x + width, y, x + width, y + height, style, &mut step, color);
+        self.draw_line_pattern(x + width, y + height, x, y + height, style, &mut step, color);
+        self.draw_line_pattern(x, y + height, x, y, style, &mut step, color);
+    }
+
+    /// Draws a rectangle at `(x, y)` with specified dimensions and an outline that is `thickness` pixels wide
+    ///
+    /// The outline is centered on the edges of the rectangle
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// core.draw_rect_thick(100, 100, 50, 50, 3, WHITE);
+    /// ```
+    pub fn draw_rect_thick(&mut self, x: i32, y: i32, width: i32, height: i32, thickness: i32, color: Color) {
+        if thickness <= 1 {
+            self.draw_rect(x, y, width, height, color);
+            return;
+        }
+
+        let (ox, oy) = (x - (thickness - 1) / 2, y - (thickness - 1) / 2);
+        self.fill_rect(ox, oy, width + thickness, thickness, color);
+        self.fill_rect(ox, oy + height, width + thickness, thickness, color);
+        self.fill_rect(ox, oy + thickness, thickness, height - thickness, color);
+        self.fill_rect(ox + width, oy + thickness, thickness, height - thickness, color);
+    }
+
+    /// Draws a filled in rectangle at `(x, y)` with specified dimensions
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// core.fill_rect(100, 100, 50, 50, WHITE);
+    /// ```
+    pub fn fill_rect(&mut self, x: i32, y: i32, width: i32, height: i32, color: Color) {
+        if !self.transform.is_identity() {
+            if width <= 0 || height <= 0 { return; }
+            return self.fill_polygon_f32(&rect_corners(x, y, width, height), color);
+        }
+
+        self.render_target.fill_rect(x, y, width, height, color);
+    }
+
+    /// Draws a filled in rectangle at `(x, y)` with specified dimensions, the corners are rounded
+    /// to the closest pixel so rectangles next to each other never overlap or leave gaps
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// core.fill_rect_f(100.4, 100.6, 50.5, 50.5, WHITE);
+    /// ```
+    pub fn fill_rect_f(&mut self, x: f32, y: f32, width: f32, height: f32, color: Color) {
+        let (x1, y1) = (round(x), round(y));
+        let (x2, y2) = (round(x + width), round(y + height));
+        self.fill_rect(x1, y1, x2 - x1, y2 - y1, color);
+    }
+
+    /// Draws a filled in rectangle at `(x, y)` with specified dimensions and a linear gradient
+    /// from `from` to `to`
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// core.fill_rect_gradient(100, 100, 50, 20, RED, GREEN, GradientDirection::Horizontal);
+    /// ```
+    pub fn fill_rect_gradient(&mut self, x: i32, y: i32, width: i32, height: i32, from: Color, to: Color, direction: GradientDirection) {
+        if !self.transform.is_identity() {
+            if width <= 0 || height <= 0 { return; }
+            let inverse = match self.transform.inverse() {
+                Some(inverse) => inverse,
+                None => return,
+            };
+            let corners = self.transform_points(&rect_corners(x, y, width, height));
+            let steps = match direction {
+                GradientDirection::Horizontal => width - 1,
+                GradientDirection::Vertical => height - 1,
+            };
+
+            // map every pixel back onto the rectangle to find its position in the gradient
+            return self.untransformed(|core| scan_contours(&[&corners], |x1, x2, py| {
+                for px in x1..=x2 {
+                    let (lx, ly) = inverse.apply(px as f32 + 0.5, py as f32 + 0.5);
+                    let step = match direction {
+                        GradientDirection::Horizontal => lx - 0.5 - x as f32,
+                        GradientDirection::Vertical => ly - 0.5 - y as f32,
+                    };
+                    let t = if steps > 0 { step / steps as f32 } else { 0.0 };
+                    core.draw(px, py, from.lerp(to, t));
+                }
+            }));
+        }
+
+        if width <= 0 { return; }
+        let steps = match direction {
+            GradientDirection::Horizontal => width - 1,
+            GradientDirection::Vertical => height - 1,
+        };
+        // color at a distance from the start of the gradient
+        let color = |step: f32| from.lerp(to, if steps > 0 { step / steps as f32 } else { 0.0 });
+
+        // only the rows on the render target are filled
+        let (_, top, _, bottom) = self.render_target.clip();
+        let x2 = x.saturating_add(width - 1);
+        for row in y.max(top)..y.saturating_add(height).min(bottom) {
+            match direction {
+                GradientDirection::Horizontal => {
+                    self.render_target.fill_span_with(x, x2, row, |px| Some(color(px as f32 - x as f32)))
+                }
+                GradientDirection::Vertical => self.render_target.fill_span(x, x2, row, color(row as f32 - y as f32)),
+            }
+        }
+    }
+
+    /// Draws a rectangle at `(x, y)` with specified dimensions filled with a repeating pattern
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// core.fill_rect_pattern(100, 100, 50, 50, &FillPattern::Mask(CHECKERBOARD, WHITE));
+    /// ```
+    pub fn fill_rect_pattern(&mut self, x: i32, y: i32, width: i32, height: i32, pattern: &FillPattern) {
+        if !self.transform.is_identity() {
+            if width <= 0 || height <= 0 { return; }
+            return self.fill_contours_pattern(&[&rect_corners(x, y, width, height)], pattern);
+        }
+
+        if width <= 0 { return; }
+        let (_, top, _, bottom) = self.render_target.clip();
+        for row in y.max(top)..y.saturating_add(height).min(bottom) {
+            self.draw_hline_pattern(x, x.saturating_add(width - 1), row, pattern);
+        }
+    }
+
+    /// Draws a rectangle at `(x, y)` with specified dimensions rotated by `angle` around `pivot`
+    ///
+    /// `angle` is in radians, increasing clockwise, `pivot` is relative to `(x, y)`
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// // rotate around the center
+    /// core.draw_rect_rotated(100, 100, 50, 20, 0.5, (25.0, 10.0), WHITE);
+    /// ```
+    pub fn draw_rect_rotated(&mut self, x: i32, y: i32, width: i32, height: i32, angle: f32, pivot: (f32, f32), color: Color) {
+        let corners = rotated_rect_corners(x, y, width, height, angle, pivot);
+        self.draw_polyline_closed(&to_pixels(&corners), color);
+    }
+
+    /// Draws a filled in rectangle at `(x, y)` with specified dimensions rotated by `angle` around `pivot`
+    ///
+    /// `angle` is in radians, increasing clockwise, `pivot` is relative to `(x, y)`
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// // rotate around the center
+    /// core.fill_rect_rotated(100, 100, 50, 20, 0.5, (25.0, 10.0), WHITE);
+    /// ```
+    pub fn fill_rect_rotated(&mut self, x: i32, y: i32, width: i32, height: i32, angle: f32, pivot: (f32, f32), color: Color) {
+        let corners = rotated_rect_corners(x, y, width, height, angle, pivot);
+        self.fill_polygon_f32(&corners, color);
+    }
+
+    /// Draws a rectangle with rounded corners at `(x, y)` with specified dimensions
+    ///
+    /// `radius` is clamped to half of the smaller dimension
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// core.draw_round_rect(100, 100, 80, 50, 8, WHITE);
+    /// ```
+    pub fn draw_round_rect(&mut self, x: i32, y: i32, width: i32, height: i32, radius: i32, color: Color) {
+        let r = radius.min(width / 2).min(height / 2);
+        if r <= 0 {
+            self.draw_rect(x, y, width, height, color);
+            return;
+        }
+        if !self.transform.is_identity() {
+            let points = round_rect_points(x, y, width, height, r);
+            return self.draw_polyline_closed(&to_pixels(&points), color);
+        }
+
+        // corner centers
+        let (cx1, cy1) = (x + r, y + r);
+        let (cx2, cy2) = (x + width - r, y + height - r);
+
+        // straight edges
+        self.draw_line(cx1, y, cx2, y, color);
+        self.draw_line(cx1, y + height, cx2, y + height, color);
+        self.draw_line(x, cy1, x, cy2, color);
+        self.draw_line(x + width, cy1, x + width, cy2, color);
+
+        // corners
+        for (x0, y0) in circle_octant(r) {
+            for &(dx, dy) in [(x0, y0), (y0, x0)].iter() {
+                self.draw(cx1 - dx, cy1 - dy, color);
+                self.draw(cx2 + dx, cy1 - dy, color);
+                self.draw(cx1 - dx, cy2 + dy, color);
+                self.draw(cx2 + dx, cy2 + dy, color);
+            }
+        }
+    }
+
+    /// Draws a filled in rectangle with rounded corners at `(x, y)` with specified dimensions
+    ///
+    /// `radius` is clamped to half of the smaller dimension
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// core.fill_round_rect(100, 100, 80, 50, 8, WHITE);
+    /// ```
+    pub fn fill_round_rect(&mut self, x: i32, y: i32, width: i32, height: i32, radius: i32, color: Color) {
+        let r = radius.min(width / 2).min(height / 2);
+        if r <= 0 {
+            self.fill_rect(x, y, width, height, color);
+            return;
+        }
+        if !self.transform.is_identity() {
+            return self.fill_polygon_f32(&round_rect_points(x, y, width, height, r), color);
+        }
+
+        // corner centers, same extents as fill_rect
+        let (cx1, cy1) = (x + r, y + r);
+        let (cx2, cy2) = (x + width - 1 - r, y + height - 1 - r);
+
+        // corner rows
+        for (x0, y0) in circle_octant(r) {
+            self.draw_hline(cx1 - x0, cx2 + x0, cy1 - y0, color);
+            self.draw_hline(cx1 - y0, cx2 + y0, cy1 - x0, color);
+            self.draw_hline(cx1 - x0, cx2 + x0, cy2 + y0, color);
+            self.draw_hline(cx1 - y0, cx2 + y0, cy2 + x0, color);
+        }
+
+        // middle section
+        self.fill_rect(x, cy1, width, cy2 - cy1 + 1, color);
+    }
+
+    /// Draws a triangle with vertices `(x1, y1)`, `(x2, y2)` and `(x3, y3)`
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// core.draw_triangle(25, 100, 75, 100, 50, 0, WHITE);
+    /// ```
+    pub fn draw_triangle(&mut self, x1: i32, y1: i32, x2: i32, y2: i32, x3: i32, y3: i32, color: Color) {
+        self.draw_line(x1, y1, x2, y2, color);
+        self.draw_line(x2, y2, x3, y3, color);
+        self.draw_line(x3, y3, x1, y1, color);
+    }
+
+    /// Draws a triangle with vertices `(x1, y1)`, `(x2, y2)` and `(x3, y3)` and an outline
+    /// that is `thickness` pixels wide
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// core.draw_triangle_thick(25, 100, 75, 100, 50, 0, 3, WHITE);
+    /// ```
+    pub fn draw_triangle_thick(&mut self, x1: i32, y1: i32, x2: i32, y2: i32, x3: i32, y3: i32, thickness: i32, color: Color) {
+        if thickness <= 1 {
+            self.draw_triangle(x1, y1, x2, y2, x3, y3, color);
+            return;
+        }
+
+        self.draw_line_thick(x1, y1, x2, y2, thickness, color);
+        self.draw_line_thick(x2, y2, x3, y3, thickness, color);
+        self.draw_line_thick(x3, y3, x1, y1, thickness, color);
+
+        // round off the corners
+        let r = (thickness - 1) / 2;
+        self.fill_circle(x1, y1, r, color);
+        self.fill_circle(x2, y2, r, color);
+        self.fill_circle(x3, y3, r, color);
+    }
+
+    /// Draws a filled in triangle with vertices `(x1, y1)`, `(x2, y2)` and `(x3, y3)`
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// core.fill_triangle(25, 100, 75, 100, 50, 0, WHITE);
+    /// ```
+    // http://www.sunshine2k.de/coding/java/TriangleRasterization/TriangleRasterization.html
+    pub fn fill_triangle(&mut self, mut x1: i32, mut y1: i32, mut x2: i32, mut y2: i32, mut x3: i32, mut y3: i32, color: Color) {
+        if !self.transform.is_identity() {
+            let ((x1, y1), (x2, y2)) = (self.transform_point(x1, y1), self.transform_point(x2, y2));
+            let (x3, y3) = self.transform_point(x3, y3);
+            return self.untransformed(|core| core.fill_triangle(x1, y1, x2, y2, x3, y3, color));
+        }
+
+        // sort vertices
+        if y1 > y2 { swap(&mut x1, &mut x2); swap(&mut y1, &mut y2); }
+        if y1 > y3 { swap(&mut x1, &mut x3); swap(&mut y1, &mut y3); }
+        if y2 > y3 { swap(&mut x2, &mut x3); swap(&mut y2, &mut y3); }
+
+        // flat triangle, the edges don't cover the middle vertex
+        if y1 == y3 {
+            let (min_x, max_x) = (x1.min(x2).min(x3), x1.max(x2).max(x3));
+            self.render_target.fill_span(min_x, max_x, y1, color);
+            return;
+        }
+
+        // every scanline spans from the long edge to one of the short edges
+        let height = self.render_target.height as i32;
+        for y in y1.max(0)..=y3.min(height - 1) {
+            let xa = edge_x(x1, y1, x3, y3, y);
+            let xb = if y < y2 { edge_x(x1, y1, x2, y2, y) } else { edge_x(x2, y2, x3, y3, y) };
+            self.render_target.fill_span(xa.min(xb), xa.max(xb), y, color);
+        }
+    }
+
+    /// Draws a filled in triangle with vertices `(x1, y1)`, `(x2, y2)` and `(x3, y3)`,
+    /// the vertices are rounded to the closest pixel
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// core.fill_triangle_f(25.5, 100.2, 75.1, 100.2, 50.3, 0.7, WHITE);
+    /// ```
+    pub fn fill_triangle_f(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x3: f32, y3: f32, color: Color) {
+        self.fill_triangle(round(x1), round(y1), round(x2), round(y2), round(x3), round(y3), color);
+    }
+
+    /// Draws connected lines through all points
+    ///
+    /// Shared endpoints are only drawn once
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// core.draw_polyline(&[(10, 10), (50, 20), (30, 60), (80, 70)], WHITE);
+    /// ```
+    pub fn draw_polyline(&mut self, points: &[(i32, i32)], color: Color) {
+        // lines don't include their endpoint, so every point is drawn once
+        for segment in points.windows(2) {
+            let ((x1, y1), (x2, y2)) = (segment[0], segment[1]);
+            self.draw_line(x1, y1, x2, y2, color);
+        }
+
+        if let Some(&(x, y)) = points.last() {
+            self.draw(x, y, color);
+        }
+    }
+
+    /// Draws connected lines through all points using the specified style
+    ///
+    /// The pattern continues across corners
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// core.draw_polyline_styled(&[(10, 10), (50, 20), (30, 60)], LineStyle::Dotted { gap: 1 }, WHITE);
+    /// ```
+    pub fn draw_polyline_styled(&mut self, points: &[(i32, i32)], style: LineStyle, color: Color) {
+        let mut step = 0;
+        for segment in points.windows(2) {
+            let ((x1, y1), (x2, y2)) = (segment[0], segment[1]);
+            self.draw_line_pattern(x1, y1, x2, y2, style, &mut step, color);
+        }
+
+        if let Some(&(x, y)) = points.last() {
+            if style.is_drawn(step) {
+                self.draw(x, y, color);
+            }
+        }
+    }
+
+    /// Draws connected lines through all points and connects the last point to the first
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// core.draw_polyline_closed(&[(10, 10), (50, 20), (30, 60), (80, 70)], WHITE);
+    /// ```
+    pub fn draw_polyline_closed(&mut self, points: &[(i32, i32)], color: Color) {
+        for i in 0..points.len() {
+            let (x1, y1) = points[i];
+            let (x2, y2) = points[(i + 1) % points.len()];
+            self.draw_line(x1, y1, x2, y2, color);
+        }
+    }
+
+    /// Draws a quadratic bezier curve from `p0` to `p2` with control point `p1`
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// core.draw_bezier_quad((10, 100), (50, 10), (90, 100), WHITE);
+    /// ```
+    pub fn draw_bezier_quad(&mut self, p0: (i32, i32), p1: (i32, i32), p2: (i32, i32), color: Color) {
+        let mut points = vec![to_point(p0)];
+        flatten_quad(to_point(p0), to_point(p1), to_point(p2), &mut points);
+        self.draw_polyline(&to_pixels(&points), color);
+    }
+
+    /// Draws a cubic bezier curve from `p0` to `p3` with control points `p1` and `p2`
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// core.draw_bezier_cubic((10, 100), (60, 100), (40, 10), (90, 10), WHITE);
+    /// ```
+    pub fn draw_bezier_cubic(&mut self, p0: (i32, i32), p1: (i32, i32), p2: (i32, i32), p3: (i32, i32), color: Color) {
+        let mut points = vec![to_point(p0)];
+        flatten_cubic(to_point(p0), to_point(p1), to_point(p2), to_point(p3), &mut points);
+        self.draw_polyline(&to_pixels(&points), color);
+    }
+
+    /// Draws a smooth curve passing through all points using catmull-rom interpolation
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// core.draw_spline(&[(10.0, 100.0), (50.0, 20.0), (90.0, 80.0), (130.0, 40.0)], WHITE);
+    /// ```
+    pub fn draw_spline(&mut self, points: &[(f32, f32)], color: Color) {
+        let mut curve = Vec::new();
+        flatten_catmull_rom(points, &mut curve);
+        self.draw_polyline(&to_pixels(&curve), color);
+    }
+
+    /// Draws the outline of a path
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// let mut path = Path::new();
+    /// path.move_to(10.0, 10.0)
+    ///     .line_to(50.0, 10.0)
+    ///     .quad_to(70.0, 30.0, 50.0, 50.0)
+    ///     .close();
+    ///
+    /// core.stroke_path(&path, WHITE);
+    /// ```
+    pub fn stroke_path(&mut self, path: &Path, color: Color) {
+        for (points, closed) in path.contours() {
+            let points = to_pixels(points);
+            if closed {
+                self.draw_polyline_closed(&points, color);
+            } else {
+                self.draw_polyline(&points, color);
+            }
+        }
+    }
+
+    /// Fills the area enclosed by a path, open subpaths are closed automatically
+    ///
+    /// Uses the even-odd rule, so subpaths inside of other subpaths will be holes
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// let mut path = Path::new();
+    /// path.move_to(10.0, 10.0)
+    ///     .line_to(50.0, 10.0)
+    ///     .quad_to(70.0, 30.0, 50.0, 50.0)
+    ///     .close();
+    ///
+    /// core.fill_path(&path, WHITE);
+    /// ```
+    pub fn fill_path(&mut self, path: &Path, color: Color) {
+        let contours: Vec<&[(f32, f32)]> = path.contours().map(|(points, _)| points).collect();
+        self.fill_contours(&contours, color);
+    }
+
+    /// Draws the outline of a polygon, the last point is connected to the first
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// core.draw_polygon(&[(10, 10), (50, 20), (30, 60)], WHITE);
+    /// ```
+    pub fn draw_polygon(&mut self, points: &[(i32, i32)], color: Color) {
+        self.draw_polyline_closed(points, color);
+    }
+
+    /// Draws a filled in polygon, works for both convex and concave polygons
+    ///
+    /// Uses the even-odd rule, so self-intersecting polygons will have holes
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// core.fill_polygon(&[(10, 10), (50, 20), (30, 60)], WHITE);
+    /// ```
+    pub fn fill_polygon(&mut self, points: &[(i32, i32)], color: Color) {
+        let points: Vec<(f32, f32)> = points.iter().map(|&(x, y)| (x as f32, y as f32)).collect();
+        self.fill_polygon_f32(&points, color);
+    }
+
+    /// Draws a regular polygon centered at `(x, y)` with vertices at distance `r` from the center
+    ///
+    /// `rotation` is in radians, with 0 the first vertex points along the positive x axis,
+    /// increasing clockwise
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// // hexagon
+    /// core.draw_regular_polygon(100, 100, 20, 6, 0.0, WHITE);
+    /// ```
+    pub fn draw_regular_polygon(&mut self, x: i32, y: i32, r: i32, sides: u32, rotation: f32, color: Color) {
+        if sides < 3 { return; }
+        let points = star_points(x, y, r, r, sides, rotation);
+        self.draw_polyline_closed(&to_pixels(&points), color);
+    }
+
+    /// Draws a filled in regular polygon centered at `(x, y)` with vertices at distance `r` from the center
+    ///
+    /// `rotation` is in radians, with 0 the first vertex points along the positive x axis,
+    /// increasing clockwise
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// // hexagon
+    /// core.fill_regular_polygon(100, 100, 20, 6, 0.0, WHITE);
+    /// ```
+    pub fn fill_regular_polygon(&mut self, x: i32, y: i32, r: i32, sides: u32, rotation: f32, color: Color) {
+        if sides < 3 { return; }
+        let points = star_points(x, y, r, r, sides, rotation);
+        self.fill_polygon(&to_pixels(&points), color);
+    }
+
+    /// Draws a star centered at `(x, y)` with `points` tips at distance `outer_r` from the center,
+    /// the corners between the tips are at distance `inner_r`
+    ///
+    /// `rotation` is in radians, with 0 the first tip points along the positive x axis,
+    /// increasing clockwise
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// core.draw_star(100, 100, 20, 8, 5, -std::f32::consts::FRAC_PI_2, YELLOW);
+    /// ```
+    pub fn draw_star(&mut self, x: i32, y: i32, outer_r: i32, inner_r: i32, points: u32, rotation: f32, color: Color) {
+        if points < 2 { return; }
+        let points = star_points(x, y, outer_r, inner_r, points, rotation);
+        self.draw_polyline_closed(&to_pixels(&points), color);
+    }
+
+    /// Draws a filled in star centered at `(x, y)` with `points` tips at distance `outer_r` from the center,
+    /// the corners between the tips are at distance `inner_r`
+    ///
+    /// `rotation` is in radians, with 0 the first tip points along the positive x axis,
+    /// increasing clockwise
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// core.fill_star(100, 100, 20, 8, 5, -std::f32::consts::FRAC_PI_2, YELLOW);
+    /// ```
+    pub fn fill_star(&mut self, x: i32, y: i32, outer_r: i32, inner_r: i32, points: u32, rotation: f32, color: Color) {
+        if points < 2 { return; }
+        let points = star_points(x, y, outer_r, inner_r, points, rotation);
+        self.fill_polygon(&to_pixels(&points), color);
+    }
+
+    /// Draws a polygon filled with a repeating pattern
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// core.fill_polygon_pattern(&[(10, 10), (50, 20), (30, 60)], &FillPattern::Mask(HATCH_DIAGONAL, WHITE));
+    /// ```
+    pub fn fill_polygon_pattern(&mut self, points: &[(i32, i32)], pattern: &FillPattern) {
+        let points: Vec<(f32, f32)> = points.iter().map(|&(x, y)| (x as f32, y as f32)).collect();
+        self.fill_contours_pattern(&[&points], pattern);
+    }
+
+    fn fill_contours_pattern(&mut self, contours: &[&[(f32, f32)]], pattern: &FillPattern) {
+        if !self.transform.is_identity() {
+            let transformed: Vec<Vec<Point>> = contours.iter().map(|c| self.transform_points(c)).collect();
+            let contours: Vec<&[Point]> = transformed.iter().map(|c| c.as_slice()).collect();
+            return self.untransformed(|core| core.fill_contours_pattern(&contours, pattern));
+        }
+
+        scan_contours(contours, |x1, x2, y| self.draw_hline_pattern(x1, x2, y, pattern));
+    }
+
+    fn fill_polygon_f32(&mut self, points: &[(f32, f32)], color: Color) {
+        self.fill_contours(&[points], color);
+    }
+
+    fn fill_contours(&mut self, contours: &[&[(f32, f32)]], color: Color) {
+        if !self.transform.is_identity() {
+            let transformed: Vec<Vec<Point>> = contours.iter().map(|c| self.transform_points(c)).collect();
+            let contours: Vec<&[Point]> = transformed.iter().map(|c| c.as_slice()).collect();
+            return self.untransformed(|core| core.fill_contours(&contours, color));
+        }
+
+        scan_contours(contours, |x1, x2, y| self.draw_hline(x1, x2, y, color));
+    }
+
+    /// Draws a filled in triangle with vertices `(x1, y1)`, `(x2, y2)` and `(x3, y3)`,
+    /// the colors of the vertices are interpolated across the triangle
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// core.fill_triangle_gradient(25, 100, 75, 100, 50, 0, RED, GREEN, BLUE);
+    /// ```
+    pub fn fill_triangle_gradient(&mut self, x1: i32, y1: i32, x2: i32, y2: i32, x3: i32, y3: i32,
+                                  c1: Color, c2: Color, c3: Color) {
+        if !self.transform.is_identity() {
+            let ((x1, y1), (x2, y2)) = (self.transform_point(x1, y1), self.transform_point(x2, y2));
+            let (x3, y3) = self.transform_point(x3, y3);
+            return self.untransformed(|core| core.fill_triangle_gradient(x1, y1, x2, y2, x3, y3, c1, c2, c3));
+        }
+
+        let bounds = (self.render_target.width as i32, self.render_target.height as i32);
+        rasterize_triangle([(x1, y1), (x2, y2), (x3, y3)], bounds, |x, y, w1, w2, w3| {
+            let mix = |a: u8, b: u8, c: u8| {
+                f32::round(a as f32 * w1 + b as f32 * w2 + c as f32 * w3).clamp(0.0, 255.0) as u8
+            };
+            let color = Color {
+                r: mix(c1.r, c2.r, c3.r),
+                g: mix(c1.g, c2.g, c3.g),
+                b: mix(c1.b, c2.b, c3.b),
+                a: mix(c1.a, c2.a, c3.a),
+            };
+            self.draw(x, y, color);
+        });
+    }
+
+    /// Draws a triangle with vertices `v1`, `v2` and `v3` filled with the texture
+    ///
+    /// Texture coordinates range from `(0.0, 0.0)` in the upper left corner of the texture
+    /// to `(1.0, 1.0)` in the lower right corner and are clamped to the edges
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// let mut texture = RenderTarget::new(2, 2);
+    /// texture.clear(RED);
+    /// texture.set_pixel(1, 1, BLUE);
+    ///
+    /// core.fill_triangle_textured((25, 100), (75, 100), (50, 0),
+    ///     (0.0, 1.0), (1.0, 1.0), (0.5, 0.0),
+    ///     &texture);
+    /// ```
+    pub fn fill_triangle_textured(&mut self, v1: (i32, i32), v2: (i32, i32), v3: (i32, i32),
+                                  uv1: (f32, f32), uv2: (f32, f32), uv3: (f32, f32),
+                                  texture: &RenderTarget) {
+        if !self.transform.is_identity() {
+            let (v1, v2, v3) = (self.transform_point(v1.0, v1.1), self.transform_point(v2.0, v2.1), self.transform_point(v3.0, v3.1));
+            return self.untransformed(|core| core.fill_triangle_textured(v1, v2, v3, uv1, uv2, uv3, texture));
+        }
+
+        if texture.width == 0 || texture.height == 0 { return; }
+        let (tw, th) = (texture.width as f32, texture.height as f32);
+
+        let bounds = (self.render_target.width as i32, self.render_target.height as i32);
+        rasterize_triangle([v1, v2, v3], bounds, |x, y, w1, w2, w3| {
+            // affine interpolation of the texture coordinates
+            let u = uv1.0 * w1 + uv2.0 * w2 + uv3.0 * w3;
+            let v = uv1.1 * w1 + uv2.1 * w2 + uv3.1 * w3;
+
+            let tx = f32::clamp(u * tw, 0.0, tw - 1.0) as i32;
+            let ty = f32::clamp(v * th, 0.0, th - 1.0) as i32;
+            if let Some(color) = texture.get_pixel(tx, ty) {
+                self.draw(x, y, color);
+            }
+        });
+    }
+
+    /// Draws a filled in triangle with vertices at `(x, y)` and depth `z`, pixels behind what was
+    /// drawn before are skipped when the screen has a depth buffer
+    ///
+    /// The depth is interpolated across the triangle, smaller values are closer. The transform
+    /// only moves the vertices, not their depth
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// core.enable_depth(true);
+    /// core.clear(BLACK);
+    /// core.clear_depth();
+    ///
+    /// // the red triangle is in front even though it's drawn first
+    /// core.fill_triangle_depth((100, 100, 1.0), (300, 100, 1.0), (200, 300, 1.0), RED);
+    /// core.fill_triangle_depth((150, 50, 2.0), (350, 150, 0.5), (250, 250, 2.0), BLUE);
+    /// ```
+    pub fn fill_triangle_depth(&mut self, v1: (i32, i32, f32), v2: (i32, i32, f32), v3: (i32, i32, f32), color: Color) {
+        if !self.transform.is_identity() {
+            let (v1, v2, v3) = (self.transform_vertex(v1), self.transform_vertex(v2), self.transform_vertex(v3));
+            return self.untransformed(|core| core.fill_triangle_depth(v1, v2, v3, color));
+        }
+
+        let bounds = (self.render_target.width as i32, self.render_target.height as i32);
+        rasterize_triangle([(v1.0, v1.1), (v2.0, v2.1), (v3.0, v3.1)], bounds, |x, y, w1, w2, w3| {
+            if self.render_target.depth_test(x, y, v1.2 * w1 + v2.2 * w2 + v3.2 * w3) {
+                self.draw(x, y, color);
+            }
+        });
+    }
+
+    /// Depth tested version of [`fill_triangle_textured`], see [`fill_triangle_depth`]
+    ///
+    /// Transparent pixels of the texture don't change the depth
+    ///
+    /// [`fill_triangle_textured`]: #method.fill_triangle_textured
+    /// [`fill_triangle_depth`]: #method.fill_triangle_depth
+    pub fn fill_triangle_textured_depth(&mut self, v1: (i32, i32, f32), v2: (i32, i32, f32), v3: (i32, i32, f32),
+                                        uv1: (f32, f32), uv2: (f32, f32), uv3: (f32, f32),
+                                        texture: &RenderTarget) {
+        if !self.transform.is_identity() {
+            let (v1, v2, v3) = (self.transform_vertex(v1), self.transform_vertex(v2), self.transform_vertex(v3));
+            return self.untransformed(|core| core.fill_triangle_textured_depth(v1, v2, v3, uv1, uv2, uv3, texture));
+        }
+
+        if texture.width == 0 || texture.height == 0 { return; }
+        let (tw, th) = (texture.width as f32, texture.height as f32);
+
+        let bounds = (self.render_target.width as i32, self.render_target.height as i32);
+        rasterize_triangle([(v1.0, v1.1), (v2.0, v2.1), (v3.0, v3.1)], bounds, |x, y, w1, w2, w3| {
+            let u = uv1.0 * w1 + uv2.0 * w2 + uv3.0 * w3;
+            let v = uv1.1 * w1 + uv2.1 * w2 + uv3.1 * w3;
+
+            let tx = f32::clamp(u * tw, 0.0, tw - 1.0) as i32;
+            let ty = f32::clamp(v * th, 0.0, th - 1.0) as i32;
+            let color = match texture.get_pixel(tx, ty) {
+                Some(color) if color.a > 0 => color,
+                _ => return,
+            };
+            if self.render_target.depth_test(x, y, v1.2 * w1 + v2.2 * w2 + v3.2 * w3) {
+                self.draw(x, y, color);
+            }
+        });
+    }
+
+    /// Adds or removes a depth buffer for the screen, or the layer that is drawn on, see
+    /// [`RenderTarget::enable_depth`]
+    ///
+    /// [`RenderTarget::enable_depth`]: struct.RenderTarget.html#method.enable_depth
+    pub fn enable_depth(&mut self, enabled: bool) {
+        if enabled {
+            self.render_target.enable_depth();
+        } else {
+            self.render_target.disable_depth();
+        }
+    }
+
+    /// Moves all depths of the screen, or the layer that is drawn on, infinitely far away
+    pub fn clear_depth(&mut self) {
+        self.render_target.clear_depth();
+    }
+
+    // draws a horizontal line from x1 to x2 (inclusive), the callers have already applied the
+    // transform
+    fn draw_hline(&mut self, mut x1: i32, mut x2: i32, y: i32, color: Color) {
+        if x2 < x1 { swap(&mut x1, &mut x2); }
+        self.render_target.fill_span(x1, x2, y, color);
+    }
+
+    // draws a horizontal line from x1 to x2 (inclusive) using the pattern
+    fn draw_hline_pattern(&mut self, x1: i32, x2: i32, y: i32, pattern: &FillPattern) {
+        self.render_target.fill_span_with(x1, x2, y, |x| pattern.color_at(x, y));
+    }
+
+    // runs draw calls without the current transform, used once the geometry has been transformed
+    // so nested draw calls don't transform it again
+    // draws an outline at the resolution of the screen while supersampling, every pixel becomes a
+    // block of render target pixels so one pixel wide outlines keep their color after the frame
+    // is downsampled
+    fn draw_logical<F: FnOnce(&mut Self)>(&mut self, draw: F) {
+        let logical = self.local_transform.then(self.camera_view());
+        let transform = replace(&mut self.transform, logical);
+        self.block = self.supersample as i32;
+        draw(self);
+        self.block = 1;
+        self.transform = transform;
+    }
+
+    // sets a pixel, or a block of pixels inside draw_logical
+    fn plot(&mut self, x: i32, y: i32, color: Color) {
+        if self.block == 1 {
+            return self.render_target.set_pixel(x, y, color);
+        }
+        for dy in 0..self.block {
+            for dx in 0..self.block {
+                self.render_target.set_pixel(x * self.block + dx, y * self.block + dy, color);
+            }
+        }
+    }
+
+    // size of the area plot can reach
+    fn plot_size(&self) -> (i32, i32) {
+        (self.render_target.width as i32 / self.block, self.render_target.height as i32 / self.block)
+    }
+
+    fn untransformed<F: FnOnce(&mut Self)>(&mut self, draw: F) {
+        let transform = replace(&mut self.transform, Transform::IDENTITY);
+        draw(self);
+        self.transform = transform;
+    }
+
+    fn transform_point(&self, x: i32, y: i32) -> (i32, i32) {
+        if self.transform.is_identity() { return (x, y); }
+        let (x, y) = self.transform.apply(x as f32, y as f32);
+        (round(x), round(y))
+    }
+
+    fn transform_vertex(&self, v: (i32, i32, f32)) -> (i32, i32, f32) {
+        let (x, y) = self.transform_point(v.0, v.1);
+        (x, y, v.2)
+    }
+
+    fn transform_points(&self, points: &[Point]) -> Vec<Point> {
+        points.iter().map(|p| self.transform.apply(p.0, p.1)).collect()
+    }
+
+    fn transform_radius(&self, r: i32) -> i32 {
+        let (sx, sy) = self.transform.scale_factors();
+        round(r as f32 * (sx + sy) / 2.0)
+    }
+}
+
+const MOUSE_BUTTONS: [MouseButton; 3] = [MouseButton::Left, MouseButton::Middle, MouseButton::Right];
+
+fn mouse_button_index(button: MouseButton) -> usize {
+    match button {
+        MouseButton::Left => 0,
+        MouseButton::Middle => 1,
+        MouseButton::Right => 2,
+    }
+}
+
+// checks a key of a chord, modifiers also match the key on the other side of the keyboard
+fn chord_key<F: Fn(Key) -> bool>(key: Key, check: F) -> bool {
+    let other = match key {
+        Key::LeftShift => Key::RightShift,
+        Key::RightShift => Key::LeftShift,
+        Key::LeftCtrl => Key::RightCtrl,
+        Key::RightCtrl => Key::LeftCtrl,
+        Key::LeftAlt => Key::RightAlt,
+        Key::RightAlt => Key::LeftAlt,
+        Key::LeftSuper => Key::RightSuper,
+        Key::RightSuper => Key::LeftSuper,
+        _ => key,
+    };
+    check(key) || check(other)
+}
+
+// gets when the next frame is due, frames are spaced evenly from the last deadline. when the
+// loop fell more than a frame behind it starts over instead of rushing frames to catch up
+fn next_frame_deadline(last: Option<Instant>, now: Instant, fps: u32) -> Instant {
+    let frame = Duration::from_secs(1) / fps;
+    match last {
+        Some(last) if last + frame * 2 > now => last + frame,
+        _ => now + frame,
+    }
+}
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+enum Frame {
+    Screen,
+    Composite,
+    Output,
+}
+
+// rounds to the closest pixel, halfway values are rounded away from zero
+fn round(v: f32) -> i32 {
+    f32::round(v) as i32
+}
+
+fn to_point(p: (i32, i32)) -> Point {
+    (p.0 as f32, p.1 as f32)
+}
+
+fn to_pixels(points: &[Point]) -> Vec<(i32, i32)> {
+    points.iter().map(|p| (f32::round(p.0) as i32, f32::round(p.1) as i32)).collect()
+}
+
+// scanline polygon fill, calls span for every row of pixels with their center inside the polygon
+// formed by all contours using the even-odd rule, the span includes both ends
+fn scan_contours<F>(contours: &[&[(f32, f32)]], mut span: F)
+where F: FnMut(i32, i32, i32) {
+    // build edge table, edges that don't cross any scanline center can be skipped
+    let mut edges = Vec::new();
+    for points in contours.iter().filter(|c| c.len() >= 3) {
+        for i in 0..points.len() {
+            let (mut x1, mut y1) = points[i];
+            let (mut x2, mut y2) = points[(i + 1) % points.len()];
+            if y1 > y2 { swap(&mut x1, &mut x2); swap(&mut y1, &mut y2); }
+
+            // first scanline and the one after the last
+            let y_min = f32::ceil(y1 - 0.5) as i32;
+            let y_max = f32::ceil(y2 - 0.5) as i32;
+            if y_min == y_max { continue; }
+
+            // x is stored at the center of the first scanline
+            let slope = (x2 - x1) / (y2 - y1);
+            let x = x1 + (y_min as f32 + 0.5 - y1) * slope;
+            edges.push(PolygonEdge { y_min, y_max, x, slope });
+        }
+    }
+    if edges.is_empty() { return; }
+    edges.sort_by_key(|e| e.y_min);
+
+    let y_start = edges[0].y_min;
+    let y_end = edges.iter().map(|e| e.y_max).max().unwrap_or(y_start);
+
+    let mut active: Vec<PolygonEdge> = Vec::new();
+    let mut next = 0;
+    for y in y_start..y_end {
+        // update active edge list
+        while next < edges.len() && edges[next].y_min == y {
+            active.push(edges[next]);
+            next += 1;
+        }
+        active.retain(|e| e.y_max > y);
+        active.sort_by(|a, b| a.x.total_cmp(&b.x));
+
+        // fill pixels with centers between pairs of intersections
+        for pair in active.chunks_exact(2) {
+            let x1 = f32::ceil(pair[0].x - 0.5) as i32;
+            let x2 = f32::ceil(pair[1].x - 0.5) as i32;
+            if x2 > x1 {
+                span(x1, x2 - 1, y);
+            }
+        }
+
+        for e in active.iter_mut() {
+            e.x += e.slope;
+        }
+    }
+}
+
+// calls draw for every pixel with its center inside the triangle together with the barycentric
+// weights of the vertices, pixels outside of bounds are skipped
+fn rasterize_triangle<F>(v: [(i32, i32); 3], bounds: (i32, i32), mut draw: F)
+    where F: FnMut(i32, i32, f32, f32, f32) {
+    let edge = |a: (i32, i32), b: (i32, i32), px: f32, py: f32| {
+        (b.0 - a.0) as f32 * (py - a.1 as f32) - (b.1 - a.1) as f32 * (px - a.0 as f32)
+    };
+
+    let area = edge(v[0], v[1], v[2].0 as f32, v[2].1 as f32);
+    if area == 0.0 { return; }
+
+    let x_min = v.iter().map(|p| p.0).min().unwrap_or(0).max(0);
+    let x_max = v.iter().map(|p| p.0).max().unwrap_or(0).min(bounds.0 - 1);
+    let y_min = v.iter().map(|p| p.1).min().unwrap_or(0).max(0);
+    let y_max = v.iter().map(|p| p.1).max().unwrap_or(0).min(bounds.1 - 1);
+
+    for y in y_min..=y_max {
+        for x in x_min..=x_max {
+            let (px, py) = (x as f32 + 0.5, y as f32 + 0.5);
+            let w1 = edge(v[1], v[2], px, py) / area;
+            let w2 = edge(v[2], v[0], px, py) / area;
+            let w3 = edge(v[0], v[1], px, py) / area;
+            if w1 >= 0.0 && w2 >= 0.0 && w3 >= 0.0 {
+                draw(x, y, w1, w2, w3);
+            }
+        }
+    }
+}
+
+// vertices of a star alternating between the outer and inner radius,
+// a regular polygon when both radii are the same
+fn star_points(x: i32, y: i32, outer_r: i32, inner_r: i32, tips: u32, rotation: f32) -> Vec<Point> {
+    // a regular polygon only needs the outer vertices
+    let radii = if outer_r == inner_r { vec![outer_r] } else { vec![outer_r, inner_r] };
+    let count = tips as usize * radii.len();
+    let step = TAU / count as f32;
+
+    (0..count).map(|i| {
+        let r = radii[i % radii.len()] as f32;
+        let angle = rotation + step * i as f32;
+        (x as f32 + r * angle.cos(), y as f32 + r * angle.sin())
+    }).collect()
+}
+
+fn rotated_rect_corners(x: i32, y: i32, width: i32, height: i32, angle: f32, pivot: (f32, f32)) -> [Point; 4] {
+    let (sin, cos) = angle.sin_cos();
+    let (px, py) = (x as f32 + pivot.0, y as f32 + pivot.1);
+
+    let corner = |cx: i32, cy: i32| {
+        let (dx, dy) = ((x + cx) as f32 - px, (y + cy) as f32 - py);
+        (px + dx * cos - dy * sin, py + dx * sin + dy * cos)
+    };
+    [corner(0, 0), corner(width, 0), corner(width, height), corner(0, height)]
+}
+
+// x coordinate of the edge from (x1, y1) to (x2, y2) at row y
+fn edge_x(x1: i32, y1: i32, x2: i32, y2: i32, y: i32) -> i32 {
+    if y1 == y2 { return x1; }
+    x1 + f32::round((y - y1) as f32 * (x2 - x1) as f32 / (y2 - y1) as f32) as i32
+}
+
+fn rect_corners(x: i32, y: i32, width: i32, height: i32) -> [Point; 4] {
+    let (x1, y1, x2, y2) = (x as f32, y as f32, (x + width) as f32, (y + height) as f32);
+    [(x1, y1), (x2, y1), (x2, y2), (x1, y2)]
+}
+
+// outline of a rounded rectangle with quarter circles in the corners
+fn round_rect_points(x: i32, y: i32, width: i32, height: i32, r: i32) -> Vec<Point> {
+    let (x1, y1) = ((x + r) as f32, (y + r) as f32);
+    let (x2, y2) = ((x + width - r) as f32, (y + height - r) as f32);
+    let centers = [(x2, y1), (x2, y2), (x1, y2), (x1, y1)];
+    let segments = r.max(1);
+    let step = TAU / 4.0 / segments as f32;
+
+    let mut points = Vec::with_capacity(4 * (segments as usize + 1));
+    for (quarter, &(cx, cy)) in centers.iter().enumerate() {
+        let start = TAU / 4.0 * (quarter as f32 - 1.0);
+        for i in 0..=segments {
+            let angle = start + step * i as f32;
+            points.push((cx + r as f32 * angle.cos(), cy + r as f32 * angle.sin()));
+        }
+    }
+    points
+}
+
+// edge used by the polygon scanline fill
+#[derive(Copy, Clone)]
+struct PolygonEdge {
+    y_min: i32,
+    y_max: i32,
+    x: f32,
+    slope: f32,
+}
+
+// checks if the direction (dx, dy) lies within the clockwise sweep from start to end
+fn angle_in_range(dx: i32, dy: i32, start: f32, end: f32) -> bool {
+    if (end - start).abs() >= TAU { return true; }
+
+    let angle = (dy as f32).atan2(dx as f32);
+    (angle - start).rem_euclid(TAU) <= (end - start).rem_euclid(TAU)
+}
+
+// midpoint circle algorithm, returns the points of the octant between 90 and 45 degrees
+fn circle_octant(r: i32) -> Vec<(i32, i32)> {
+    let mut points = Vec::new();
+    let mut x0 = 0;
+    let mut y0 = r;
+    let mut d = 3 - 2 * r;
+
+    while y0 >= x0 {
+        points.push((x0, y0));
+        if d < 0 { d += 4 * x0 + 6; x0 += 1; }
+        else { x0 += 1; y0 -= 1; d += 4 * (x0 - y0) + 10; }
+    }
+
+    points
+}
+
+// midpoint ellipse algorithm, returns the points of the first quadrant relative to the center
+fn ellipse_quadrant(rx: i32, ry: i32) -> Vec<(i32, i32)> {
+    let (rx2, ry2) = (rx as i64 * rx as i64, ry as i64 * ry as i64);
+    let mut points = Vec::new();
+
+    let mut x0: i64 = 0;
+    let mut y0: i64 = ry as i64;
+    let mut px = 0;
+    let mut py = 2 * rx2 * y0;
+
+    // region 1, slope > -1
+    let mut d = ry2 - rx2 * ry as i64 + rx2 / 4;
+    while px < py {
+        points.push((x0 as i32, y0 as i32));
+        x0 += 1;
+        px += 2 * ry2;
+        if d < 0 { d += ry2 + px; }
+        else { y0 -= 1; py -= 2 * rx2; d += ry2 + px - py; }
+    }
+
+    // region 2, slope <= -1
+    d = ry2 * (x0 * x0 + x0) + rx2 * (y0 - 1) * (y0 - 1) - rx2 * ry2;
+    while y0 >= 0 {
+        points.push((x0 as i32, y0 as i32));
+        y0 -= 1;
+        py -= 2 * rx2;
+        if d > 0 { d += rx2 - py; }
+        else { x0 += 1; px += 2 * ry2; d += rx2 - py + px; }
+    }
+
+    points
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn create_core(w: usize, h: usize) -> RainCore {
+        RainCore::init("", w, h, false)
+    }
+
+    #[test]
+    fn test_clear() {
+        let mut core = create_core(10, 10);
+
+        core.clear(WHITE);
+        for p in core.render_target.data {
+            assert_eq!(p, WHITE.into());
+        }
+    }
+
+    #[test]
+    fn test_draw() {
+        let mut core = create_core(10, 10);
+
+        core.draw(5, 3, WHITE);
+        assert_eq!(core.render_target.get_pixel(5, 3), Some(WHITE));
+    }
+
+    #[test]
+    fn test_draw_line_styled() {
+        let mut core = create_core(20, 20);
+
+        core.draw_line_styled(0, 5, 10, 5, LineStyle::Dashed { dash: 2, gap: 3 }, WHITE);
+        let drawn: Vec<bool> = (0..10).map(|x| core.render_target.get_pixel(x, 5) == Some(WHITE)).collect();
+        assert_eq!(drawn, vec![true, true, false, false, false, true, true, false, false, false]);
+    }
+
+    #[test]
+    fn test_draw_polyline_styled() {
+        let mut core = create_core(20, 20);
+
+        // pattern continues around the corner
+        core.draw_polyline_styled(&[(0, 0), (3, 0), (3, 3)], LineStyle::Dotted { gap: 1 }, WHITE);
+        assert_eq!(core.render_target.get_pixel(0, 0), Some(WHITE));
+        assert_eq!(core.render_target.get_pixel(2, 0), Some(WHITE));
+        assert_eq!(core.render_target.get_pixel(3, 0), Some(NONE));
+        assert_eq!(core.render_target.get_pixel(3, 1), Some(WHITE));
+        assert_eq!(core.render_target.get_pixel(3, 3), Some(WHITE));
+    }
+
+    #[test]
+    fn test_draw_rect_styled_solid() {
+        let mut styled = create_core(20, 20);
+        let mut plain = create_core(20, 20);
+
+        styled.draw_rect_styled(2, 3, 10, 8, LineStyle::Solid, WHITE);
+        plain.draw_rect(2, 3, 10, 8, WHITE);
+        assert_eq!(styled.render_target.data, plain.render_target.data);
+    }
+
+    #[test]
+    fn test_draw_f_rounds() {
+        let mut float = create_core(20, 20);
+        let mut int = create_core(20, 20);
+
+        float.draw_f(2.4, 3.6, WHITE);
+        float.draw_line_f(1.2, 10.4, 14.6, 10.4, WHITE);
+        float.fill_rect_f(4.6, 12.4, 5.2, 3.0, WHITE);
+        int.draw(2, 4, WHITE);
+        int.draw_line(1, 10, 15, 10, WHITE);
+        int.fill_rect(5, 12, 5, 3, WHITE);
+        assert_eq!(float.render_target.data, int.render_target.data);
+    }
+
+    #[test]
+    fn test_fill_rect_f_no_gaps() {
+        let mut core = create_core(20, 20);
+
+        // adjacent rects share their edge without gaps or overlap
+        core.set_blend_mode(BlendMode::Additive);
+        core.fill_rect_f(0.0, 0.0, 3.5, 1.0, Color::rgb(100, 0, 0));
+        core.fill_rect_f(3.5, 0.0, 3.5, 1.0, Color::rgb(100, 0, 0));
+        for x in 0..7 {
+            assert_eq!(core.render_target.get_pixel(x, 0), Some(Color::rgb(100, 0, 0)));
+        }
+    }
+
+    #[test]
+    fn test_fill_circle_aa_f() {
+        let mut core = create_core(30, 30);
+        core.clear(BLACK);
+
+        // center between two pixels, both get the same coverage
+        core.fill_circle_aa_f(15.5, 15.0, 5.2, WHITE);
+        assert_eq!(core.render_target.get_pixel(15, 15), Some(WHITE));
+        assert_eq!(core.render_target.get_pixel(16, 15), Some(WHITE));
+        assert_eq!(core.render_target.get_pixel(10, 15), core.render_target.get_pixel(21, 15));
+        let edge = core.render_target.get_pixel(10, 15).unwrap();
+        assert!(edge.r > 0 && edge.r < 255);
+        assert_eq!(core.render_target.get_pixel(22, 15), Some(BLACK));
+    }
+
+    #[test]
+    fn test_draw_line_aa_f() {
+        let mut core = create_core(20, 20);
+        core.clear(BLACK);
+
+        // horizontal line between two rows covers both halfway
+        core.draw_line_aa_f(2.0, 5.5, 12.0, 5.5, WHITE);
+        assert_eq!(core.render_target.get_pixel(6, 5), Some(Color::rgb(128, 128, 128)));
+        assert_eq!(core.render_target.get_pixel(6, 6), Some(Color::rgb(128, 128, 128)));
+        assert_eq!(core.render_target.get_pixel(6, 7), Some(BLACK));
+    }
+
+    #[test]
+    fn test_draw_arrow() {
+        let mut core = create_core(30, 30);
+
+        core.draw_arrow(2, 15, 20, 15, 6, WHITE);
+        assert_eq!(core.render_target.get_pixel(2, 15), Some(WHITE));
+        assert_eq!(core.render_target.get_pixel(20, 15), Some(WHITE));
+        // head lines end behind the tip, above and below the shaft
+        assert_eq!(core.render_target.get_pixel(15, 12), Some(WHITE));
+        assert_eq!(core.render_target.get_pixel(15, 18), Some(WHITE));
+        assert_eq!(core.render_target.get_pixel(21, 15), Some(NONE));
+    }
+
+    #[test]
+    fn test_draw_grid() {
+        let mut core = create_core(20, 20);
+
+        core.draw_grid(2, 3, 5, 5, WHITE);
+        assert_eq!(core.render_target.get_pixel(2, 0), Some(WHITE));
+        assert_eq!(core.render_target.get_pixel(17, 19), Some(WHITE));
+        assert_eq!(core.render_target.get_pixel(0, 8), Some(WHITE));
+        assert_eq!(core.render_target.get_pixel(4, 5), Some(NONE));
+    }
+
+    #[test]
+    fn test_draw_grid_major() {
+        let mut core = create_core(20, 20);
+
+        core.draw_grid_major(5, 5, 5, 5, WHITE, 2, RED);
+        assert_eq!(core.render_target.get_pixel(5, 1), Some(RED));
+        assert_eq!(core.render_target.get_pixel(15, 1), Some(RED));
+        assert_eq!(core.render_target.get_pixel(10, 1), Some(WHITE));
+        assert_eq!(core.render_target.get_pixel(0, 1), Some(WHITE));
+        assert_eq!(core.render_target.get_pixel(10, 5), Some(RED));
+    }
+
+    #[test]
+    fn test_draw_line_thick() {
+        let mut core = create_core(20, 20);
+
+        core.draw_line_thick(2, 10, 16, 10, 3, WHITE);
+        assert_eq!(core.render_target.get_pixel(2, 9), Some(WHITE));
+        assert_eq!(core.render_target.get_pixel(16, 11), Some(WHITE));
+        assert_eq!(core.render_target.get_pixel(10, 8), Some(NONE));
+        assert_eq!(core.render_target.get_pixel(10, 12), Some(NONE));
+        assert_eq!(core.render_target.get_pixel(17, 10), Some(NONE));
+    }
+
+    #[test]
+    fn test_draw_line_aa() {
+        let mut core = create_core(20, 20);
+        core.clear(BLACK);
+
+        core.draw_line_aa(0, 0, 10, 5, WHITE);
+        assert_eq!(core.render_target.get_pixel(0, 0), Some(WHITE));
+        assert_eq!(core.render_target.get_pixel(10, 5), Some(WHITE));
+        assert_eq!(core.render_target.get_pixel(1, 0), Some(Color::rgb(128, 128, 128)));
+        assert_eq!(core.render_target.get_pixel(1, 1), Some(Color::rgb(128, 128, 128)));
+        assert_eq!(core.render_target.get_pixel(1, 2), Some(BLACK));
+    }
+
+    #[test]
+    fn test_draw_circle_aa() {
+        let mut core = create_core(30, 30);
+        core.clear(BLACK);
+
+        core.draw_circle_aa(15, 15, 8, WHITE);
+        assert_eq!(core.render_target.get_pixel(23, 15), Some(WHITE));
+        assert_eq!(core.render_target.get_pixel(15, 15), Some(BLACK));
+        assert_eq!(core.render_target.get_pixel(25, 15), Some(BLACK));
+
+        // diagonal edge pixel is only partially covered
+        let edge = core.render_target.get_pixel(21, 21).unwrap();
+        assert!(edge.r > 0 && edge.r < 255);
+    }
+
+    #[test]
+    fn test_fill_circle_aa() {
+        let mut core = create_core(30, 30);
+        core.clear(BLACK);
+
+        core.fill_circle_aa(15, 15, 8, WHITE);
+        assert_eq!(core.render_target.get_pixel(15, 15), Some(WHITE));
+        assert_eq!(core.render_target.get_pixel(22, 15), Some(WHITE));
+        assert_eq!(core.render_target.get_pixel(24, 15), Some(BLACK));
+        assert_eq!(core.render_target.get_pixel(23, 15), Some(Color::rgb(128, 128, 128)));
+    }
+
+    #[test]
+    fn test_draw_rect_thick() {
+        let mut thick = create_core(20, 20);
+        let mut filled = create_core(20, 20);
+
+        thick.draw_rect_thick(5, 5, 10, 10, 3, WHITE);
+        filled.fill_rect(4, 4, 13, 3, WHITE);
+        filled.fill_rect(4, 14, 13, 3, WHITE);
+        filled.fill_rect(4, 7, 3, 7, WHITE);
+        filled.fill_rect(14, 7, 3, 7, WHITE);
+        assert_eq!(thick.render_target.data, filled.render_target.data);
+    }
+
+    #[test]
+    fn test_draw_circle_thick() {
+        let mut core = create_core(30, 30);
+
+        core.draw_circle_thick(15, 15, 8, 4, WHITE);
+        assert_eq!(core.render_target.get_pixel(22, 15), Some(WHITE));
+        assert_eq!(core.render_target.get_pixel(15, 8), Some(WHITE));
+        assert_eq!(core.render_target.get_pixel(15, 15), Some(NONE));
+        assert_eq!(core.render_target.get_pixel(26, 15), Some(NONE));
+    }
+
+    #[test]
+    fn test_draw_arc() {
+        let mut core = create_core(20, 20);
+
+        core.draw_arc(10, 10, 5, 0.0, std::f32::consts::PI, WHITE);
+        assert_eq!(core.render_target.get_pixel(15, 10), Some(WHITE));
+        assert_eq!(core.render_target.get_pixel(10, 15), Some(WHITE));
+        assert_eq!(core.render_target.get_pixel(10, 5), Some(NONE));
+    }
+
+    #[test]
+    fn test_fill_pie() {
+        let mut core = create_core(20, 20);
+
+        core.fill_pie(10, 10, 5, 0.0, std::f32::consts::FRAC_PI_2, WHITE);
+        assert_eq!(core.render_target.get_pixel(10, 10), Some(WHITE));
+        assert_eq!(core.render_target.get_pixel(12, 12), Some(WHITE));
+        assert_eq!(core.render_target.get_pixel(8, 8), Some(NONE));
+        assert_eq!(core.render_target.get_pixel(8, 12), Some(NONE));
+    }
+
+    #[test]
+    fn test_draw_sprite() {
+        let mut core = create_core(10, 10);
+        let sprite = Sprite::from_colors(2, 2, &[RED, NONE, Color::rgba(255, 255, 255, 128), BLUE]).unwrap();
+
+        core.clear(BLACK);
+        core.draw_sprite(8, 8, &sprite);
+        core.draw_sprite(-1, 0, &sprite);
+        assert_eq!(core.render_target.get_pixel(8, 8), Some(RED));
+        assert_eq!(core.render_target.get_pixel(9, 8), Some(BLACK));
+        assert_eq!(core.render_target.get_pixel(8, 9), Some(Color::rgb(128, 128, 128)));
+        assert_eq!(core.render_target.get_pixel(9, 9), Some(BLUE));
+        assert_eq!(core.render_target.get_pixel(0, 0), Some(BLACK));
+        assert_eq!(core.render_target.get_pixel(0, 1), Some(BLUE));
+
+        core.set_blend_mode(BlendMode::Replace);
+        core.draw_sprite(0, 4, &sprite);
+        assert_eq!(core.render_target.get_pixel(0, 5), Some(Color::rgba(255, 255, 255, 128)));
+    }
+
+    #[test]
+    fn test_draw_sprite_scaled() {
+        let mut core = create_core(10, 10);
+        let sprite = Sprite::from_colors(2, 1, &[RED, BLUE]).unwrap();
+
+        core.draw_sprite_scaled(1, 1, &sprite, 2.0, 3.0, Filter::Nearest);
+        assert_eq!(core.render_target.get_pixel(1, 1), Some(RED));
+        // sizes and positions near the limits of i32 don't overflow
+        core.draw_sprite_scaled(i32::MAX - 1, 0, &sprite, 2.0, 1.0, Filter::Nearest);
+        core.draw_sprite_scaled(8, 8, &sprite, f32::MAX, f32::MAX, Filter::Nearest);
+        core.draw_sprite_scaled(i32::MIN, i32::MIN, &sprite, 1e10, 1e10, Filter::Nearest);
+        assert_eq!(core.render_target.get_pixel(9, 9), Some(RED));
+        assert_eq!(core.render_target.get_pixel(2, 3), Some(RED));
+        assert_eq!(core.render_target.get_pixel(3, 3), Some(BLUE));
+        assert_eq!(core.render_target.get_pixel(4, 3), Some(BLUE));
+        assert_eq!(core.render_target.get_pixel(5, 3), Some(NONE));
+        assert_eq!(core.render_target.get_pixel(1, 4), Some(NONE));
+
+        // colors blend between pixel centers
+        core.clear(NONE);
+        core.draw_sprite_scaled(0, 0, &sprite, 4.0, 1.0, Filter::Bilinear);
+        assert_eq!(core.render_target.get_pixel(0, 0), Some(RED));
+        assert_eq!(core.render_target.get_pixel(7, 0), Some(BLUE));
+        let middle = core.render_target.get_pixel(4, 0).unwrap();
+        assert!(middle.r > 0 && middle.b > 0);
+
+        // scaled by the transform
+        core.clear(NONE);
+        core.scale(2.0, 2.0);
+        core.draw_sprite(1, 1, &sprite);
+        assert_eq!(core.render_target.get_pixel(2, 3), Some(RED));
+        assert_eq!(core.render_target.get_pixel(5, 3), Some(BLUE));
+        assert_eq!(core.render_target.get_pixel(6, 3), Some(NONE));
+    }
+
+    #[test]
+    fn test_draw_sprite_rotated() {
+        let mut core = create_core(10, 10);
+        let sprite = Sprite::from_colors(3, 1, &[RED, GREEN, BLUE]).unwrap();
+
+        // quarter turn around the center pixel turns the row into a column
+        core.draw_sprite_rotated(3, 5, &sprite, std::f32::consts::FRAC_PI_2, (1.5, 0.5));
+        assert_eq!(core.render_target.get_pixel(4, 4), Some(RED));
+        assert_eq!(core.render_target.get_pixel(4, 5), Some(GREEN));
+        assert_eq!(core.render_target.get_pixel(4, 6), Some(BLUE));
+        assert_eq!(core.render_target.get_pixel(3, 5), Some(NONE));
+        assert_eq!(core.render_target.data.iter().filter(|&&p| p != 0).count(), 3);
+
+        // rotated by the transform as well
+        core.clear(NONE);
+        core.translate(5.0, 0.0);
+        core.rotate(std::f32::consts::FRAC_PI_2);
+        core.draw_sprite(0, 0, &sprite);
+        assert_eq!(core.render_target.get_pixel(4, 0), Some(RED));
+        assert_eq!(core.render_target.get_pixel(4, 2), Some(BLUE));
+    }
+
+    #[test]
+    fn test_draw_sprite_tinted() {
+        let mut core = create_core(4, 4);
+        let sprite = Sprite::from_colors(2, 1, &[WHITE, Color::rgb(200, 200, 200)]).unwrap();
+
+        core.draw_sprite_tinted(0, 0, &sprite, Color::rgb(0, 255, 0));
+        assert_eq!(core.render_target.get_pixel(0, 0), Some(GREEN));
+        assert_eq!(core.render_target.get_pixel(1, 0), Some(Color::rgb(0, 200, 0)));
+
+        core.translate(0.0, 2.25);
+        core.draw_sprite_tinted(0, 0, &sprite, BLACK);
+        assert_eq!(core.render_target.get_pixel(0, 2), Some(BLACK));
+    }
+
+    #[test]
+    fn test_draw_sprite_part() {
+        let mut core = create_core(4, 4);
+        let sprite = Sprite::from_colors(3, 1, &[RED, GREEN, BLUE]).unwrap();
+
+        core.draw_sprite_part(0, 0, &sprite, Rect::new(1, 0, 5, 1));
+        assert_eq!(core.render_target.get_pixel(0, 0), Some(GREEN));
+        assert_eq!(core.render_target.get_pixel(1, 0), Some(BLUE));
+        assert_eq!(core.render_target.get_pixel(2, 0), Some(NONE));
+
+        // the skipped part keeps the position of the rest
+        core.draw_sprite_part(0, 1, &sprite, Rect::new(-1, 0, 2, 1));
+        assert_eq!(core.render_target.get_pixel(0, 1), Some(NONE));
+        assert_eq!(core.render_target.get_pixel(1, 1), Some(RED));
+
+        core.translate(0.0, 2.25);
+        core.draw_sprite_part(0, 0, &sprite, Rect::new(2, 0, 1, 1));
+        assert_eq!(core.render_target.get_pixel(0, 2), Some(BLUE));
+        assert_eq!(core.render_target.get_pixel(1, 2), Some(NONE));
+    }
+
+    #[test]
+    fn test_draw_bitmap() {
+        let mut core = create_core(12, 4);
+        let bitmap = [0b10100000, 0b00000000, 0b00000000, 0b10000000];
+
+        core.draw_bitmap(1, 1, &bitmap, 9, 2, RED);
+        assert_eq!(core.render_target.get_pixel(1, 1), Some(RED));
+        assert_eq!(core.render_target.get_pixel(3, 1), Some(RED));
+        assert_eq!(core.render_target.get_pixel(9, 2), Some(RED));
+        assert_eq!(core.render_target.data.iter().filter(|&&p| p != 0).count(), 3);
+
+        core.clear(NONE);
+        core.scale(2.0, 2.0);
+        core.draw_bitmap(0, 0, &bitmap, 9, 1, BLUE);
+        assert_eq!(core.render_target.get_pixel(1, 1), Some(BLUE));
+        assert_eq!(core.render_target.get_pixel(2, 0), Some(NONE));
+        assert_eq!(core.render_target.get_pixel(3, 1), Some(NONE));
+        assert_eq!(core.render_target.get_pixel(5, 1), Some(BLUE));
+    }
+
+    #[test]
+    fn test_draw_sprite_mips() {
+        let mut core = create_core(4, 4);
+        let colors: Vec<Color> = (0..16).map(|i| if (i + i / 4) % 2 == 0 { RED } else { BLUE }).collect();
+        let mut sprite = Sprite::from_colors(4, 4, &colors).unwrap();
+
+        core.draw_sprite_scaled(0, 0, &sprite, 0.25, 0.25, Filter::Nearest);
+        assert_eq!(core.render_target.get_pixel(0, 0), Some(RED));
+
+        sprite.generate_mips();
+        core.draw_sprite_scaled(0, 0, &sprite, 0.25, 0.25, Filter::Nearest);
+        assert_eq!(core.render_target.get_pixel(0, 0), Some(Color::rgb(127, 0, 127)));
+
+        core.translate(2.0, 0.0);
+        core.draw_sprite_scaled(0, 0, &sprite, 0.5, 0.5, Filter::Nearest);
+        assert_eq!(core.render_target.get_pixel(2, 0), Some(Color::rgb(127, 0, 127)));
+        assert_eq!(core.render_target.get_pixel(3, 1), Some(Color::rgb(127, 0, 127)));
+    }
+
+    #[test]
+    fn test_clip_rect() {
+        let mut core = create_core(10, 10);
+        core.set_clip_rect(Some(Rect::new(2, 2, 4, 4)));
+        assert_eq!(core.get_clip_rect(), Some(Rect::new(2, 2, 4, 4)));
+
+        core.fill_circle(5, 5, 5, RED);
+        core.draw_line(0, 0, 9, 9, GREEN);
+        core.draw_text(0, 0, "####", BLUE);
+        let sprite = Sprite::from_colors(1, 1, &[WHITE]).unwrap();
+        core.draw_sprite_scaled(0, 0, &sprite, 10.0, 10.0, Filter::Nearest);
+        for y in 0..10 {
+            for x in 0..10 {
+                let inside = (2..6).contains(&x) && (2..6).contains(&y);
+                assert_eq!(core.render_target.get_pixel(x, y) != Some(NONE), inside);
+            }
+        }
+
+        core.set_clip_rect(None);
+        core.draw(0, 0, RED);
+        assert_eq!(core.render_target.get_pixel(0, 0), Some(RED));
+    }
+
+    #[test]
+    fn test_layers() {
+        let mut core = create_core(4, 1);
+        core.add_layer("top", 1);
+        core.add_layer("bottom", -1);
+        core.add_layer("hidden", 2);
+
+        core.draw(0, 0, RED);
+        core.set_draw_layer(Some("bottom"));
+        core.set_blend_mode(BlendMode::Replace);
+        core.clear(BLUE);
+        // unknown layers keep drawing on the current one
+        core.set_draw_layer(Some("missing"));
+        core.draw(0, 0, GREEN);
+        core.set_draw_layer(Some("top"));
+        assert_eq!(core.get_blend_mode(), BlendMode::Replace);
+        core.draw(1, 0, GREEN);
+        core.set_draw_layer(Some("hidden"));
+        core.clear(WHITE);
+        core.set_layer_visible("hidden", false);
+
+        core.set_draw_layer(None);
+        assert_eq!(core.render_target.get_pixel(0, 0), Some(RED));
+
+        core.set_draw_layer(Some("top"));
+        core.composite_layers();
+        assert_eq!(core.composite.get_pixel(0, 0), Some(RED));
+        assert_eq!(core.composite.get_pixel(1, 0), Some(GREEN));
+        assert_eq!(core.composite.get_pixel(2, 0), Some(BLUE));
+        assert_eq!(core.render_target.get_pixel(1, 0), Some(GREEN));
+
+        // half transparent layer on top
+        core.set_layer_order("bottom", 5);
+        core.set_layer_opacity("bottom", 0.5);
+        core.composite_layers();
+        assert_eq!(core.composite.get_pixel(0, 0), Some(Color::rgb(127, 128, 0)));
+        assert_eq!(core.composite.get_pixel(3, 0).unwrap().b, 128);
+
+        core.remove_layer("bottom");
+        core.remove_layer("top");
+        core.draw(3, 0, WHITE);
+        core.composite_layers();
+        assert_eq!(core.composite.get_pixel(3, 0), Some(WHITE));
+        assert_eq!(core.composite.get_pixel(1, 0), Some(NONE));
+    }
+
+    #[test]
+    fn test_draw_to_target() {
+        let mut core = create_core(10, 10);
+        let mut target = RenderTarget::new(4, 4);
+        core.translate(5.0, 0.0);
+        core.draw_to_target(&mut target, |core| {
+            core.fill_rect(1, 1, 2, 2, RED);
+            core.draw(3, 3, Color::rgba(0, 0, 255, 128));
+        });
+        assert!(!core.get_transform().is_identity());
+        assert_eq!(target.get_pixel(1, 1), Some(RED));
+        assert_eq!(core.render_target.get_pixel(6, 1), Some(NONE));
+
+        core.draw_target(0, 0, &target);
+        assert_eq!(core.render_target.get_pixel(6, 1), Some(RED));
+        assert_eq!(core.render_target.get_pixel(5, 0), Some(NONE));
+        assert_eq!(core.render_target.get_pixel(8, 3).unwrap().a, 128);
+
+        core.reset_transform();
+        core.clear(WHITE);
+        core.draw_target(-2, -2, &target);
+        assert_eq!(core.render_target.get_pixel(0, 0), Some(RED));
+        assert_eq!(core.render_target.get_pixel(2, 2), Some(WHITE));
+    }
+
+    #[test]
+    fn test_camera() {
+        let mut core = create_core(10, 10);
+        core.set_camera(Some(Camera2D { position: (100.0, 100.0), zoom: 2.0, ..Camera2D::default() }));
+        assert_eq!(core.world_to_screen(101.0, 100.0), (7.0, 5.0));
+        assert_eq!(core.screen_to_world(7.0, 5.0), (101.0, 100.0));
+
+        // the transform places things in the world before the camera applies
+        core.translate(1.0, 0.0);
+        core.fill_rect(100, 99, 1, 1, RED);
+        assert_eq!(core.get_transform(), Transform::translation(1.0, 0.0));
+        assert_eq!(core.render_target.get_pixel(7, 3), Some(RED));
+        assert_eq!(core.render_target.get_pixel(8, 4), Some(RED));
+        assert_eq!(core.render_target.get_pixel(9, 4), Some(NONE));
+
+        // targets are drawn without the camera
+        let mut target = RenderTarget::new(2, 2);
+        core.draw_to_target(&mut target, |core| core.draw(1, 1, GREEN));
+        assert_eq!(target.get_pixel(1, 1), Some(GREEN));
+        assert!(core.get_camera().is_some());
+
+        core.set_camera(None);
+        core.reset_transform();
+        assert_eq!(core.screen_to_world(7.0, 5.0), (7.0, 5.0));
+        core.draw(0, 0, BLUE);
+        assert_eq!(core.render_target.get_pixel(0, 0), Some(BLUE));
+    }
+
+    #[test]
+    fn test_logical_size() {
+        let mut core = create_core(10, 10);
+        core.add_layer("ui", 1);
+        core.fill_rect(0, 0, 10, 10, RED);
+        core.set_logical_size(5, 2, ScaleMode::Integer);
+        assert_eq!(core.get_logical_size(), (5, 2));
+        assert_eq!(core.render_target.get_pixel(4, 1), Some(RED));
+        assert_eq!(core.render_target.get_pixel(5, 1), None);
+        assert_eq!(core.layers[0].target.width, 5);
+
+        // scaled by 2 and centered vertically
+        assert_eq!(core.viewport(), Some(Rect::new(0, 3, 10, 4)));
+        assert_eq!(core.window_to_screen(3.0, 5.0), (1.5, 1.0));
+
+        core.set_logical_size(10, 10, ScaleMode::Stretch);
+        assert_eq!(core.viewport(), None);
+        assert_eq!(core.window_to_screen(3.0, 5.0), (3.0, 5.0));
+    }
+
+    #[test]
+    fn test_pixel_ratio() {
+        let mut core = create_core(10, 10);
+        assert_eq!(core.window_size_to_screen(20, 8), (20, 8));
+        assert_eq!(core.window_to_screen(3.0, 5.0), (3.0, 5.0));
+
+        // native resolution on a display with twice as many pixels
+        core.pixel_ratio = 2.0;
+        assert_eq!(core.window_size_to_screen(20, 8), (40, 16));
+        assert_eq!(core.window_to_screen(3.0, 5.0), (6.0, 10.0));
+        core.pixel_ratio = 1.5;
+        assert_eq!(core.window_size_to_screen(5, 7), (8, 11));
+
+        // the logical size is scaled into the native pixels
+        core.pixel_ratio = 2.0;
+        core.set_logical_size(5, 2, ScaleMode::Integer);
+        assert_eq!(core.window_to_screen(1.5, 2.5), (1.5, 1.0));
+    }
+
+    #[test]
+    fn test_resize_window() {
+        let mut core = create_core(10, 10);
+        core.set_camera(Some(Camera2D::default()));
+        assert!(core.resize_window(20, 4));
+        assert!(!core.resize_window(20, 4));
+        assert!(!core.resize_window(0, 0));
+        assert_eq!(core.get_window_size(), (20, 4));
+        assert_eq!(core.get_logical_size(), (20, 4));
+        // the camera stays centered
+        assert_eq!(core.world_to_screen(0.0, 0.0), (10.0, 2.0));
+
+        // a logical size is scaled instead
+        core.set_logical_size(5, 5, ScaleMode::Letterbox);
+        assert!(core.resize_window(10, 20));
+        assert_eq!(core.get_logical_size(), (5, 5));
+        assert_eq!(core.viewport(), Some(Rect::new(0, 5, 10, 10)));
+    }
+
+    #[test]
+    fn test_take_dirty() {
+        let mut core = create_core(10, 10);
+        assert!(core.take_dirty());
+        assert!(!core.take_dirty());
+        core.draw(20, 20, RED);
+        assert!(!core.take_dirty());
+        core.draw(2, 2, RED);
+        assert!(core.take_dirty());
+
+        core.add_layer("ui", 1);
+        assert!(core.take_dirty());
+        core.set_draw_layer(Some("ui"));
+        core.draw(2, 2, RED);
+        assert!(core.take_dirty());
+        core.set_layer_opacity("ui", 0.5);
+        assert!(core.take_dirty());
+        core.remove_layer("ui");
+        assert!(core.take_dirty());
+        assert!(!core.take_dirty());
+    }
+
+    #[test]
+    fn test_post_process() {
+        let mut core = create_core(4, 4);
+        core.fill_rect(0, 0, 2, 4, RED);
+        core.set_post_process(Some(Box::new(|x, _, c| if x == 0 { BLUE } else { c })));
+        core.present();
+        assert_eq!(core.composite.get_pixel(0, 0), Some(BLUE));
+        assert_eq!(core.composite.get_pixel(1, 0), Some(RED));
+        assert_eq!(core.composite.get_pixel(2, 0), Some(NONE));
+        assert_eq!(core.render_target.get_pixel(0, 0), Some(RED));
+
+        // runs after the layers are combined
+        core.add_layer("ui", 1);
+        core.set_draw_layer(Some("ui"));
+        core.fill_rect(0, 0, 4, 4, GREEN);
+        core.present();
+        assert_eq!(core.composite.get_pixel(0, 3), Some(BLUE));
+        assert_eq!(core.composite.get_pixel(3, 3), Some(GREEN));
+    }
+
+    #[test]
+    fn test_palette() {
+        let mut core = create_core(4, 4);
+        core.set_palette(Some(Palette::new(&[BLACK, RED, GREEN])));
+        core.fill_rect(0, 0, 2, 4, Palette::index(1));
+        core.present();
+        assert_eq!(core.composite.get_pixel(0, 0), Some(RED));
+        assert_eq!(core.composite.get_pixel(3, 0), Some(BLACK));
+
+        core.get_palette_mut().unwrap().set(1, GREEN);
+        assert!(core.take_dirty());
+        core.set_post_process(Some(Box::new(|x, _, c| if x == 3 { BLUE } else { c })));
+        core.present();
+        assert_eq!(core.composite.get_pixel(1, 3), Some(GREEN));
+        assert_eq!(core.composite.get_pixel(3, 3), Some(BLUE));
+        assert_eq!(core.render_target.get_pixel(1, 3), Some(Palette::index(1)));
+    }
+
+    #[test]
+    fn test_dither() {
+        let mut core = create_core(4, 4);
+        core.clear(Color::rgb(100, 0, 0));
+        core.set_dither(Some(Palette::new(&[BLACK, RED])));
+        core.present();
+        let red = core.composite.data.iter().filter(|&&p| p == RED.into()).count();
+        assert_eq!(red, 6);
+        assert_eq!(core.composite.data.iter().filter(|&&p| p == BLACK.into()).count(), 16 - red);
+    }
+
+    #[test]
+    fn test_fill_triangle_depth() {
+        let mut core = create_core(10, 10);
+        core.enable_depth(true);
+        core.fill_triangle_depth((0, 0, 1.0), (9, 0, 1.0), (0, 9, 1.0), RED);
+        // in front on the left, behind on the right
+        core.fill_triangle_depth((0, 0, 0.0), (9, 0, 2.0), (9, 9, 2.0), BLUE);
+        assert_eq!(core.render_target.get_pixel(1, 0), Some(BLUE));
+        assert_eq!(core.render_target.get_pixel(5, 1), Some(RED));
+        assert_eq!(core.render_target.get_pixel(8, 8), Some(BLUE));
+
+        let mut texture = RenderTarget::new(2, 1);
+        texture.set_pixel(1, 0, GREEN);
+        core.clear_depth();
+        core.fill_triangle_textured_depth((0, 0, 0.0), (9, 0, 0.0), (0, 9, 0.0),
+            (0.0, 0.0), (1.0, 0.0), (0.0, 0.0), &texture);
+        assert_eq!(core.render_target.get_pixel(8, 0), Some(GREEN));
+        assert_eq!(core.render_target.get_pixel(1, 3), Some(RED));
+        assert_eq!(core.render_target.get_depth(1, 3), Some(f32::INFINITY));
+
+        core.enable_depth(false);
+        core.fill_triangle_depth((0, 0, 5.0), (9, 0, 5.0), (0, 9, 5.0), WHITE);
+        assert_eq!(core.render_target.get_pixel(1, 3), Some(WHITE));
+    }
+
+    #[test]
+    fn test_supersampling() {
+        let mut core = create_core(4, 4);
+        core.draw(0, 0, RED);
+        core.set_clip_rect(Some(Rect::new(0, 0, 3, 3)));
+        core.set_supersampling(true);
+        assert_eq!((core.render_target.width, core.get_logical_size()), (8, (4, 4)));
+        assert_eq!(core.render_target.get_pixel(1, 1), Some(RED));
+        assert_eq!(core.get_clip_rect(), Some(Rect::new(0, 0, 3, 3)));
+
+        core.draw(1, 1, GREEN);
+        assert_eq!(core.render_target.get_pixel(3, 3), Some(GREEN));
+        // the edge of the triangle only covers part of a pixel
+        core.fill_triangle_f(0.0, 0.0, 4.0, 0.0, 0.0, 4.0, WHITE);
+        core.present();
+        assert_eq!(core.composite.width, 4);
+        assert_eq!(core.composite.get_pixel(1, 1), Some(WHITE));
+        let edge = core.composite.get_pixel(2, 2).unwrap();
+        assert!(edge.r > 0 && edge.r < 255);
+
+        core.set_supersampling(false);
+        assert_eq!(core.render_target.width, 4);
+        assert_eq!(core.render_target.get_pixel(1, 1), Some(WHITE));
+        assert_eq!(core.get_clip_rect(), Some(Rect::new(0, 0, 3, 3)));
+
+        // one pixel wide outlines keep their full color after downsampling
+        let mut core = create_core(16, 16);
+        core.set_supersampling(true);
+        core.clear(BLACK);
+        core.draw_line(0, 2, 7, 2, WHITE);
+        core.draw_rect(8, 8, 4, 4, WHITE);
+        core.draw_circle(4, 11, 2, WHITE);
+        core.present();
+        let frame = core.get_frame();
+        assert_eq!(frame.width, 16);
+        assert_eq!(frame.get_pixel(3, 2), Some(WHITE));
+        assert_eq!(frame.get_pixel(3, 3), Some(BLACK));
+        assert_eq!((frame.get_pixel(8, 10), frame.get_pixel(10, 8)), (Some(WHITE), Some(WHITE)));
+        assert_eq!(frame.get_pixel(9, 9), Some(BLACK));
+        assert_eq!((frame.get_pixel(4, 9), frame.get_pixel(6, 11)), (Some(WHITE), Some(WHITE)));
+        assert_eq!(frame.get_pixel(4, 11), Some(BLACK));
+    }
+
+    #[test]
+    fn test_snapshot_region() {
+        let mut core = create_core(10, 10);
+        core.fill_rect(2, 2, 2, 2, RED);
+        core.draw(3, 3, Color::rgba(0, 255, 0, 128));
+        let snapshot = core.snapshot_region(Rect::new(1, 1, 3, 12));
+        assert_eq!((snapshot.width, snapshot.height), (3, 12));
+        assert_eq!(snapshot.get_pixel(0, 0), Some(NONE));
+        assert_eq!(snapshot.get_pixel(1, 1), Some(RED));
+        assert_eq!(snapshot.get_pixel(2, 2), core.render_target.get_pixel(3, 3));
+        assert_eq!(snapshot.get_pixel(2, 11), Some(NONE));
+        assert!(core.snapshot_region(Rect::new(0, 0, -1, 5)).data.is_empty());
+
+        core.set_supersampling(true);
+        let snapshot = core.snapshot_region(Rect::new(2, 2, 2, 2));
+        assert_eq!((snapshot.width, snapshot.height), (2, 2));
+        assert_eq!(snapshot.get_pixel(0, 0), Some(RED));
+    }
+
+    #[test]
+    fn test_physical_keys() {
+        let mut core = create_core(4, 4);
+        let w = PhysicalKey(Key::W);
+        let mut input = InputMap::new();
+        input.bind("up", Binding::Physical(w));
+        core.set_input_map(input);
+        assert!(!core.physical_key_down(w) && !core.action_down("up"));
+
+        core.physical_keys = vec![Key::W, Key::A];
+        assert!(core.physical_key_down(w) && core.physical_key_pressed(w));
+        assert!(core.action_down("up") && core.action_pressed("up"));
+
+        core.physical_last = replace(&mut core.physical_keys, vec![Key::A]);
+        assert!(!core.physical_key_down(w) && core.physical_key_released(w));
+        assert!(core.action_released("up"));
+        assert!(!core.physical_key_pressed(PhysicalKey(Key::A)));
+    }
+
+    #[test]
+    fn test_actions() {
+        let mut core = create_core(4, 4);
+        let mut input = InputMap::new();
+        input.bind("fire", Binding::Mouse(MouseButton::Left));
+        input.bind("fire", Binding::Mouse(MouseButton::Right));
+        core.set_input_map(input);
+        assert!(!core.action_down("fire"));
+
+        core.mouse_buttons = [true, false, false];
+        assert!(core.action_down("fire") && core.action_pressed("fire"));
+        assert!(!core.action_down("jump"));
+
+        // pressing another binding while one is held doesn't press the action again
+        core.mouse_previous = core.mouse_buttons;
+        core.mouse_buttons = [true, false, true];
+        assert!(core.mouse_button_pressed(MouseButton::Right));
+        assert!(!core.action_pressed("fire"));
+
+        core.mouse_previous = core.mouse_buttons;
+        core.mouse_buttons = [false, false, true];
+        assert!(!core.action_released("fire"));
+        core.mouse_previous = core.mouse_buttons;
+        core.mouse_buttons = [false; 3];
+        assert!(core.action_released("fire"));
+
+        core.get_input_map_mut().clear("fire");
+        core.mouse_buttons = [true; 3];
+        assert!(!core.action_down("fire"));
+    }
+
+    #[test]
+    fn test_window_state() {
+        #[derive(Default)]
+        struct Events(Vec<&'static str>);
+        impl RainApp for Events {
+            fn on_focus_gained(&mut self) { self.0.push("focus gained") }
+            fn on_focus_lost(&mut self) { self.0.push("focus lost") }
+            fn on_mouse_enter(&mut self) { self.0.push("mouse enter") }
+            fn on_mouse_leave(&mut self) { self.0.push("mouse leave") }
+        }
+
+        let mut core = create_core(4, 4);
+        let mut app = Events::default();
+        assert!(core.is_focused());
+        core.update_window_state(&mut app, true, false);
+        assert!(app.0.is_empty());
+        core.update_window_state(&mut app, false, true);
+        assert!(!core.is_focused());
+        core.update_window_state(&mut app, false, true);
+        core.update_window_state(&mut app, true, false);
+        assert_eq!(app.0, vec!["focus lost", "mouse enter", "focus gained", "mouse leave"]);
+    }
+
+    #[test]
+    fn test_mouse_mode() {
+        let core = create_core(4, 3);
+        assert_eq!(core.apply_mouse_mode(-2.0, 5.0, MouseMode::Pass), Some((-2.0, 5.0)));
+        assert_eq!(core.apply_mouse_mode(-2.0, 5.0, MouseMode::Clamp), Some((0.0, 2.0)));
+        assert_eq!(core.apply_mouse_mode(-2.0, 1.0, MouseMode::Discard), None);
+        assert_eq!(core.apply_mouse_mode(3.5, 2.5, MouseMode::Discard), Some((3.5, 2.5)));
+        assert_eq!(core.apply_mouse_mode(4.0, 2.5, MouseMode::Discard), None);
+
+        let core = create_core(0, 0);
+        assert_eq!(core.apply_mouse_mode(-2.0, 5.0, MouseMode::Clamp), Some((0.0, 0.0)));
+        assert_eq!(core.apply_mouse_mode(0.0, 0.0, MouseMode::Discard), None);
+    }
+
+    #[test]
+    fn test_run_frames() {
+        struct App(Vec<&'static str>);
+        impl RainApp for App {
+            fn on_start(&mut self) { self.0.push("start") }
+            fn on_update(&mut self, rain: &mut RainCore, dt: Duration) {
+                assert_eq!(dt, Duration::from_millis(20));
+                self.0.push("update");
+                rain.fill_rect(0, 0, 1, 1, RED);
+                if self.0.len() == 3 {
+                    rain.exit();
+                }
+            }
+            fn on_exit(&mut self) { self.0.push("exit") }
+        }
+
+        let mut core = create_core(4, 4);
+        core.set_target_fps(Some(50));
+        core.add_layer("top", 1);
+        core.set_draw_layer(Some("top"));
+        core.fill_rect(1, 1, 1, 1, BLUE);
+        core.set_draw_layer(None);
+
+        let mut app = App(Vec::new());
+        core.run_frames(10, &mut app);
+        assert_eq!(app.0, vec!["start", "update", "update", "exit"]);
+        assert_eq!(core.get_frame().get_pixel(0, 0), Some(RED));
+        assert_eq!(core.get_frame().get_pixel(1, 1), Some(BLUE));
+    }
+
+    #[test]
+    fn test_window_position() {
+        let mut core = create_core(4, 4);
+        assert_eq!(core.get_window_position(), None);
+        core.set_window_position(-20, 300);
+        assert_eq!(core.get_window_position(), Some((-20, 300)));
+    }
+
+    #[test]
+    fn test_title() {
+        let mut core = create_core(4, 4);
+        core.set_title("game");
+        assert_eq!(core.title(), "game");
+        core.fps = 60;
+        assert_eq!(core.title(), "game - FPS: 60");
+        core.set_title_fps(false);
+        assert_eq!(core.title(), "game");
+    }
+
+    #[test]
+    fn test_target_fps() {
+        let mut core = create_core(4, 4);
+        core.set_target_fps(Some(0));
+        assert_eq!(core.get_target_fps(), None);
+        core.set_target_fps(Some(50));
+        assert_eq!(core.get_target_fps(), Some(50));
+
+        let now = Instant::now();
+        let frame = Duration::from_millis(20);
+        assert_eq!(next_frame_deadline(None, now, 50), now + frame);
+        // a slow frame is made up for by the next one
+        assert_eq!(next_frame_deadline(Some(now), now + Duration::from_millis(30), 50), now + frame);
+        assert_eq!(next_frame_deadline(Some(now), now + Duration::from_millis(50), 50), now + Duration::from_millis(70));
+
+        assert_eq!(core.window_update_rate(), None);
+        core.set_update_rate(Some(Duration::from_millis(10)));
+        assert_eq!(core.get_update_rate(), Some(Duration::from_millis(10)));
+        assert_eq!(core.window_update_rate(), None);
+        core.set_target_fps(None);
+        assert_eq!(core.window_update_rate(), Some(Duration::from_millis(10)));
+        core.set_target_fps(Some(50));
+
+        let start = Instant::now();
+        core.wait_for_frame();
+        core.wait_for_frame();
+        assert!(start.elapsed() >= frame);
+    }
+
+    #[test]
+    fn test_input_buffer() {
+        let mut core = create_core(4, 4);
+        let mut input = InputMap::new();
+        input.bind("jump", Binding::Mouse(MouseButton::Left));
+        core.set_input_map(input);
+
+        // nothing is kept without a buffer
+        core.mouse_buttons[0] = true;
+        core.update_input_buffer(Instant::now());
+        assert!(!core.consume_buffered("jump", Duration::from_secs(1)));
+
+        core.set_input_buffer(Some(Duration::from_secs(10)));
+        core.update_input_buffer(Instant::now());
+        assert!(!core.consume_buffered("fire", Duration::from_secs(1)));
+        assert!(core.consume_buffered("jump", Duration::from_secs(1)));
+        assert!(!core.consume_buffered("jump", Duration::from_secs(1)));
+
+        // too old for the requested time
+        let earlier = Instant::now() - Duration::from_millis(500);
+        core.buffered.push(("jump".to_string(), earlier));
+        assert!(!core.consume_buffered("jump", Duration::from_millis(100)));
+        assert!(core.consume_buffered("jump", Duration::from_secs(1)));
+
+        // and forgotten once older than the buffer
+        core.set_input_buffer(Some(Duration::from_millis(100)));
+        core.mouse_previous[0] = true;
+        core.buffered.push(("jump".to_string(), earlier));
+        core.update_input_buffer(Instant::now());
+        assert!(core.buffered.is_empty());
+    }
+
+    #[test]
+    fn test_drag() {
+        let mut core = create_core(4, 4);
+        core.mouse_pos = Some((1.0, 1.0));
+        core.mouse_buttons[2] = true;
+        core.update_drag();
+        assert!(!core.is_dragging(MouseButton::Right));
+
+        core.mouse_previous[2] = true;
+        core.mouse_pos = Some((6.0, 1.0));
+        core.update_drag();
+        assert!(core.drag_started(MouseButton::Right));
+        assert!(!core.drag_started(MouseButton::Left));
+        assert_eq!((core.drag_origin(), core.drag_delta()), (Some((1.0, 1.0)), Some((5.0, 0.0))));
+
+        core.mouse_buttons[2] = false;
+        core.update_drag();
+        assert!(core.drag_ended(MouseButton::Right) && !core.is_dragging(MouseButton::Right));
+        core.mouse_previous[2] = false;
+        core.update_drag();
+        assert_eq!(core.drag_delta(), None);
+    }
+
+    #[test]
+    fn test_key_repeat() {
+        let mut core = create_core(4, 4);
+        assert_eq!(core.get_key_repeat(), (Duration::from_millis(250), Duration::from_millis(50)));
+        core.set_key_repeat(Duration::from_millis(400), Duration::from_millis(100));
+        assert_eq!(core.get_key_repeat(), (Duration::from_millis(400), Duration::from_millis(100)));
+        assert!(!core.key_pressed_repeat(Key::Down));
+    }
+
+    #[test]
+    fn test_chords() {
+        let mut core = create_core(4, 4);
+        assert!(!core.key_chord_pressed(&[]));
+        core.mouse_buttons[0] = true;
+        assert!(core.mouse_chord_pressed(&[], MouseButton::Left));
+        assert!(!core.mouse_chord_pressed(&[Key::LeftShift], MouseButton::Left));
+        core.mouse_previous[0] = true;
+        assert!(!core.mouse_chord_pressed(&[], MouseButton::Left));
+
+        assert!(chord_key(Key::LeftCtrl, |key| key == Key::RightCtrl));
+        assert!(!chord_key(Key::S, |key| key == Key::RightCtrl));
+    }
+
+    #[test]
+    fn test_cursor() {
+        let mut core = create_core(4, 4);
+        assert!(core.is_cursor_visible());
+        assert_eq!(core.get_cursor_style(), CursorStyle::Arrow);
+        core.set_cursor_visible(false);
+        core.set_cursor_style(CursorStyle::OpenHand);
+        assert!(!core.is_cursor_visible());
+        assert_eq!(core.get_cursor_style(), CursorStyle::OpenHand);
+    }
+
+    #[test]
+    fn test_cursor_grab() {
+        let mut core = create_core(4, 4);
+        assert!(!core.is_cursor_grabbed());
+        if core.set_cursor_grab(true) {
+            assert!(core.is_cursor_grabbed());
+        }
+        // nothing to confine without a window
+        core.update_cursor_grab();
+        assert!(!core.cursor_grabbed);
+        assert!(core.set_cursor_grab(false));
+        assert!(!core.is_cursor_grabbed());
+    }
+
+    #[test]
+    fn test_mouse_delta() {
+        let mut core = create_core(4, 4);
+        assert_eq!(core.get_mouse_delta(), (0.0, 0.0));
+        core.mouse_pos = Some((10.0, 5.0));
+        assert_eq!(core.get_mouse_delta(), (0.0, 0.0));
+        core.mouse_last = core.mouse_pos;
+        core.mouse_pos = Some((-2.0, 8.5));
+        assert_eq!(core.get_mouse_delta(), (-12.0, 3.5));
+    }
+
+    #[test]
+    fn test_draw_text() {
+        let mut core = create_core(32, 20);
+        core.draw_text(0, 0, "|\n.", WHITE);
+        assert_eq!(core.render_target.get_pixel(2, 0), Some(WHITE));
+        assert_eq!(core.render_target.get_pixel(2, 6), Some(WHITE));
+        assert_eq!(core.render_target.get_pixel(1, 13), Some(WHITE));
+        assert_eq!(core.render_target.data.iter().filter(|&&p| p != 0).count(), 7 + 4);
+
+        core.clear(NONE);
+        core.draw_text_scaled(1, 0, " -", 2, RED);
+        assert_eq!(core.render_target.get_pixel(13, 6), Some(RED));
+        assert_eq!(core.render_target.get_pixel(22, 7), Some(RED));
+        assert_eq!(core.render_target.get_pixel(23, 7), Some(NONE));
+        assert_eq!(core.render_target.data.iter().filter(|&&p| p != 0).count(), 5 * 2 * 2);
+    }
+
+    #[test]
+    #[cfg(feature = "truetype")]
+    fn test_draw_text_ttf() {
+        let mut core = create_core(32, 24);
+        let font = Font::from_bytes(&truetype::test_font()).unwrap();
+
+        // squares of 10 pixels with the top of the line 8 pixels above the baseline
+        core.draw_text_ttf(0, 2, "AB\nA", &font, 10.0, WHITE);
+        assert_eq!(core.render_target.get_pixel(0, 0), Some(WHITE));
+        assert_eq!(core.render_target.get_pixel(9, 9), Some(WHITE));
+        assert_eq!(core.render_target.get_pixel(11, 5), Some(NONE));
+        assert_eq!(core.render_target.get_pixel(17, 5), Some(WHITE));
+        assert_eq!(core.render_target.get_pixel(27, 5), Some(NONE));
+        assert_eq!(core.render_target.get_pixel(5, 19), Some(WHITE));
+        assert_eq!(core.render_target.get_pixel(5, 9), Some(WHITE));
+    }
+
+    #[test]
+    fn test_measure_text() {
+        let core = create_core(1, 1);
+        let font = Font::builtin();
+        assert_eq!(core.measure_text("", &font, 8.0), (0, 0));
+        assert_eq!(core.measure_text("abc", &font, 8.0), (18, 8));
+        assert_eq!(core.measure_text("abc\nde\n", &font, 16.0), (36, 48));
+
+        // the built-in font draws the same at the matching size
+        let mut core = create_core(16, 16);
+        core.draw_text_ttf(0, 0, "-", &font, 14.0, WHITE);
+        assert_eq!(core.render_target.get_pixel(9, 7), Some(WHITE));
+    }
+
+    #[test]
+    fn test_draw_text_wrapped() {
+        let mut core = create_core(40, 40);
+        let font = Font::builtin();
+        let count = |core: &RainCore, x: i32, y: i32, w: i32, h: i32| {
+            (x..x + w).flat_map(|px| (y..y + h).map(move |py| (px, py)))
+                .filter(|&(px, py)| core.render_target.get_pixel(px, py) != Some(NONE))
+                .count()
+        };
+
+        // two lines of one character each in the bottom right corner
+        core.draw_text_wrapped(Rect::new(30, 0, 10, 40), "| |", &font, 8.0, TextAlign::Right, VerticalAlign::Bottom, WHITE);
+        assert_eq!(count(&core, 34, 24, 6, 16), 14);
+        assert_eq!(count(&core, 0, 0, 40, 40), 14);
+
+        core.clear(NONE);
+        core.draw_text_wrapped(Rect::new(0, 0, 40, 40), "||", &font, 8.0, TextAlign::Center, VerticalAlign::Middle, WHITE);
+        assert_eq!(core.render_target.get_pixel(16, 16), Some(WHITE));
+        assert_eq!(core.render_target.get_pixel(22, 22), Some(WHITE));
+        assert_eq!(count(&core, 0, 0, 40, 40), 14);
+    }
+
+    #[test]
+    fn test_draw_text_bmfont() {
+        let mut core = create_core(20, 10);
+        let page = Sprite::from_colors(8, 4, &[WHITE; 32]).unwrap();
+        let font = Font::from_bmfont_str(bitmapfont::test_descriptor(), vec![page]).unwrap();
+
+        // B is pulled 2 pixels closer to A by kerning
+        assert_eq!(core.measure_text("AB", &font, 4.0), (6, 5));
+        assert_eq!(core.measure_text("BA\nA", &font, 8.0), (16, 20));
+
+        core.draw_text_ttf(0, 0, "AB", &font, 4.0, RED);
+        assert_eq!(core.render_target.get_pixel(0, 0), Some(NONE));
+        assert_eq!(core.render_target.get_pixel(0, 1), Some(RED));
+        assert_eq!(core.render_target.get_pixel(4, 0), Some(RED));
+        assert_eq!(core.render_target.get_pixel(6, 0), Some(NONE));
+        assert_eq!(core.render_target.data.iter().filter(|&&p| p != 0).count(), 16 + 8);
+
+        // twice the size
+        core.clear(NONE);
+        core.draw_text_ttf(0, 0, "B", &font, 8.0, RED);
+        assert_eq!(core.render_target.get_pixel(2, 0), Some(RED));
+        assert_eq!(core.render_target.data.iter().filter(|&&p| p != 0).count(), 32);
+    }
+
+    #[test]
+    fn test_draw_text_grid() {
+        let mut core = create_core(20, 10);
+        let sheet = Sprite::from_ascii("
+            #.##
+            #..#
+        ", &[('#', WHITE)]);
+        assert!(Font::from_grid(&sheet, 3, 3, 'a').is_none());
+        assert!(Font::from_grid(&sheet, 0, 1, 'a').is_none());
+
+        let font = Font::from_grid(&sheet, 2, 2, 'a').unwrap();
+        assert_eq!(core.measure_text("ab", &font, 2.0), (4, 2));
+
+        // unknown characters are skipped
+        core.draw_text_ttf(0, 0, "b?a", &font, 4.0, GREEN);
+        assert_eq!(core.render_target.get_pixel(0, 0), Some(GREEN));
+        assert_eq!(core.render_target.get_pixel(0, 3), Some(NONE));
+        assert_eq!(core.render_target.get_pixel(2, 3), Some(GREEN));
+        assert_eq!(core.render_target.get_pixel(4, 3), Some(GREEN));
+        assert_eq!(core.render_target.get_pixel(6, 0), Some(NONE));
+        assert_eq!(core.render_target.data.iter().filter(|&&p| p != 0).count(), 3 * 4 + 2 * 4);
+    }
+
+    #[test]
+    fn test_draw_text_styled() {
+        let mut core = create_core(20, 20);
+        let font = Font::builtin();
+        let style = TextStyle { outline: Some((RED, 1)), shadow: Some((BLUE, 2, 2)) };
+        core.draw_text_styled(5, 5, "|", &font, 8.0, &style, WHITE);
+        assert_eq!(core.render_target.get_pixel(7, 5), Some(WHITE));
+        assert_eq!(core.render_target.get_pixel(6, 5), Some(RED));
+        assert_eq!(core.render_target.get_pixel(7, 4), Some(RED));
+        assert_eq!(core.render_target.get_pixel(6, 4), Some(NONE));
+        assert_eq!(core.render_target.get_pixel(8, 12), Some(BLUE));
+        assert_eq!(core.render_target.get_pixel(10, 13), Some(BLUE));
+        assert_eq!(core.render_target.get_pixel(9, 14), Some(BLUE));
+        assert_eq!(core.render_target.get_pixel(10, 14), Some(NONE));
+
+        // a transparent shadow is blended once where it overlaps itself
+        core.clear(WHITE);
+        let style = TextStyle { shadow: Some((Color::rgba(0, 0, 0, 128), 1, 0)), ..TextStyle::default() };
+        core.draw_text_styled(5, 5, "-", &font, 8.0, &style, RED);
+        assert_eq!(core.render_target.get_pixel(9, 8), Some(RED));
+        let shadow = core.render_target.get_pixel(10, 8).unwrap();
+        assert!(shadow.r > 100 && shadow.r < 150);
+    }
+
+    #[test]
+    fn test_draw_text_spans() {
+        let mut core = create_core(20, 20);
+        core.draw_text_spans(0, 0, &[("|", RED), ("|\n|", GREEN)], &Font::builtin(), 8.0);
+        assert_eq!(core.render_target.get_pixel(2, 0), Some(RED));
+        assert_eq!(core.render_target.get_pixel(8, 6), Some(GREEN));
+        assert_eq!(core.render_target.get_pixel(2, 8), Some(GREEN));
+        assert_eq!(core.render_target.data.iter().filter(|&&p| p != 0).count(), 3 * 7);
+
+        // kerning applies between spans
+        core.clear(NONE);
+        let page = Sprite::from_colors(8, 4, &[WHITE; 32]).unwrap();
+        let font = Font::from_bmfont_str(bitmapfont::test_descriptor(), vec![page]).unwrap();
+        core.draw_text_spans(0, 0, &[("A", RED), ("B", GREEN)], &font, 4.0);
+        assert_eq!(core.render_target.get_pixel(3, 1), Some(RED));
+        assert_eq!(core.render_target.get_pixel(4, 1), Some(GREEN));
+        assert_eq!(core.render_target.get_pixel(6, 1), Some(NONE));
+    }
+
+    #[test]
+    #[cfg(feature = "truetype")]
+    fn test_measure_text_ttf() {
+        let core = create_core(1, 1);
+        let font = Font::from_bytes(&truetype::test_font()).unwrap();
+        assert_eq!(core.measure_text("AB", &font, 10.0), (29, 10));
+        assert_eq!(core.measure_text("A\nAB", &font, 20.0), (58, 40));
+    }
+
+    #[test]
+    fn test_draw_sprite_flipped() {
+        let mut core = create_core(10, 10);
+        let sprite = Sprite::from_colors(2, 2, &[RED, GREEN, BLUE, WHITE]).unwrap();
+
+        core.draw_sprite_flipped(0, 0, &sprite, Flip::Horizontal);
+        core.draw_sprite_flipped(4, 0, &sprite, Flip::Vertical);
+        assert_eq!(core.render_target.get_pixel(0, 0), Some(GREEN));
+        assert_eq!(core.render_target.get_pixel(1, 1), Some(BLUE));
+        assert_eq!(core.render_target.get_pixel(4, 0), Some(BLUE));
+        assert_eq!(core.render_target.get_pixel(5, 1), Some(GREEN));
+
+        // same result through the transform
+        core.translate(0.0, 0.25);
+        core.draw_sprite_flipped(0, 4, &sprite, Flip::Both);
+        assert_eq!(core.render_target.get_pixel(0, 4), Some(WHITE));
+        assert_eq!(core.render_target.get_pixel(1, 5), Some(RED));
+    }
+
+    #[test]
+    fn test_fill_triangle() {
+        let mut core = create_core(20, 20);
+
+        core.fill_triangle(2, 2, 12, 2, 2, 12, WHITE);
+        assert_eq!(core.render_target.get_pixel(2, 2), Some(WHITE));
+        assert_eq!(core.render_target.get_pixel(12, 2), Some(WHITE));
+        assert_eq!(core.render_target.get_pixel(2, 12), Some(WHITE));
+        assert_eq!(core.render_target.get_pixel(7, 7), Some(WHITE));
+        assert_eq!(core.render_target.get_pixel(8, 8), Some(NONE));
+        assert_eq!(core.render_target.get_pixel(13, 2), Some(NONE));
+
+        // every row is filled once, so translucent triangles have a uniform color
+        core.clear(NONE);
+        core.fill_triangle(-10, 5, 30, 0, 5, 40, Color::rgba(255, 255, 255, 128));
+        assert_eq!(core.render_target.get_pixel(0, 5), Some(Color::rgba(128, 128, 128, 128)));
+        for p in core.render_target.data.iter().filter(|&&p| p != 0) {
+            assert_eq!(Color::from(*p), Color::rgba(128, 128, 128, 128));
+        }
+    }
+
+    #[test]
+    fn test_fill_triangle_gradient() {
+        let mut core = create_core(100, 100);
+
+        core.fill_triangle_gradient(0, 0, 100, 0, 0, 100, RED, GREEN, BLUE);
+        let corners = [(0, 0), (98, 0), (0, 98)];
+        let colors: Vec<Color> = corners.iter().map(|&(x, y)| core.render_target.get_pixel(x, y).unwrap()).collect();
+        assert!(colors[0].r > 240 && colors[0].g < 10 && colors[0].b < 10);
+        assert!(colors[1].g > 240 && colors[1].r < 10 && colors[1].b < 10);
+        assert!(colors[2].b > 240 && colors[2].r < 10 && colors[2].g < 10);
+
+        let center = core.render_target.get_pixel(33, 33).unwrap();
+        assert!(center.r > 70 && center.g > 70 && center.b > 70);
+        assert_eq!(core.render_target.get_pixel(80, 80), Some(NONE));
+    }
+
+    #[test]
+    fn test_fill_triangle_textured() {
+        let mut core = create_core(20, 20);
+
+        // left half red, right half blue
+        let mut texture = RenderTarget::new(2, 1);
+        texture.set_pixel(0, 0, RED);
+        texture.set_pixel(1, 0, BLUE);
+
+        core.fill_triangle_textured((0, 0), (20, 0), (0, 20), (0.0, 0.0), (1.0, 0.0), (0.0, 1.0), &texture);
+        assert_eq!(core.render_target.get_pixel(2, 2), Some(RED));
+        assert_eq!(core.render_target.get_pixel(15, 2), Some(BLUE));
+        assert_eq!(core.render_target.get_pixel(15, 15), Some(NONE));
+    }
+
+    #[test]
+    fn test_draw_polyline() {
+        let mut core = create_core(20, 20);
+
+        core.draw_polyline(&[(2, 2), (10, 2), (10, 10)], WHITE);
+        assert_eq!(core.render_target.get_pixel(2, 2), Some(WHITE));
+        assert_eq!(core.render_target.get_pixel(10, 2), Some(WHITE));
+        assert_eq!(core.render_target.get_pixel(10, 10), Some(WHITE));
+        assert_eq!(core.render_target.get_pixel(6, 6), Some(NONE));
+    }
+
+    #[test]
+    fn test_draw_polyline_closed() {
+        let mut core = create_core(20, 20);
+
+        core.draw_polyline_closed(&[(2, 2), (10, 2), (10, 10), (2, 10)], WHITE);
+        assert_eq!(core.render_target.get_pixel(2, 6), Some(WHITE));
+        assert_eq!(core.render_target.get_pixel(2, 10), Some(WHITE));
+        assert_eq!(core.render_target.get_pixel(10, 10), Some(WHITE));
+        assert_eq!(core.render_target.get_pixel(6, 6), Some(NONE));
+    }
+
+    #[test]
+    fn test_draw_bezier() {
+        let mut core = create_core(30, 30);
+
+        core.draw_bezier_quad((2, 20), (15, 0), (28, 20), WHITE);
+        assert_eq!(core.render_target.get_pixel(2, 20), Some(WHITE));
+        assert_eq!(core.render_target.get_pixel(15, 10), Some(WHITE));
+        assert_eq!(core.render_target.get_pixel(28, 20), Some(WHITE));
+
+        core.draw_bezier_cubic((2, 28), (2, 20), (28, 20), (28, 28), RED);
+        assert_eq!(core.render_target.get_pixel(2, 28), Some(RED));
+        assert_eq!(core.render_target.get_pixel(15, 22), Some(RED));
+        assert_eq!(core.render_target.get_pixel(28, 28), Some(RED));
+    }
+
+    #[test]
+    fn test_draw_spline() {
+        let mut core = create_core(30, 30);
+
+        core.draw_spline(&[(2.0, 15.0), (10.0, 5.0), (20.0, 25.0), (28.0, 15.0)], WHITE);
+        assert_eq!(core.render_target.get_pixel(2, 15), Some(WHITE));
+        assert_eq!(core.render_target.get_pixel(10, 5), Some(WHITE));
+        assert_eq!(core.render_target.get_pixel(20, 25), Some(WHITE));
+        assert_eq!(core.render_target.get_pixel(28, 15), Some(WHITE));
+    }
+
+    #[test]
+    fn test_stroke_path() {
+        let mut core = create_core(20, 20);
+
+        let mut path = Path::new();
+        path.move_to(2.0, 2.0).line_to(12.0, 2.0).line_to(12.0, 12.0).close();
+        core.stroke_path(&path, WHITE);
+        assert_eq!(core.render_target.get_pixel(7, 2), Some(WHITE));
+        assert_eq!(core.render_target.get_pixel(12, 7), Some(WHITE));
+        assert_eq!(core.render_target.get_pixel(7, 7), Some(WHITE));
+        assert_eq!(core.render_target.get_pixel(4, 8), Some(NONE));
+    }
+
+    #[test]
+    fn test_fill_path_with_hole() {
+        let mut core = create_core(20, 20);
+
+        let mut path = Path::new();
+        path.move_to(0.0, 0.0).line_to(16.0, 0.0).line_to(16.0, 16.0).line_to(0.0, 16.0).close();
+        path.move_to(4.0, 4.0).line_to(12.0, 4.0).line_to(12.0, 12.0).line_to(4.0, 12.0).close();
+        core.fill_path(&path, WHITE);
+        assert_eq!(core.render_target.get_pixel(1, 1), Some(WHITE));
+        assert_eq!(core.render_target.get_pixel(14, 14), Some(WHITE));
+        assert_eq!(core.render_target.get_pixel(8, 8), Some(NONE));
+        assert_eq!(core.render_target.get_pixel(17, 17), Some(NONE));
+    }
+
+    #[test]
+    fn test_fill_polygon() {
+        let mut core = create_core(20, 20);
+
+        // U shape
+        core.fill_polygon(&[(2, 2), (6, 2), (6, 10), (12, 10), (12, 2), (16, 2), (16, 14), (2, 14)], WHITE);
+        assert_eq!(core.render_target.get_pixel(2, 2), Some(WHITE));
+        assert_eq!(core.render_target.get_pixel(15, 13), Some(WHITE));
+        assert_eq!(core.render_target.get_pixel(9, 12), Some(WHITE));
+        assert_eq!(core.render_target.get_pixel(9, 5), Some(NONE));
+        assert_eq!(core.render_target.get_pixel(16, 13), Some(NONE));
+        assert_eq!(core.render_target.get_pixel(2, 14), Some(NONE));
+    }
+
+    #[test]
+    fn test_fill_polygon_matches_rect() {
+        let mut polygon = create_core(20, 20);
+        let mut rect = create_core(20, 20);
+
+        polygon.fill_polygon(&[(3, 4), (13, 4), (13, 11), (3, 11)], WHITE);
+        rect.fill_rect(3, 4, 10, 7, WHITE);
+        assert_eq!(polygon.render_target.data, rect.render_target.data);
+    }
+
+    #[test]
+    fn test_scan_contours_nan() {
+        let mut spans = Vec::new();
+        scan_contours(&[&[(f32::NAN, 0.0), (4.0, 0.0), (0.0, 4.0)]], |x1, x2, y| spans.push((x1, x2, y)));
+        assert!(spans.len() <= 4);
+    }
+
+    #[test]
+    fn test_rect_rotated_without_angle() {
+        let mut rotated = create_core(20, 20);
+        let mut plain = create_core(20, 20);
+
+        rotated.fill_rect_rotated(3, 4, 10, 7, 0.0, (5.0, 3.5), WHITE);
+        plain.fill_rect(3, 4, 10, 7, WHITE);
+        rotated.draw_rect_rotated(3, 4, 10, 7, 0.0, (0.0, 0.0), RED);
+        plain.draw_rect(3, 4, 10, 7, RED);
+        assert_eq!(rotated.render_target.data, plain.render_target.data);
+    }
+
+    #[test]
+    fn test_fill_rect_rotated() {
+        let mut core = create_core(30, 30);
+
+        // 20x4 rect rotated 90 degrees around its center becomes a 4x20 rect
+        core.fill_rect_rotated(5, 13, 20, 4, std::f32::consts::FRAC_PI_2, (10.0, 2.0), WHITE);
+        assert_eq!(core.render_target.get_pixel(15, 6), Some(WHITE));
+        assert_eq!(core.render_target.get_pixel(15, 24), Some(WHITE));
+        assert_eq!(core.render_target.get_pixel(6, 15), Some(NONE));
+        assert_eq!(core.render_target.get_pixel(24, 15), Some(NONE));
+    }
+
+    #[test]
+    fn test_draw_round_rect() {
+        let mut core = create_core(30, 30);
+
+        core.draw_round_rect(2, 2, 20, 10, 4, WHITE);
+        assert_eq!(core.render_target.get_pixel(12, 2), Some(WHITE));
+        assert_eq!(core.render_target.get_pixel(22, 7), Some(WHITE));
+        assert_eq!(core.render_target.get_pixel(12, 12), Some(WHITE));
+        assert_eq!(core.render_target.get_pixel(2, 2), Some(NONE));
+        assert_eq!(core.render_target.get_pixel(22, 12), Some(NONE));
+    }
+
+    #[test]
+    fn test_fill_round_rect() {
+        let mut core = create_core(30, 30);
+
+        core.fill_round_rect(2, 2, 20, 10, 4, WHITE);
+        assert_eq!(core.render_target.get_pixel(12, 7), Some(WHITE));
+        assert_eq!(core.render_target.get_pixel(12, 2), Some(WHITE));
+        assert_eq!(core.render_target.get_pixel(21, 7), Some(WHITE));
+        assert_eq!(core.render_target.get_pixel(2, 2), Some(NONE));
+        assert_eq!(core.render_target.get_pixel(22, 7), Some(NONE));
+        assert_eq!(core.render_target.get_pixel(12, 12), Some(NONE));
+    }
+
+    #[test]
+    fn test_round_rect_radius_clamped() {
+        let mut clamped = create_core(30, 30);
+        let mut max = create_core(30, 30);
+
+        clamped.fill_round_rect(2, 2, 20, 10, 100, WHITE);
+        max.fill_round_rect(2, 2, 20, 10, 5, WHITE);
+        assert_eq!(clamped.render_target.data, max.render_target.data);
+    }
+
+    #[test]
+    fn test_flood_fill() {
+        let mut core = create_core(30, 30);
+
+        core.draw_rect(5, 5, 10, 10, WHITE);
+        core.flood_fill(10, 10, RED);
+        assert_eq!(core.render_target.get_pixel(6, 6), Some(RED));
+        assert_eq!(core.render_target.get_pixel(14, 14), Some(RED));
+        assert_eq!(core.render_target.get_pixel(5, 5), Some(WHITE));
+        assert_eq!(core.render_target.get_pixel(20, 20), Some(NONE));
+    }
+
+    #[test]
+    fn test_flood_fill_large_area() {
+        let mut core = create_core(1000, 1000);
+
+        core.flood_fill(500, 500, RED);
+        assert!(core.render_target.data.iter().all(|&p| p == RED.into()));
+    }
+
+    #[test]
+    fn test_fill_rect_gradient() {
+        let mut core = create_core(20, 20);
+
+        core.fill_rect_gradient(0, 0, 11, 5, BLACK, WHITE, GradientDirection::Horizontal);
+        assert_eq!(core.render_target.get_pixel(0, 4), Some(BLACK));
+        assert_eq!(core.render_target.get_pixel(5, 2), Some(Color::rgb(128, 128, 128)));
+        assert_eq!(core.render_target.get_pixel(10, 0), Some(WHITE));
+
+        core.fill_rect_gradient(0, 10, 5, 3, RED, BLUE, GradientDirection::Vertical);
+        assert_eq!(core.render_target.get_pixel(4, 10), Some(RED));
+        assert_eq!(core.render_target.get_pixel(0, 12), Some(BLUE));
+
+        // clipped to the screen, the gradient still starts at the edge of the rectangle
+        core.clear(NONE);
+        core.fill_rect_gradient(-10, -5, 31, i32::MAX, BLACK, WHITE, GradientDirection::Horizontal);
+        assert_eq!(core.render_target.get_pixel(5, 19), Some(Color::rgb(128, 128, 128)));
+        assert_eq!(core.render_target.get_pixel(19, 0), Some(Color::rgb(247, 247, 247)));
+        core.fill_rect_gradient(i32::MAX - 1, i32::MIN, i32::MAX, i32::MAX, RED, BLUE, GradientDirection::Vertical);
+    }
+
+    #[test]
+    fn test_fill_circle_gradient() {
+        let mut core = create_core(30, 30);
+
+        core.fill_circle_gradient(15, 15, 10, WHITE, BLACK);
+        assert_eq!(core.render_target.get_pixel(15, 15), Some(WHITE));
+        assert_eq!(core.render_target.get_pixel(25, 15), Some(BLACK));
+        assert_eq!(core.render_target.get_pixel(20, 15), Some(Color::rgb(128, 128, 128)));
+        assert_eq!(core.render_target.get_pixel(26, 15), Some(NONE));
+    }
+
+    #[test]
+    fn test_draw_translucent() {
+        let mut core = create_core(10, 10);
+        core.clear(WHITE);
+
+        core.fill_rect(0, 0, 5, 5, Color::rgba(255, 0, 0, 128));
+        assert_eq!(core.render_target.get_pixel(2, 2), Some(Color::rgb(255, 127, 127)));
+        assert_eq!(core.render_target.get_pixel(7, 7), Some(WHITE));
+    }
+
+    #[test]
+    fn test_set_blend_mode() {
+        let mut core = create_core(10, 10);
+        core.clear(Color::rgb(100, 0, 0));
+
+        core.set_blend_mode(BlendMode::Additive);
+        assert_eq!(core.get_blend_mode(), BlendMode::Additive);
+        core.fill_rect(0, 0, 5, 5, Color::rgb(100, 50, 0));
+        core.fill_rect(0, 0, 2, 2, Color::rgb(100, 50, 0));
+        assert_eq!(core.render_target.get_pixel(3, 3), Some(Color::rgb(200, 50, 0)));
+        assert_eq!(core.render_target.get_pixel(1, 1), Some(Color::rgb(255, 100, 0)));
+    }
+
+    #[test]
+    fn test_fill_rect_pattern() {
+        let mut core = create_core(20, 20);
+
+        core.fill_rect_pattern(2, 2, 10, 10, &FillPattern::Mask(CHECKERBOARD, WHITE));
+        assert_eq!(core.render_target.get_pixel(2, 2), Some(WHITE));
+        assert_eq!(core.render_target.get_pixel(3, 2), Some(NONE));
+        assert_eq!(core.render_target.get_pixel(3, 3), Some(WHITE));
+        assert_eq!(core.render_target.get_pixel(12, 12), Some(NONE));
+
+        core.set_clip_rect(Some(Rect::new(0, 0, 3, 3)));
+        core.fill_rect_pattern(-100, -100, i32::MAX, i32::MAX, &FillPattern::Mask(CHECKERBOARD, RED));
+        assert_eq!(core.render_target.get_pixel(0, 0), Some(RED));
+        assert_eq!(core.render_target.get_pixel(3, 3), Some(WHITE));
+    }
+
+    #[test]
+    fn test_fill_circle_pattern() {
+        let mut tile = RenderTarget::new(1, 2);
+        tile.set_pixel(0, 0, RED);
+        tile.set_pixel(0, 1, BLUE);
+
+        let mut core = create_core(20, 20);
+        core.fill_circle_pattern(10, 10, 5, &FillPattern::Tile(&tile));
+        assert_eq!(core.render_target.get_pixel(10, 10), Some(RED));
+        assert_eq!(core.render_target.get_pixel(10, 11), Some(BLUE));
+        assert_eq!(core.render_target.get_pixel(15, 15), Some(NONE));
+    }
+
+    #[test]
+    fn test_regular_polygon() {
+        let mut core = create_core(30, 30);
+
+        // square rotated by 45 degrees
+        core.draw_regular_polygon(15, 15, 10, 4, 0.0, WHITE);
+        assert_eq!(core.render_target.get_pixel(25, 15), Some(WHITE));
+        assert_eq!(core.render_target.get_pixel(15, 25), Some(WHITE));
+        assert_eq!(core.render_target.get_pixel(20, 10), Some(WHITE));
+        assert_eq!(core.render_target.get_pixel(15, 15), Some(NONE));
+
+        core.fill_regular_polygon(15, 15, 10, 4, 0.0, RED);
+        assert_eq!(core.render_target.get_pixel(15, 15), Some(RED));
+        assert_eq!(core.render_target.get_pixel(22, 8), Some(NONE));
+    }
+
+    #[test]
+    fn test_star() {
+        let mut core = create_core(50, 50);
+
+        core.fill_star(25, 25, 20, 5, 4, 0.0, WHITE);
+        assert_eq!(core.render_target.get_pixel(25, 25), Some(WHITE));
+        assert_eq!(core.render_target.get_pixel(42, 25), Some(WHITE));
+        assert_eq!(core.render_target.get_pixel(25, 42), Some(WHITE));
+        // between the tips
+        assert_eq!(core.render_target.get_pixel(35, 35), Some(NONE));
+    }
+
+    #[test]
+    fn test_fill_polygon_pattern() {
+        let mut pattern = create_core(20, 20);
+        let mut rect = create_core(20, 20);
+
+        let fill = FillPattern::Mask(HATCH_HORIZONTAL, WHITE);
+        pattern.fill_polygon_pattern(&[(3, 4), (13, 4), (13, 11), (3, 11)], &fill);
+        rect.fill_rect_pattern(3, 4, 10, 7, &fill);
+        assert_eq!(pattern.render_target.data, rect.render_target.data);
+    }
+
+    #[test]
+    fn test_fill_ring() {
+        let mut core = create_core(30, 30);
+
+        core.fill_ring(15, 15, 5, 10, WHITE);
+        assert_eq!(core.render_target.get_pixel(15, 15), Some(NONE));
+        assert_eq!(core.render_target.get_pixel(19, 15), Some(NONE));
+        assert_eq!(core.render_target.get_pixel(20, 15), Some(WHITE));
+        assert_eq!(core.render_target.get_pixel(25, 15), Some(WHITE));
+        assert_eq!(core.render_target.get_pixel(15, 7), Some(WHITE));
+        assert_eq!(core.render_target.get_pixel(26, 15), Some(NONE));
+    }
+
+    #[test]
+    fn test_fill_ring_counts_each_pixel_once() {
+        let mut core = create_core(30, 30);
+
+        // every pixel is written once, so translucent rings have a uniform color
+        core.fill_ring(15, 15, 3, 10, Color::rgba(255, 255, 255, 128));
+        for p in core.render_target.data.iter().filter(|&&p| p != 0) {
+            assert_eq!(Color::from(*p), Color::rgba(128, 128, 128, 128));
+        }
+    }
+
+    #[test]
+    fn test_draw_ellipse() {
+        let mut core = create_core(20, 20);
+
+        core.draw_ellipse(10, 10, 6, 3, WHITE);
+        assert_eq!(core.render_target.get_pixel(16, 10), Some(WHITE));
+        assert_eq!(core.render_target.get_pixel(4, 10), Some(WHITE));
+        assert_eq!(core.render_target.get_pixel(10, 13), Some(WHITE));
+        assert_eq!(core.render_target.get_pixel(10, 7), Some(WHITE));
+        assert_eq!(core.render_target.get_pixel(10, 10), Some(NONE));
+    }
+
+    #[test]
+    fn test_fill_ellipse() {
+        let mut core = create_core(20, 20);
+
+        core.fill_ellipse(10, 10, 6, 3, WHITE);
+        assert_eq!(core.render_target.get_pixel(10, 10), Some(WHITE));
+        assert_eq!(core.render_target.get_pixel(16, 10), Some(WHITE));
+        assert_eq!(core.render_target.get_pixel(10, 14), Some(NONE));
+        assert_eq!(core.render_target.get_pixel(17, 10), Some(NONE));
+    }
+
+    #[test]
+    fn test_translate() {
+        let mut core = create_core(20, 20);
+
+        core.translate(5.0, 3.0);
+        core.draw(0, 0, WHITE);
+        core.fill_rect(0, 2, 2, 2, RED);
+        assert_eq!(core.render_target.get_pixel(5, 3), Some(WHITE));
+        assert_eq!(core.render_target.get_pixel(5, 5), Some(RED));
+        assert_eq!(core.render_target.get_pixel(6, 6), Some(RED));
+        assert_eq!(core.render_target.get_pixel(7, 7), Some(NONE));
+        assert_eq!(core.render_target.get_pixel(0, 2), Some(NONE));
+    }
+
+    #[test]
+    fn test_push_pop_transform() {
+        let mut core = create_core(20, 20);
+
+        core.translate(2.0, 0.0);
+        core.push_transform();
+        core.translate(3.0, 0.0);
+        core.draw(0, 0, WHITE);
+        core.pop_transform();
+        core.draw(0, 1, WHITE);
+        assert_eq!(core.render_target.get_pixel(5, 0), Some(WHITE));
+        assert_eq!(core.render_target.get_pixel(2, 1), Some(WHITE));
+
+        // popping an empty stack resets the transform
+        core.pop_transform();
+        assert!(core.get_transform().is_identity());
+    }
+
+    #[test]
+    fn test_rotate() {
+        let mut core = create_core(20, 20);
+
+        core.translate(10.0, 10.0);
+        core.rotate(std::f32::consts::FRAC_PI_2);
+        core.fill_rect(0, -1, 5, 2, WHITE);
+
+        // the horizontal bar now points down
+        assert_eq!(core.render_target.get_pixel(9, 12), Some(WHITE));
+        assert_eq!(core.render_target.get_pixel(10, 14), Some(WHITE));
+        assert_eq!(core.render_target.get_pixel(12, 10), Some(NONE));
+        assert_eq!(core.render_target.get_pixel(10, 16), Some(NONE));
+    }
+
+    #[test]
+    fn test_scale() {
+        let mut core = create_core(30, 30);
+
+        core.scale(2.0, 2.0);
+        core.fill_circle(5, 5, 3, WHITE);
+        assert_eq!(core.render_target.get_pixel(10, 10), Some(WHITE));
+        assert_eq!(core.render_target.get_pixel(10, 4), Some(WHITE));
+        assert_eq!(core.render_target.get_pixel(15, 10), Some(WHITE));
+        assert_eq!(core.render_target.get_pixel(17, 10), Some(NONE));
+
+        core.reset_transform();
+        core.draw(1, 1, RED);
+        assert_eq!(core.render_target.get_pixel(1, 1), Some(RED));
     }
 }
@@ -0,0 +1,206 @@
+use crate::core::{Color, Sprite, BLACK, NONE};
+
+#[derive(Debug, PartialEq, Clone)]
+/// Up to 256 colors that palette indices are mapped to when the screen is shown, set it with
+/// [`set_palette`]
+///
+/// While a palette is set everything is drawn with [`Palette::index`] instead of real colors,
+/// changing the palette changes every pixel that uses the index
+///
+/// [`set_palette`]: struct.RainCore.html#method.set_palette
+/// [`Palette::index`]: #method.index
+///
+/// ### Example
+/// ```
+///# use rain2d::core::*;
+/// let mut palette = Palette::new(&[BLACK, Color::rgb(255, 0, 77), WHITE]);
+/// assert_eq!(palette.get(1), Color::rgb(255, 0, 77));
+/// assert_eq!(palette.nearest(Color::rgb(200, 10, 60)), 1);
+///
+/// palette.set(1, Color::rgb(0, 228, 54));
+/// assert_eq!(palette.get(1), Color::rgb(0, 228, 54));
+/// ```
+pub struct Palette {
+    colors: Vec<Color>,
+    // average distance between neighbouring colors, used as the range of dithering thresholds
+    spread: f32,
+}
+
+impl Palette {
+    /// Creates a palette from the colors, colors after the first 256 are ignored
+    pub fn new(colors: &[Color]) -> Self {
+        let mut palette = Palette { colors: colors.iter().take(256).copied().collect(), spread: 0.0 };
+        palette.update_spread();
+        palette
+    }
+
+    /// Gets the color to draw with to write `index`, the index is stored in the blue channel
+    pub fn index(index: u8) -> Color {
+        Color::rgba(0, 0, index, 255)
+    }
+
+    /// Gets the color at `index`, indices without a color are black
+    pub fn get(&self, index: u8) -> Color {
+        self.colors.get(index as usize).copied().unwrap_or(BLACK)
+    }
+
+    /// Replaces the color at `index`, the palette grows with black colors if needed
+    pub fn set(&mut self, index: u8, color: Color) {
+        let i = index as usize;
+        if i >= self.colors.len() {
+            self.colors.resize(i + 1, BLACK);
+        }
+        self.colors[i] = color;
+        self.update_spread();
+    }
+
+    /// Gets the colors in the palette
+    pub fn colors(&self) -> &[Color] {
+        &self.colors
+    }
+
+    /// Gets the index of the color closest to `color`, alpha is ignored
+    pub fn nearest(&self, color: Color) -> u8 {
+        let distance = |c: &Color| {
+            let (dr, dg, db) = (c.r as i32 - color.r as i32, c.g as i32 - color.g as i32, c.b as i32 - color.b as i32);
+            dr * dr + dg * dg + db * db
+        };
+        self.colors.iter()
+            .enumerate()
+            .min_by_key(|(_, c)| distance(c))
+            .map_or(0, |(i, _)| i as u8)
+    }
+
+    /// Gets the palette color for the pixel at `(x, y)` using ordered dithering, areas between two
+    /// palette colors become a pattern of both
+    ///
+    /// ### Example
+    /// ```
+    ///# use rain2d::core::*;
+    /// let palette = Palette::new(&[BLACK, WHITE]);
+    /// let gray = Color::rgb(128, 128, 128);
+    /// let white = (0..4).flat_map(|y| (0..4).map(move |x| (x, y)))
+    ///     .filter(|&(x, y)| palette.dither(x, y, gray) == WHITE)
+    ///     .count();
+    /// assert_eq!(white, 8);
+    /// ```
+    pub fn dither(&self, x: i32, y: i32, color: Color) -> Color {
+        let offset = (bayer(x, y) - 0.5) * self.spread;
+        let channel = |c: u8| (c as f32 + offset).round().clamp(0.0, 255.0) as u8;
+        let shifted = Color::rgba(channel(color.r), channel(color.g), channel(color.b), color.a);
+        let mut dithered = self.get(self.nearest(shifted));
+        dithered.a = color.a;
+        dithered
+    }
+
+    /// Converts the sprite to the indices of the nearest colors so it can be drawn while the
+    /// palette is set, transparent pixels stay transparent
+    pub fn index_sprite(&self, sprite: &Sprite) -> Sprite {
+        let data = sprite.data.iter()
+            .map(|&p| {
+                let color = Color::from(p);
+                if color.a == 0 || sprite.is_keyed(p) { NONE } else { Palette::index(self.nearest(color)) }
+            })
+            .map(u32::from)
+            .collect();
+        Sprite { width: sprite.width, height: sprite.height, data, color_key: None, mips: Vec::new() }
+    }
+
+    // the distance between two colors is the largest difference of a channel so the spread
+    // matches the step between levels of gray or of every channel in color cubes
+    fn update_spread(&mut self) {
+        let distance = |a: &Color, b: &Color| {
+            (a.r as i32 - b.r as i32).abs().max((a.g as i32 - b.g as i32).abs()).max((a.b as i32 - b.b as i32).abs())
+        };
+        let nearest = self.colors.iter().enumerate().filter_map(|(i, a)| {
+            self.colors.iter().enumerate()
+                .filter(|&(j, b)| j != i && b != a)
+                .map(|(_, b)| distance(a, b))
+                .min()
+        });
+        let (sum, count) = nearest.fold((0, 0), |(sum, count), d| (sum + d, count + 1));
+        self.spread = if count == 0 { 0.0 } else { sum as f32 / count as f32 };
+    }
+
+    // maps an index color to its palette color
+    pub(crate) fn map(&self, color: Color) -> Color {
+        self.get(color.b)
+    }
+}
+
+const BAYER: [[u8; 4]; 4] = [
+    [0, 8, 2, 10],
+    [12, 4, 14, 6],
+    [3, 11, 1, 9],
+    [15, 7, 13, 5],
+];
+
+/// Gets the threshold between 0.0 and 1.0 of the 4x4 Bayer matrix at `(x, y)`, the matrix repeats
+/// every 4 pixels
+///
+/// Comparing a value with the threshold gives a regular pattern where the share of pixels above
+/// it matches the value, see [`Palette::dither`](struct.Palette.html#method.dither)
+pub fn bayer(x: i32, y: i32) -> f32 {
+    (BAYER[y.rem_euclid(4) as usize][x.rem_euclid(4) as usize] as f32 + 0.5) / 16.0
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::core::color::{WHITE, RED, MAGENTA};
+
+    #[test]
+    fn test_palette() {
+        let mut palette = Palette::new(&[BLACK, WHITE]);
+        assert_eq!(palette.get(1), WHITE);
+        assert_eq!(palette.get(200), BLACK);
+        palette.set(3, RED);
+        assert_eq!(palette.colors(), &[BLACK, WHITE, BLACK, RED]);
+        assert_eq!(palette.map(Palette::index(3)), RED);
+        assert_eq!(Palette::new(&[WHITE; 300]).colors().len(), 256);
+    }
+
+    #[test]
+    fn test_nearest() {
+        let palette = Palette::new(&[BLACK, WHITE, RED]);
+        assert_eq!(palette.nearest(Color::rgb(40, 30, 30)), 0);
+        assert_eq!(palette.nearest(Color::rgb(200, 200, 220)), 1);
+        assert_eq!(palette.nearest(Color::rgb(160, 60, 40)), 2);
+        assert_eq!(Palette::new(&[]).nearest(RED), 0);
+    }
+
+    #[test]
+    fn test_bayer() {
+        let mut thresholds: Vec<f32> = (0..16).map(|i| bayer(i % 4, i / 4)).collect();
+        assert_eq!(bayer(-1, -4), bayer(3, 0));
+        thresholds.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert!(thresholds.windows(2).all(|t| (t[1] - t[0] - 1.0 / 16.0).abs() < 1e-6));
+    }
+
+    #[test]
+    fn test_dither() {
+        let palette = Palette::new(&[BLACK, WHITE]);
+        let count = |color: Color| (0..16).filter(|&i| palette.dither(i % 4, i / 4, color) == WHITE).count();
+        assert_eq!(count(BLACK), 0);
+        assert_eq!(count(WHITE), 16);
+        assert_eq!(count(Color::rgb(64, 64, 64)), 4);
+        assert_eq!(palette.dither(0, 0, Color::rgba(255, 255, 255, 100)).a, 100);
+
+        // 4 levels of gray dither between the two closest levels
+        let grays = Palette::new(&[BLACK, Color::rgb(85, 85, 85), Color::rgb(170, 170, 170), WHITE]);
+        let light = (0..16).filter(|&i| grays.dither(i % 4, i / 4, Color::rgb(128, 128, 128)) == grays.get(2)).count();
+        assert_eq!(light, 8);
+        assert!((0..16).all(|i| grays.dither(i % 4, i / 4, Color::rgb(128, 128, 128)).r >= 85));
+    }
+
+    #[test]
+    fn test_index_sprite() {
+        let mut sprite = Sprite::from_colors(3, 1, &[WHITE, MAGENTA, NONE]).unwrap();
+        sprite.color_key = Some(MAGENTA);
+        let palette = Palette::new(&[BLACK, WHITE]);
+        let indexed = palette.index_sprite(&sprite);
+        assert_eq!(indexed.get_pixel(0, 0), Some(Palette::index(1)));
+        assert_eq!(indexed.get_pixel(1, 0), Some(NONE));
+        assert_eq!(indexed.get_pixel(2, 0), Some(NONE));
+    }
+}
@@ -0,0 +1,143 @@
+use crate::core::curve::*;
+
+#[derive(Debug, PartialEq, Clone, Default)]
+struct SubPath {
+    points: Vec<Point>,
+    closed: bool,
+}
+
+#[derive(Debug, PartialEq, Clone, Default)]
+/// Shape made up of lines and curves that can be drawn with
+/// [`stroke_path`] or [`fill_path`]
+///
+/// [`stroke_path`]: struct.RainCore.html#method.stroke_path
+/// [`fill_path`]: struct.RainCore.html#method.fill_path
+///
+/// ### Example
+/// ```
+///# use rain2d::core::Path;
+/// let mut path = Path::new();
+/// path.move_to(10.0, 10.0)
+///     .line_to(50.0, 10.0)
+///     .quad_to(70.0, 30.0, 50.0, 50.0)
+///     .line_to(10.0, 50.0)
+///     .close();
+/// ```
+pub struct Path {
+    subpaths: Vec<SubPath>,
+}
+
+impl Path {
+    /// Creates an empty path
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Starts a new subpath at `(x, y)`
+    pub fn move_to(&mut self, x: f32, y: f32) -> &mut Self {
+        self.subpaths.push(SubPath { points: vec![(x, y)], closed: false });
+        self
+    }
+
+    /// Adds a line from the current point to `(x, y)`
+    pub fn line_to(&mut self, x: f32, y: f32) -> &mut Self {
+        self.current().points.push((x, y));
+        self
+    }
+
+    /// Adds a quadratic bezier curve from the current point to `(x, y)` with control point `(cx, cy)`
+    pub fn quad_to(&mut self, cx: f32, cy: f32, x: f32, y: f32) -> &mut Self {
+        let sub = self.current();
+        let start = *sub.points.last().unwrap_or(&(cx, cy));
+        flatten_quad(start, (cx, cy), (x, y), &mut sub.points);
+        self
+    }
+
+    /// Adds a cubic bezier curve from the current point to `(x, y)` with control points
+    /// `(c1x, c1y)` and `(c2x, c2y)`
+    pub fn cubic_to(&mut self, c1x: f32, c1y: f32, c2x: f32, c2y: f32, x: f32, y: f32) -> &mut Self {
+        let sub = self.current();
+        let start = *sub.points.last().unwrap_or(&(c1x, c1y));
+        flatten_cubic(start, (c1x, c1y), (c2x, c2y), (x, y), &mut sub.points);
+        self
+    }
+
+    /// Closes the current subpath by connecting the current point to its starting point,
+    /// following commands start a new subpath at the same point
+    pub fn close(&mut self) -> &mut Self {
+        if let Some(sub) = self.subpaths.last_mut() {
+            if !sub.closed {
+                sub.closed = true;
+                let start = sub.points[0];
+                self.subpaths.push(SubPath { points: vec![start], closed: false });
+            }
+        }
+        self
+    }
+
+    /// Checks if the path contains no lines or curves
+    pub fn is_empty(&self) -> bool {
+        self.subpaths.iter().all(|sub| sub.points.len() < 2)
+    }
+
+    // flattened subpaths with at least one segment, together with whether they are closed
+    pub(crate) fn contours(&self) -> impl Iterator<Item = (&[Point], bool)> {
+        self.subpaths.iter()
+            .filter(|sub| sub.points.len() > 1)
+            .map(|sub| (sub.points.as_slice(), sub.closed))
+    }
+
+    // gets the subpath commands are added to, lines without a starting point start at the origin
+    fn current(&mut self) -> &mut SubPath {
+        if self.subpaths.last().is_none_or(|sub| sub.closed) {
+            self.move_to(0.0, 0.0);
+        }
+        self.subpaths.last_mut().unwrap()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_lines() {
+        let mut path = Path::new();
+        path.move_to(1.0, 2.0).line_to(3.0, 4.0).line_to(5.0, 6.0);
+
+        let contours: Vec<_> = path.contours().collect();
+        assert_eq!(contours, vec![(&[(1.0, 2.0), (3.0, 4.0), (5.0, 6.0)][..], false)]);
+    }
+
+    #[test]
+    fn test_close_starts_new_subpath() {
+        let mut path = Path::new();
+        path.move_to(0.0, 0.0).line_to(10.0, 0.0).line_to(10.0, 10.0).close();
+        path.line_to(0.0, 10.0);
+
+        let contours: Vec<_> = path.contours().collect();
+        assert_eq!(contours.len(), 2);
+        assert!(contours[0].1);
+        assert_eq!(contours[1].0, &[(0.0, 0.0), (0.0, 10.0)][..]);
+    }
+
+    #[test]
+    fn test_curves_are_flattened() {
+        let mut path = Path::new();
+        path.move_to(0.0, 0.0).quad_to(50.0, 100.0, 100.0, 0.0);
+
+        let (points, _) = path.contours().next().unwrap();
+        assert!(points.len() > 3);
+        assert_eq!(points.last(), Some(&(100.0, 0.0)));
+    }
+
+    #[test]
+    fn test_is_empty() {
+        let mut path = Path::new();
+        assert!(path.is_empty());
+        path.move_to(5.0, 5.0);
+        assert!(path.is_empty());
+        path.line_to(10.0, 5.0);
+        assert!(!path.is_empty());
+    }
+}
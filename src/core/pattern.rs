@@ -0,0 +1,83 @@
+use crate::core::{Color, RenderTarget};
+
+/// 8x8 checkerboard mask
+pub const CHECKERBOARD: [u8; 8] = [0xAA, 0x55, 0xAA, 0x55, 0xAA, 0x55, 0xAA, 0x55];
+
+/// 8x8 mask with horizontal lines
+pub const HATCH_HORIZONTAL: [u8; 8] = [0xFF, 0x00, 0x00, 0x00, 0xFF, 0x00, 0x00, 0x00];
+
+/// 8x8 mask with vertical lines
+pub const HATCH_VERTICAL: [u8; 8] = [0x88; 8];
+
+/// 8x8 mask with diagonal lines
+pub const HATCH_DIAGONAL: [u8; 8] = [0x80, 0x40, 0x20, 0x10, 0x08, 0x04, 0x02, 0x01];
+
+/// 8x8 mask with crossing diagonal lines
+pub const CROSSHATCH: [u8; 8] = [0x81, 0x42, 0x24, 0x18, 0x18, 0x24, 0x42, 0x81];
+
+/// Repeating pattern used by the pattern fill functions
+///
+/// Patterns are anchored at the upper left corner of the screen,
+/// so shapes drawn next to each other line up
+///
+/// ### Example
+/// ```
+///# use rain2d::core::*;
+/// let pattern = FillPattern::Mask(CHECKERBOARD, WHITE);
+/// assert_eq!(pattern.color_at(0, 0), Some(WHITE));
+/// assert_eq!(pattern.color_at(1, 0), None);
+/// ```
+pub enum FillPattern<'a> {
+    /// Repeats the render target
+    Tile(&'a RenderTarget),
+
+    /// Repeats an 8x8 bitmask, each byte is a row with the leftmost pixel in the most
+    /// significant bit, set bits are drawn with the color and unset bits are skipped
+    Mask([u8; 8], Color),
+}
+
+impl<'a> FillPattern<'a> {
+    /// Gets the color of the pattern at `(x, y)`, returns `None` if nothing should be drawn
+    pub fn color_at(&self, x: i32, y: i32) -> Option<Color> {
+        match self {
+            FillPattern::Tile(tile) => {
+                if tile.width == 0 || tile.height == 0 { return None; }
+                let tx = x.rem_euclid(tile.width as i32);
+                let ty = y.rem_euclid(tile.height as i32);
+                tile.get_pixel(tx, ty)
+            }
+            FillPattern::Mask(mask, color) => {
+                let row = mask[y.rem_euclid(8) as usize];
+                if row & (0x80 >> x.rem_euclid(8)) != 0 { Some(*color) } else { None }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::core::color::{RED, BLUE, WHITE};
+
+    #[test]
+    fn test_mask() {
+        let pattern = FillPattern::Mask(HATCH_DIAGONAL, WHITE);
+        assert_eq!(pattern.color_at(0, 0), Some(WHITE));
+        assert_eq!(pattern.color_at(3, 3), Some(WHITE));
+        assert_eq!(pattern.color_at(11, 3), Some(WHITE));
+        assert_eq!(pattern.color_at(-5, 3), Some(WHITE));
+        assert_eq!(pattern.color_at(4, 3), None);
+    }
+
+    #[test]
+    fn test_tile() {
+        let mut tile = RenderTarget::new(2, 1);
+        tile.set_pixel(0, 0, RED);
+        tile.set_pixel(1, 0, BLUE);
+
+        let pattern = FillPattern::Tile(&tile);
+        assert_eq!(pattern.color_at(0, 5), Some(RED));
+        assert_eq!(pattern.color_at(3, 0), Some(BLUE));
+        assert_eq!(pattern.color_at(-1, 0), Some(BLUE));
+    }
+}
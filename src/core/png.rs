@@ -0,0 +1,262 @@
+// png decoder supporting all non-interlaced color types and bit depths
+
+use crate::core::inflate::zlib_decompress;
+use crate::core::{Color, ImageError, Sprite};
+
+const SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A];
+
+const INVALID: ImageError = ImageError::Invalid("corrupt png file");
+
+pub fn decode(bytes: &[u8]) -> Result<Sprite, ImageError> {
+    if !bytes.starts_with(&SIGNATURE) {
+        return Err(ImageError::Invalid("not a png file"));
+    }
+
+    let mut header = None;
+    let mut palette: Vec<Color> = Vec::new();
+    let mut transparency: Option<&[u8]> = None;
+    let mut compressed = Vec::new();
+
+    let mut pos = SIGNATURE.len();
+    while pos + 8 <= bytes.len() {
+        let len = u32::from_be_bytes([bytes[pos], bytes[pos + 1], bytes[pos + 2], bytes[pos + 3]]) as usize;
+        let kind = &bytes[pos + 4..pos + 8];
+        let data = bytes.get(pos + 8..pos + 8 + len).ok_or(INVALID)?;
+        // skip the chunk and its crc
+        pos += 12 + len;
+
+        match kind {
+            b"IHDR" => header = Some(Header::parse(data)?),
+            b"PLTE" => palette = data.chunks_exact(3).map(|c| Color::rgb(c[0], c[1], c[2])).collect(),
+            b"tRNS" => transparency = Some(data),
+            b"IDAT" => compressed.extend_from_slice(data),
+            b"IEND" => break,
+            _ => (),
+        }
+    }
+
+    let header = header.ok_or(INVALID)?;
+    if let Some(alpha) = transparency.filter(|_| header.color_type == 3) {
+        for (color, &a) in palette.iter_mut().zip(alpha) {
+            color.a = a;
+        }
+    }
+
+    let data = zlib_decompress(&compressed)?;
+    let rows = unfilter(&header, &data)?;
+
+    // transparent color for grayscale and rgb images, compared with the raw samples
+    let key: Option<Vec<u16>> = transparency.filter(|_| header.color_type == 0 || header.color_type == 2)
+        .map(|t| t.chunks_exact(2).map(|c| u16::from_be_bytes([c[0], c[1]])).collect());
+
+    let mut sprite = Sprite::new(header.width, header.height);
+    let mut samples = Vec::with_capacity(4);
+    for (y, row) in rows.chunks_exact(header.stride()).enumerate() {
+        for x in 0..header.width {
+            samples.clear();
+            samples.extend((0..header.channels()).map(|c| header.sample(row, x * header.channels() + c)));
+
+            let color = match header.color_type {
+                0 => { let v = header.scale(samples[0]); Color::rgb(v, v, v) }
+                2 => Color::rgb(header.scale(samples[0]), header.scale(samples[1]), header.scale(samples[2])),
+                3 => *palette.get(samples[0] as usize).ok_or(INVALID)?,
+                4 => { let v = header.scale(samples[0]); Color::rgba(v, v, v, header.scale(samples[1])) }
+                _ => Color::rgba(
+                    header.scale(samples[0]), header.scale(samples[1]),
+                    header.scale(samples[2]), header.scale(samples[3]),
+                ),
+            };
+            let transparent = key.as_ref().is_some_and(|key| *key == samples);
+            sprite.set_pixel(x as i32, y as i32, if transparent { Color { a: 0, ..color } } else { color });
+        }
+    }
+
+    Ok(sprite)
+}
+
+struct Header {
+    width: usize,
+    height: usize,
+    bit_depth: u8,
+    color_type: u8,
+}
+
+impl Header {
+    fn parse(data: &[u8]) -> Result<Self, ImageError> {
+        if data.len() < 13 { return Err(INVALID); }
+        let width = u32::from_be_bytes([data[0], data[1], data[2], data[3]]) as usize;
+        let height = u32::from_be_bytes([data[4], data[5], data[6], data[7]]) as usize;
+        let (bit_depth, color_type) = (data[8], data[9]);
+
+        let valid = match color_type {
+            0 => [1, 2, 4, 8, 16].contains(&bit_depth),
+            3 => [1, 2, 4, 8].contains(&bit_depth),
+            2 | 4 | 6 => [8, 16].contains(&bit_depth),
+            _ => false,
+        };
+        if !valid || width == 0 || height == 0 || data[10] != 0 || data[11] != 0 {
+            return Err(INVALID);
+        }
+        if data[12] != 0 {
+            return Err(ImageError::Unsupported("interlaced png"));
+        }
+
+        Ok(Header { width, height, bit_depth, color_type })
+    }
+
+    fn channels(&self) -> usize {
+        match self.color_type {
+            2 => 3,
+            4 => 2,
+            6 => 4,
+            _ => 1,
+        }
+    }
+
+    // bytes per row, without the filter type
+    fn stride(&self) -> usize {
+        (self.width * self.channels() * self.bit_depth as usize).div_ceil(8)
+    }
+
+    // distance to the corresponding byte of the previous pixel used by the filters
+    fn bytes_per_pixel(&self) -> usize {
+        (self.channels() * self.bit_depth as usize).div_ceil(8)
+    }
+
+    // raw value of the sample at index i in the row
+    fn sample(&self, row: &[u8], i: usize) -> u16 {
+        match self.bit_depth {
+            16 => u16::from_be_bytes([row[i * 2], row[i * 2 + 1]]),
+            8 => row[i] as u16,
+            depth => {
+                let per_byte = 8 / depth as usize;
+                let shift = 8 - depth as usize * (i % per_byte + 1);
+                ((row[i / per_byte] >> shift) & ((1 << depth) - 1)) as u16
+            }
+        }
+    }
+
+    // converts a sample to 8 bits
+    fn scale(&self, value: u16) -> u8 {
+        match self.bit_depth {
+            16 => (value >> 8) as u8,
+            depth => (value as u32 * 255 / ((1 << depth) - 1)) as u8,
+        }
+    }
+}
+
+// undoes the per row filters, returns the rows without their filter type
+fn unfilter(header: &Header, data: &[u8]) -> Result<Vec<u8>, ImageError> {
+    let (stride, bpp) = (header.stride(), header.bytes_per_pixel());
+    if (stride + 1).checked_mul(header.height).is_none_or(|len| data.len() < len) {
+        return Err(INVALID);
+    }
+
+    let mut rows = vec![0u8; stride * header.height];
+    for y in 0..header.height {
+        let filter = data[y * (stride + 1)];
+        let src = &data[y * (stride + 1) + 1..(y + 1) * (stride + 1)];
+        let (prev, cur) = rows.split_at_mut(y * stride);
+        let prev = if y > 0 { &prev[(y - 1) * stride..] } else { &[][..] };
+        let cur = &mut cur[..stride];
+
+        for i in 0..stride {
+            let a = if i >= bpp { cur[i - bpp] } else { 0 };
+            let b = prev.get(i).copied().unwrap_or(0);
+            let c = if i >= bpp { prev.get(i - bpp).copied().unwrap_or(0) } else { 0 };
+            cur[i] = src[i].wrapping_add(match filter {
+                0 => 0,
+                1 => a,
+                2 => b,
+                3 => ((a as u16 + b as u16) / 2) as u8,
+                4 => paeth(a, b, c),
+                _ => return Err(INVALID),
+            });
+        }
+    }
+    Ok(rows)
+}
+
+fn paeth(a: u8, b: u8, c: u8) -> u8 {
+    let p = a as i16 + b as i16 - c as i16;
+    let (pa, pb, pc) = ((p - a as i16).abs(), (p - b as i16).abs(), (p - c as i16).abs());
+    if pa <= pb && pa <= pc { a } else if pb <= pc { b } else { c }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::core::color::{RED, GREEN, BLUE, NONE};
+
+    // builds a png with a single stored deflate block
+    fn encode(width: u32, height: u32, bit_depth: u8, color_type: u8, extra: &[(&[u8; 4], &[u8])], raw: &[u8]) -> Vec<u8> {
+        fn chunk(out: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+            out.extend_from_slice(&(data.len() as u32).to_be_bytes());
+            out.extend_from_slice(kind);
+            out.extend_from_slice(data);
+            out.extend_from_slice(&[0; 4]);
+        }
+
+        let mut out = SIGNATURE.to_vec();
+        let mut ihdr = Vec::new();
+        ihdr.extend_from_slice(&width.to_be_bytes());
+        ihdr.extend_from_slice(&height.to_be_bytes());
+        ihdr.extend_from_slice(&[bit_depth, color_type, 0, 0, 0]);
+        chunk(&mut out, b"IHDR", &ihdr);
+        for &(kind, data) in extra {
+            chunk(&mut out, kind, data);
+        }
+
+        let mut zlib = vec![0x78, 0x01, 0x01];
+        zlib.extend_from_slice(&(raw.len() as u16).to_le_bytes());
+        zlib.extend_from_slice(&(!(raw.len() as u16)).to_le_bytes());
+        zlib.extend_from_slice(raw);
+        chunk(&mut out, b"IDAT", &zlib);
+        chunk(&mut out, b"IEND", &[]);
+        out
+    }
+
+    #[test]
+    fn test_rgba() {
+        // two rows, the second one uses the up filter
+        let raw = [0, 255, 0, 0, 255, 0, 255, 0, 128, 2, 0, 0, 255, 0, 0, 0, 0, 0];
+        let sprite = decode(&encode(2, 2, 8, 6, &[], &raw)).unwrap();
+        assert_eq!((sprite.width, sprite.height), (2, 2));
+        assert_eq!(sprite.get_pixel(0, 0), Some(RED));
+        assert_eq!(sprite.get_pixel(1, 0), Some(Color::rgba(0, 255, 0, 128)));
+        assert_eq!(sprite.get_pixel(0, 1), Some(Color::rgb(255, 0, 255)));
+        assert_eq!(sprite.get_pixel(1, 1), Some(Color::rgba(0, 255, 0, 128)));
+    }
+
+    #[test]
+    fn test_palette() {
+        // 2 bit indices with transparency for the first entry
+        let palette = [0, 0, 0, 0, 0, 255, 0, 255, 0];
+        let raw = [0, 0b00_01_10_00];
+        let png = encode(3, 1, 2, 3, &[(b"PLTE", &palette), (b"tRNS", &[0])], &raw);
+        let sprite = decode(&png).unwrap();
+        assert_eq!(sprite.get_pixel(0, 0), Some(NONE));
+        assert_eq!(sprite.get_pixel(1, 0), Some(BLUE));
+        assert_eq!(sprite.get_pixel(2, 0), Some(GREEN));
+    }
+
+    #[test]
+    fn test_grayscale() {
+        // 16 bit with the sub filter
+        let raw = [1, 0x80, 0x00, 0x7F, 0xFF];
+        let sprite = decode(&encode(2, 1, 16, 0, &[], &raw)).unwrap();
+        assert_eq!(sprite.get_pixel(0, 0), Some(Color::rgb(128, 128, 128)));
+        assert_eq!(sprite.get_pixel(1, 0), Some(Color::rgb(255, 255, 255)));
+    }
+
+    #[test]
+    fn test_invalid() {
+        assert!(decode(b"not a png").is_err());
+        assert!(decode(&encode(2, 2, 8, 6, &[], &[0, 1, 2])).is_err());
+        assert!(decode(&encode(2, 2, 3, 2, &[], &[])).is_err());
+        // empty images and sizes that overflow
+        assert!(decode(&encode(0, 2, 8, 6, &[], &[0, 0])).is_err());
+        assert!(decode(&encode(2, 0, 8, 6, &[], &[])).is_err());
+        assert!(decode(&encode(u32::MAX, u32::MAX, 16, 6, &[], &[0])).is_err());
+    }
+}
@@ -0,0 +1,148 @@
+// anti-aliased polygon rasterizer that computes the exact area of every pixel covered by the
+// contours, used for text where scanline sampling looks too jagged
+
+use crate::core::curve::Point;
+
+// calls pixel for every pixel that is at least partially covered with its coverage between 0 and 1,
+// overlapping contours with the same winding direction count once
+pub fn coverage<F: FnMut(i32, i32, f32)>(contours: &[Vec<Point>], mut pixel: F) {
+    let points = contours.iter().flatten();
+    let (mut x_min, mut y_min, mut x_max, mut y_max) = (f32::MAX, f32::MAX, f32::MIN, f32::MIN);
+    for &(x, y) in points {
+        if !x.is_finite() || !y.is_finite() { return; }
+        x_min = x_min.min(x);
+        y_min = y_min.min(y);
+        x_max = x_max.max(x);
+        y_max = y_max.max(y);
+    }
+    if x_min > x_max { return; }
+
+    // the buffer has an extra column because lines add to the pixel right of them
+    let (ox, oy) = (x_min.floor(), y_min.floor());
+    let width = (x_max.ceil() - ox) as usize + 2;
+    let height = (y_max.ceil() - oy) as usize;
+    let mut acc = Accumulator { width, height, area: vec![0.0; width * height + 1] };
+
+    for contour in contours.iter().filter(|c| c.len() >= 2) {
+        for i in 0..contour.len() {
+            let (x1, y1) = contour[i];
+            let (x2, y2) = contour[(i + 1) % contour.len()];
+            acc.line((x1 - ox, y1 - oy), (x2 - ox, y2 - oy));
+        }
+    }
+
+    for y in 0..height {
+        let mut sum = 0.0;
+        for x in 0..width {
+            sum += acc.area[y * width + x];
+            let c = sum.abs().min(1.0);
+            if c > 1.0 / 255.0 {
+                pixel(x as i32 + ox as i32, y as i32 + oy as i32, c);
+            }
+        }
+    }
+}
+
+// stores the signed area every line adds to a pixel minus the area it adds to the pixel before,
+// a running sum over every row then gives the coverage
+struct Accumulator {
+    width: usize,
+    height: usize,
+    area: Vec<f32>,
+}
+
+impl Accumulator {
+    fn line(&mut self, p0: Point, p1: Point) {
+        if (p0.1 - p1.1).abs() <= f32::EPSILON { return; }
+        let (dir, p0, p1) = if p0.1 < p1.1 { (1.0, p0, p1) } else { (-1.0, p1, p0) };
+        let dxdy = (p1.0 - p0.0) / (p1.1 - p0.1);
+
+        let mut x = p0.0;
+        let y_start = p0.1.max(0.0) as usize;
+        if p0.1 < 0.0 { x -= p0.1 * dxdy; }
+
+        for y in y_start..self.height.min(p1.1.ceil() as usize) {
+            let row = y * self.width;
+            let dy = ((y + 1) as f32).min(p1.1) - (y as f32).max(p0.1);
+            let x_next = x + dxdy * dy;
+            let d = dy * dir;
+            let (x0, x1) = if x < x_next { (x, x_next) } else { (x_next, x) };
+            let (x0_floor, x1_ceil) = (x0.floor(), x1.ceil());
+            let (x0i, x1i) = (x0_floor as usize, x1_ceil as usize);
+
+            if x1i <= x0i + 1 {
+                // the line stays within one pixel
+                let xm = 0.5 * (x + x_next) - x0_floor;
+                self.area[row + x0i] += d - d * xm;
+                self.area[row + x0i + 1] += d * xm;
+            } else {
+                // the line crosses several pixels, the area grows linearly between the end pixels
+                let s = (x1 - x0).recip();
+                let x0f = x0 - x0_floor;
+                let a0 = 0.5 * s * (1.0 - x0f) * (1.0 - x0f);
+                let x1f = x1 - x1_ceil + 1.0;
+                let am = 0.5 * s * x1f * x1f;
+
+                self.area[row + x0i] += d * a0;
+                if x1i == x0i + 2 {
+                    self.area[row + x0i + 1] += d * (1.0 - a0 - am);
+                } else {
+                    let a1 = s * (1.5 - x0f);
+                    self.area[row + x0i + 1] += d * (a1 - a0);
+                    for xi in x0i + 2..x1i - 1 {
+                        self.area[row + xi] += d * s;
+                    }
+                    let a2 = a1 + (x1i - x0i - 3) as f32 * s;
+                    self.area[row + x1i - 1] += d * (1.0 - a2 - am);
+                }
+                self.area[row + x1i] += d * am;
+            }
+            x = x_next;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn collect(contours: &[Vec<Point>]) -> Vec<(i32, i32, f32)> {
+        let mut pixels = Vec::new();
+        coverage(contours, |x, y, c| pixels.push((x, y, c)));
+        pixels
+    }
+
+    #[test]
+    fn test_square() {
+        let pixels = collect(&[vec![(1.0, 1.0), (3.0, 1.0), (3.0, 3.0), (1.0, 3.0)]]);
+        assert_eq!(pixels.len(), 4);
+        assert!(pixels.iter().all(|&(x, y, c)| (1..3).contains(&x) && (1..3).contains(&y) && c == 1.0));
+
+        // half covered pixels, winding order doesn't matter
+        let pixels = collect(&[vec![(0.5, 0.0), (0.5, 1.0), (2.0, 1.0), (2.0, 0.0)]]);
+        assert_eq!(pixels, vec![(0, 0, 0.5), (1, 0, 1.0)]);
+    }
+
+    #[test]
+    fn test_triangle() {
+        // diagonal through a single pixel covers half of it
+        let pixels = collect(&[vec![(0.0, 0.0), (1.0, 1.0), (0.0, 1.0)]]);
+        assert_eq!(pixels.len(), 1);
+        assert!((pixels[0].2 - 0.5).abs() < 1e-5);
+
+        // total area matches the triangle
+        let pixels = collect(&[vec![(-2.3, 0.4), (5.7, 1.2), (1.1, 6.9)]]);
+        let area: f32 = pixels.iter().map(|p| p.2).sum();
+        let expected: f32 = 0.5 * ((5.7f32 + 2.3) * (6.9 - 0.4) - (1.1 + 2.3) * (1.2 - 0.4)).abs();
+        assert!((area - expected).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_hole() {
+        // inner contour in the opposite direction cuts a hole
+        let outer = vec![(0.0, 0.0), (4.0, 0.0), (4.0, 4.0), (0.0, 4.0)];
+        let inner = vec![(1.0, 1.0), (1.0, 3.0), (3.0, 3.0), (3.0, 1.0)];
+        let pixels = collect(&[outer, inner]);
+        assert_eq!(pixels.len(), 12);
+    }
+}
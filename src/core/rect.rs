@@ -0,0 +1,82 @@
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Default)]
+/// Axis aligned rectangle in pixels
+///
+/// ### Example
+/// ```
+///# use rain2d::core::Rect;
+/// let a = Rect::new(0, 0, 10, 10);
+/// let b = Rect::new(5, 8, 10, 10);
+/// assert_eq!(a.intersect(b), Some(Rect::new(5, 8, 5, 2)));
+/// assert!(a.contains(9, 9));
+/// ```
+pub struct Rect {
+    /// Left edge
+    pub x: i32,
+
+    /// Top edge
+    pub y: i32,
+
+    /// Width in pixels
+    pub width: i32,
+
+    /// Height in pixels
+    pub height: i32,
+}
+
+impl Rect {
+    /// Creates a rectangle with its upper left corner at `(x, y)`
+    pub fn new(x: i32, y: i32, width: i32, height: i32) -> Self {
+        Rect { x, y, width, height }
+    }
+
+    /// Gets the x coordinate just past the right edge
+    pub fn right(&self) -> i32 {
+        self.x.saturating_add(self.width)
+    }
+
+    /// Gets the y coordinate just past the bottom edge
+    pub fn bottom(&self) -> i32 {
+        self.y.saturating_add(self.height)
+    }
+
+    /// Checks if the rectangle doesn't contain any pixels
+    pub fn is_empty(&self) -> bool {
+        self.width <= 0 || self.height <= 0
+    }
+
+    /// Checks if the pixel at `(x, y)` is inside the rectangle
+    pub fn contains(&self, x: i32, y: i32) -> bool {
+        x >= self.x && x < self.right() && y >= self.y && y < self.bottom()
+    }
+
+    /// Gets the area covered by both rectangles, returns `None` if they don't overlap
+    pub fn intersect(&self, other: Rect) -> Option<Rect> {
+        let (x1, y1) = (self.x.max(other.x), self.y.max(other.y));
+        let (x2, y2) = (self.right().min(other.right()), self.bottom().min(other.bottom()));
+        if x1 >= x2 || y1 >= y2 { return None; }
+        Some(Rect::new(x1, y1, x2 - x1, y2 - y1))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_intersect() {
+        let a = Rect::new(-5, -5, 10, 10);
+        assert_eq!(a.intersect(Rect::new(0, 0, 20, 20)), Some(Rect::new(0, 0, 5, 5)));
+        assert_eq!(a.intersect(Rect::new(5, 0, 20, 20)), None);
+        assert_eq!(a.intersect(Rect::new(0, 0, -3, 3)), None);
+    }
+
+    #[test]
+    fn test_contains() {
+        let rect = Rect::new(1, 2, 3, 4);
+        assert!(rect.contains(1, 2));
+        assert!(rect.contains(3, 5));
+        assert!(!rect.contains(4, 5));
+        assert!(!rect.contains(1, 6));
+        assert!(Rect::new(0, 0, 0, 5).is_empty());
+    }
+}
@@ -1,45 +1,539 @@
-use crate::core::Color;
+use crate::core::{Color, BlendMode, Flip, Palette, Rect, Sprite, NONE, WHITE};
+use std::mem::replace;
+use crate::core::sprite::rgba8;
 
+/// Buffer of pixels that can be drawn to
+///
+/// Pixels are stored row by row as `0xAARRGGBB`. Use [`draw_to_target`] to draw on it
+/// with every primitive of [`RainCore`] and [`draw_target`] to draw it on the screen
+///
+/// [`draw_to_target`]: struct.RainCore.html#method.draw_to_target
+/// [`draw_target`]: struct.RainCore.html#method.draw_target
+/// [`RainCore`]: struct.RainCore.html
+///
+/// ### Example
+/// ```no_run
+/// # use rain2d::core::*;
+/// # let mut core = RainCore::init("example app", 640, 360, true);
+/// // draw the background once and reuse it every frame
+/// let mut background = RenderTarget::new(640, 360);
+/// core.draw_to_target(&mut background, |core| {
+///     core.fill_rect_gradient(0, 0, 640, 360, BLUE, BLACK, GradientDirection::Vertical);
+///     core.fill_circle(500, 80, 40, WHITE);
+/// });
+///
+/// core.draw_target(0, 0, &background);
+/// ```
 pub struct RenderTarget {
+    /// Width in pixels
     pub width: usize,
+
+    /// Height in pixels
     pub height: usize,
+
+    /// Pixel data
     pub data: Vec<u32>,
+
+    /// Blend mode used when writing pixels
+    pub blend_mode: BlendMode,
+
+    /// Pixels outside of this rectangle are never written, `None` allows writing everywhere
+    pub clip_rect: Option<Rect>,
+
+    /// Set when pixels are written, the engine only presents the screen when it is set and clears
+    /// it afterwards. Set it after changing `data` directly
+    pub dirty: bool,
+
+    // depth of every pixel for depth tested drawing, smaller values are closer
+    depth: Option<Vec<f32>>,
 }
 
 #[allow(dead_code)]
 impl RenderTarget {
+    /// Creates a render target with the specified dimensions, all pixels are set to 0
     pub fn new(width: usize, height: usize) -> Self {
         Self {
             width,
             height,
             data: vec![0; width * height],
+            blend_mode: BlendMode::default(),
+            clip_rect: None,
+            dirty: true,
+            depth: None,
         }
     }
 
+    /// Sets all pixels inside the clip rect to the color, ignores the blend mode
     pub fn clear(&mut self, color: Color) {
-        for p in self.data.iter_mut() {
-            *p = color.into();
+        let (x1, y1, x2, y2) = self.clip();
+        self.dirty |= x1 < x2 && y1 < y2;
+        for row in y1..y2 {
+            let start = row as usize * self.width;
+            self.data[start + x1 as usize..start + x2 as usize].fill(color.into());
+        }
+    }
+
+    /// Changes the dimensions, pixels that still fit are kept and new pixels are set to 0
+    pub fn resize(&mut self, width: usize, height: usize) {
+        if (width, height) == (self.width, self.height) { return; }
+        let mut data = vec![0; width * height];
+        let (columns, rows) = (width.min(self.width), height.min(self.height));
+        for row in 0..rows {
+            data[row * width..row * width + columns]
+                .copy_from_slice(&self.data[row * self.width..row * self.width + columns]);
+        }
+        self.width = width;
+        self.height = height;
+        self.data = data;
+        self.dirty = true;
+        if self.depth.is_some() {
+            self.enable_depth();
+        }
+    }
+
+    /// Adds a depth buffer so depth tested drawing like [`fill_triangle_depth`] skips pixels
+    /// behind what was drawn before, all depths start infinitely far away
+    ///
+    /// [`fill_triangle_depth`]: struct.RainCore.html#method.fill_triangle_depth
+    pub fn enable_depth(&mut self) {
+        self.depth = Some(vec![f32::INFINITY; self.width * self.height]);
+    }
+
+    /// Removes the depth buffer, depth tested drawing draws every pixel again
+    pub fn disable_depth(&mut self) {
+        self.depth = None;
+    }
+
+    /// Moves all depths infinitely far away, usually done every frame together with [`clear`]
+    ///
+    /// [`clear`]: #method.clear
+    pub fn clear_depth(&mut self) {
+        if let Some(depth) = &mut self.depth {
+            depth.fill(f32::INFINITY);
+        }
+    }
+
+    /// Gets the depth at `(x, y)`, returns `None` without a depth buffer or if the location is out
+    /// of bounds
+    pub fn get_depth(&self, x: i32, y: i32) -> Option<f32> {
+        if x < 0 || y < 0 || x >= self.width as i32 || y >= self.height as i32 { return None; }
+        self.depth.as_ref().map(|depth| depth[x as usize + y as usize * self.width])
+    }
+
+    // checks if a pixel at depth z is in front of what was drawn there and stores its depth if
+    // it is, passes for every writable pixel without a depth buffer
+    pub(crate) fn depth_test(&mut self, x: i32, y: i32, z: f32) -> bool {
+        let i = match self.index(x, y) {
+            Some(i) => i,
+            None => return false,
+        };
+        match &mut self.depth {
+            Some(depth) if z < depth[i] => {
+                depth[i] = z;
+                true
+            }
+            Some(_) => false,
+            None => true,
+        }
+    }
+
+    /// Creates a render target from 8 bit rgba bytes stored row by row, returns `None` if the
+    /// number of bytes doesn't match the dimensions
+    pub fn from_rgba8(width: usize, height: usize, bytes: &[u8]) -> Option<Self> {
+        let sprite = Sprite::from_rgba8(width, height, bytes)?;
+        Some(Self { width, height, data: sprite.data, ..Self::new(0, 0) })
+    }
+
+    /// Converts the pixels to 8 bit rgba bytes stored row by row, useful for saving screenshots
+    /// with other libraries
+    pub fn to_rgba8(&self) -> Vec<u8> {
+        rgba8(&self.data)
+    }
+
+    /// Copies the pixels into a new sprite, useful to keep something that was drawn once
+    pub fn to_sprite(&self) -> Sprite {
+        Sprite { width: self.width, height: self.height, data: self.data.clone(), color_key: None, mips: Vec::new() }
+    }
+
+    /// Iterates over every pixel with its location, row by row
+    pub fn pixels(&self) -> impl Iterator<Item = (i32, i32, Color)> + '_ {
+        let width = self.width.max(1);
+        self.data.iter().enumerate().map(move |(i, &p)| ((i % width) as i32, (i / width) as i32, p.into()))
+    }
+
+    /// Iterates over every pixel with its location row by row, pixels are stored as `u32` and
+    /// convert from and to [`Color`] with `into`. Ignores the clip rect
+    ///
+    /// [`Color`]: struct.Color.html
+    pub fn pixels_mut(&mut self) -> impl Iterator<Item = (i32, i32, &mut u32)> + '_ {
+        self.dirty = true;
+        let width = self.width.max(1);
+        self.data.iter_mut().enumerate().map(move |(i, p)| ((i % width) as i32, (i / width) as i32, p))
+    }
+
+    /// Replaces every pixel inside the clip rect with the result of `f`, ignores the blend mode
+    ///
+    /// ### Example
+    /// ```
+    ///# use rain2d::core::*;
+    /// let mut target = RenderTarget::new(64, 64);
+    /// target.map_pixels(|x, y, _| Color::rgb((x * 4) as u8, (y * 4) as u8, 0));
+    /// assert_eq!(target.get_pixel(10, 20), Some(Color::rgb(40, 80, 0)));
+    /// ```
+    pub fn map_pixels<F: FnMut(i32, i32, Color) -> Color>(&mut self, mut f: F) {
+        let (x1, y1, x2, y2) = self.clip();
+        self.dirty |= x1 < x2 && y1 < y2;
+        for y in y1..y2 {
+            let start = y as usize * self.width;
+            let row = &mut self.data[start + x1 as usize..start + x2 as usize];
+            for (x, p) in (x1..x2).zip(row) {
+                *p = f(x, y, (*p).into()).into();
+            }
         }
     }
 
+    /// Reduces the pixels inside the clip rect to the colors of the palette with ordered dithering,
+    /// see [`Palette::dither`]
+    ///
+    /// [`Palette::dither`]: struct.Palette.html#method.dither
+    pub fn dither(&mut self, palette: &Palette) {
+        self.map_pixels(|x, y, c| palette.dither(x, y, c));
+    }
+
+    /// Writes the pixel using the current blend mode if the location is in bounds
     pub fn set_pixel(&mut self, x: i32, y: i32, color: Color) {
-        if x >= 0 && x < self.width as i32 && y >= 0 && y < self.height as i32 {
-            self.data[(x + y * self.width as i32) as usize] = color.into();
+        match (self.blend_mode, color.a) {
+            (BlendMode::Replace, _) | (BlendMode::Alpha, 255) => {
+                if let Some(i) = self.index(x, y) {
+                    self.data[i] = color.into();
+                    self.dirty = true;
+                }
+            }
+            (_, 0) => (),
+            _ => self.blend_pixel(x, y, color, 1.0),
         }
     }
 
+    /// Gets the pixel at `(x, y)`, returns `None` if the location is out of bounds
     pub fn get_pixel(&self, x: i32, y: i32) -> Option<Color> {
         if x >= 0 && x < self.width as i32 && y >= 0 && y < self.height as i32 {
             return Some(self.data[x as usize + y as usize * self.width].into());
         }
         None
     }
+
+    /// Fills the area of connected pixels with the same color as `(x, y)`, the area stops at
+    /// the edges of the clip rect
+    // span based flood fill, uses an explicit stack so large areas can't overflow
+    pub fn flood_fill(&mut self, x: i32, y: i32, color: Color) {
+        if self.index(x, y).is_none() { return; }
+        let target: u32 = match self.get_pixel(x, y) {
+            Some(c) => c.into(),
+            None => return,
+        };
+        let replacement: u32 = color.into();
+        if target == replacement { return; }
+        self.dirty = true;
+
+        let (left, top, right, bottom) = self.clip();
+        let mut stack = vec![(x, y)];
+        while let Some((x, y)) = stack.pop() {
+            let row = y as usize * self.width;
+            if self.data[row + x as usize] != target { continue; }
+
+            // expand span to the left and right
+            let mut x1 = x;
+            while x1 > left && self.data[row + x1 as usize - 1] == target { x1 -= 1; }
+            let mut x2 = x;
+            while x2 < right - 1 && self.data[row + x2 as usize + 1] == target { x2 += 1; }
+
+            for p in &mut self.data[row + x1 as usize..=row + x2 as usize] {
+                *p = replacement;
+            }
+
+            // push the start of every matching span above and below
+            for &ny in [y - 1, y + 1].iter() {
+                if ny < top || ny >= bottom { continue; }
+                let next_row = ny as usize * self.width;
+
+                let mut in_span = false;
+                for i in x1..=x2 {
+                    let matches = self.data[next_row + i as usize] == target;
+                    if matches && !in_span {
+                        stack.push((i, ny));
+                    }
+                    in_span = matches;
+                }
+            }
+        }
+    }
+
+    /// Fills the rectangle at `(x, y)` with specified dimensions using the current blend mode,
+    /// the parts outside of the render target or the clip rect are skipped
+    pub fn fill_rect(&mut self, x: i32, y: i32, width: i32, height: i32, color: Color) {
+        let (_, top, right, bottom) = self.clip();
+        let y1 = y.max(top);
+        let y2 = y.saturating_add(height).min(bottom);
+        let x2 = x.saturating_add(width).min(right);
+        if x2 <= x { return; }
+
+        for row in y1..y2 {
+            self.fill_span(x, x2 - 1, row, color);
+        }
+    }
+
+    // fills the pixels from x1 to x2 (inclusive) on row y, clipped to the render target
+    pub(crate) fn fill_span(&mut self, x1: i32, x2: i32, y: i32, color: Color) {
+        let (left, top, right, bottom) = self.clip();
+        if y < top || y >= bottom { return; }
+        let x1 = x1.max(left);
+        let x2 = x2.min(right - 1);
+        if x1 > x2 { return; }
+
+        let row = y as usize * self.width;
+        self.dirty = true;
+        let span = &mut self.data[row + x1 as usize..=row + x2 as usize];
+        match (self.blend_mode, color.a) {
+            (BlendMode::Replace, _) | (BlendMode::Alpha, 255) => span.fill(color.into()),
+            (_, 0) => (),
+            (mode, _) => {
+                for p in span {
+                    *p = mode.blend((*p).into(), color, 1.0).into();
+                }
+            }
+        }
+    }
+
+    // fills the pixels from x1 to x2 (inclusive) on row y with the color returned for each x,
+    // clipped to the render target, pixels without a color are skipped
+    pub(crate) fn fill_span_with<F: FnMut(i32) -> Option<Color>>(&mut self, x1: i32, x2: i32, y: i32, mut color: F) {
+        let (left, top, right, bottom) = self.clip();
+        if y < top || y >= bottom { return; }
+        let x1 = x1.max(left);
+        let x2 = x2.min(right - 1);
+        if x1 > x2 { return; }
+
+        let row = y as usize * self.width;
+        self.dirty = true;
+        let span = &mut self.data[row + x1 as usize..=row + x2 as usize];
+        for (x, p) in (x1..=x2).zip(span) {
+            let color = match color(x) {
+                Some(color) => color,
+                None => continue,
+            };
+            match (self.blend_mode, color.a) {
+                (BlendMode::Replace, _) | (BlendMode::Alpha, 255) => *p = color.into(),
+                (_, 0) => (),
+                (mode, _) => *p = mode.blend((*p).into(), color, 1.0).into(),
+            }
+        }
+    }
+
+    // copies pixels stored row by row with their upper left corner at (x, y), clipped to the
+    // render target, transparent pixels and pixels matching the color key are skipped and
+    // translucent ones use the blend mode, every pixel is multiplied with the tint
+    pub(crate) fn blit_sprite(&mut self, x: i32, y: i32, sprite: &Sprite, flip: Flip, tint: Color) {
+        let src = Rect::new(0, 0, sprite.width as i32, sprite.height as i32);
+        self.blit_sprite_part(x, y, sprite, src, flip, tint);
+    }
+
+    // same as blit_sprite but only copies the src part of the sprite, src must be inside the sprite
+    pub(crate) fn blit_sprite_part(&mut self, x: i32, y: i32, sprite: &Sprite, src: Rect, flip: Flip, tint: Color) {
+        self.blit_pixels(x, y, &sprite.data, sprite.width, sprite.color_key, src, flip, tint);
+    }
+
+    /// Draws the `src_rect` part of `src`, or all of it if `src_rect` is `None`, with its upper
+    /// left corner at `(x, y)`
+    ///
+    /// The part of `src_rect` outside of `src` is skipped and the rest stays at the same position.
+    /// Translucent pixels use the blend mode, set it to [`BlendMode::Replace`] to copy them
+    /// without blending. Fully transparent pixels are always skipped
+    ///
+    /// [`BlendMode::Replace`]: enum.BlendMode.html#variant.Replace
+    ///
+    /// ### Example
+    /// ```
+    /// # use rain2d::core::*;
+    /// let mut atlas = RenderTarget::new(64, 64);
+    /// atlas.fill_rect(16, 0, 16, 16, RED);
+    ///
+    /// let mut target = RenderTarget::new(32, 32);
+    /// target.blit(8, 8, &atlas, Some(Rect::new(16, 0, 16, 16)));
+    /// assert_eq!(target.get_pixel(8, 8), Some(RED));
+    /// ```
+    pub fn blit(&mut self, x: i32, y: i32, src: &RenderTarget, src_rect: Option<Rect>) {
+        let bounds = Rect::new(0, 0, src.width as i32, src.height as i32);
+        let rect = src_rect.unwrap_or(bounds);
+        let part = match rect.intersect(bounds) {
+            Some(part) => part,
+            None => return,
+        };
+        let (x, y) = (x.saturating_add(part.x - rect.x), y.saturating_add(part.y - rect.y));
+        self.blit_pixels(x, y, &src.data, src.width, None, part, Flip::None, WHITE);
+    }
+
+    /// Copies the `src_rect` part of the render target to `(x, y)`, the parts may overlap
+    ///
+    /// The destination is clipped to the clip rect, the blend mode is ignored
+    pub fn copy_region(&mut self, src_rect: Rect, x: i32, y: i32) {
+        let bounds = Rect::new(0, 0, self.width as i32, self.height as i32);
+        let src = match src_rect.intersect(bounds) {
+            Some(src) => src,
+            None => return,
+        };
+        let (x, y) = (x.saturating_add(src.x - src_rect.x), y.saturating_add(src.y - src_rect.y));
+        let (left, top, right, bottom) = self.clip();
+        let dst = match Rect::new(x, y, src.width, src.height).intersect(Rect::new(left, top, right - left, bottom - top)) {
+            Some(dst) => dst,
+            None => return,
+        };
+        let (sx, sy) = (src.x + dst.x - x, src.y + dst.y - y);
+        self.dirty = true;
+
+        // copy rows in the direction that doesn't overwrite rows that still have to be copied
+        for i in 0..dst.height {
+            let row = if dst.y > sy { dst.height - 1 - i } else { i };
+            let from = (sy + row) as usize * self.width + sx as usize;
+            let to = (dst.y + row) as usize * self.width + dst.x as usize;
+            self.data.copy_within(from..from + dst.width as usize, to);
+        }
+    }
+
+    /// Moves the pixels inside the clip rect by `(dx, dy)`, uncovered pixels are set to 0
+    ///
+    /// ### Example
+    /// ```
+    ///# use rain2d::core::*;
+    /// let mut target = RenderTarget::new(100, 100);
+    /// target.set_pixel(50, 90, WHITE);
+    /// // move everything up by a line of text
+    /// target.scroll(0, -8);
+    /// assert_eq!(target.get_pixel(50, 82), Some(WHITE));
+    /// assert_eq!(target.get_pixel(50, 90), Some(NONE));
+    /// ```
+    pub fn scroll(&mut self, dx: i32, dy: i32) {
+        let (left, top, right, bottom) = self.clip();
+        let area = Rect::new(left, top, right - left, bottom - top);
+        if area.is_empty() { return; }
+        self.copy_region(area, left.saturating_add(dx), top.saturating_add(dy));
+
+        // clear the uncovered rows and columns
+        let (dx, dy) = (dx.clamp(-area.width, area.width), dy.clamp(-area.height, area.height));
+        let mode = replace(&mut self.blend_mode, BlendMode::Replace);
+        let rows = if dy > 0 { (top, dy) } else { (bottom + dy, -dy) };
+        self.fill_rect(left, rows.0, area.width, rows.1, NONE);
+        let columns = if dx > 0 { (left, dx) } else { (right + dx, -dx) };
+        self.fill_rect(columns.0, top, columns.1, area.height, NONE);
+        self.blend_mode = mode;
+    }
+
+    // same as blit for all of src with every pixel multiplied by the tint
+    pub(crate) fn blit_tinted(&mut self, x: i32, y: i32, src: &RenderTarget, tint: Color) {
+        let rect = Rect::new(0, 0, src.width as i32, src.height as i32);
+        self.blit_pixels(x, y, &src.data, src.width, None, rect, Flip::None, tint);
+    }
+
+    // copies the src part of pixels stored row by row with stride pixels per row
+    fn blit_pixels(&mut self, x: i32, y: i32, data: &[u32], stride: usize, key: Option<Color>, src: Rect, flip: Flip, tint: Color) {
+        let (left, top, right, bottom) = self.clip();
+        let (x1, y1) = (x.max(left), y.max(top));
+        let x2 = x.saturating_add(src.width).min(right);
+        let y2 = y.saturating_add(src.height).min(bottom);
+        if x1 >= x2 || y1 >= y2 { return; }
+        self.dirty = true;
+
+        let span = (x2 - x1) as usize;
+        for row in y1..y2 {
+            let dy = if flip.vertical() { src.height - 1 - (row - y) } else { row - y };
+            let sy = (src.y + dy) as usize;
+            let src_row = &data[sy * stride..(sy + 1) * stride];
+            let dst_start = row as usize * self.width + x1 as usize;
+            let dst = &mut self.data[dst_start..dst_start + span];
+
+            if flip.horizontal() {
+                let start = (src.right() - (x1 - x)) as usize - span;
+                blit_span(self.blend_mode, key, tint, dst, src_row[start..start + span].iter().rev());
+            } else {
+                let start = (src.x + x1 - x) as usize;
+                blit_span(self.blend_mode, key, tint, dst, src_row[start..start + span].iter());
+            }
+        }
+    }
+
+    /// Blends the color with the existing pixel using the current blend mode,
+    /// `coverage` scales the alpha of the color
+    pub fn blend_pixel(&mut self, x: i32, y: i32, color: Color, coverage: f32) {
+        if let Some(i) = self.index(x, y) {
+            self.data[i] = self.blend_mode.blend(self.data[i].into(), color, coverage).into();
+            self.dirty = true;
+        }
+    }
+
+    // gets the index of a pixel that can be written
+    fn index(&self, x: i32, y: i32) -> Option<usize> {
+        let (left, top, right, bottom) = self.clip();
+        if x >= left && x < right && y >= top && y < bottom {
+            return Some(x as usize + y as usize * self.width);
+        }
+        None
+    }
+
+    // gets the left, top, right and bottom edge of the area that can be written, the right and
+    // bottom edge are exclusive
+    pub(crate) fn clip(&self) -> (i32, i32, i32, i32) {
+        let bounds = Rect::new(0, 0, self.width as i32, self.height as i32);
+        match self.clip_rect.map_or(Some(bounds), |clip| clip.intersect(bounds)) {
+            Some(clip) => (clip.x, clip.y, clip.right(), clip.bottom()),
+            None => (0, 0, 0, 0),
+        }
+    }
+}
+
+/// Converts an image decoded or processed with the image crate, available with the `image`
+/// feature
+#[cfg(feature = "image")]
+impl From<image::DynamicImage> for RenderTarget {
+    fn from(image: image::DynamicImage) -> Self {
+        let sprite = Sprite::from(image);
+        Self { width: sprite.width, height: sprite.height, data: sprite.data, ..Self::new(0, 0) }
+    }
+}
+
+/// Converts the render target to an rgba image of the image crate, available with the `image`
+/// feature
+///
+/// ### Example
+/// ```no_run
+/// # use rain2d::core::*;
+/// # let core = RainCore::init("example app", 640, 360, true);
+/// let screenshot = image::DynamicImage::from(core.get_frame());
+/// screenshot.save("screenshot.png").unwrap();
+/// ```
+#[cfg(feature = "image")]
+impl From<&RenderTarget> for image::DynamicImage {
+    fn from(target: &RenderTarget) -> Self {
+        crate::core::sprite::rgba_image(target.width, target.height, &target.data)
+    }
+}
+
+fn blit_span<'a>(mode: BlendMode, key: Option<Color>, tint: Color, dst: &mut [u32], src: impl Iterator<Item = &'a u32>) {
+    let tinted = tint != WHITE;
+    let key = key.map(|key| u32::from(key) & 0xFFFFFF);
+    for (d, &s) in dst.iter_mut().zip(src) {
+        if key == Some(s & 0xFFFFFF) { continue; }
+        let s = if tinted { Color::from(s).modulate(tint).into() } else { s };
+        match (mode, s >> 24) {
+            (_, 0) => (),
+            (BlendMode::Replace, _) | (BlendMode::Alpha, 255) => *d = s,
+            (mode, _) => *d = mode.blend((*d).into(), s.into(), 1.0).into(),
+        }
+    }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::core::color::WHITE;
+    use crate::core::color::{WHITE, BLACK, NONE, MAGENTA};
 
     #[test]
     fn test_new_rendertarget() {
@@ -50,6 +544,126 @@ mod test {
         assert_eq!(target.data.iter().sum::<u32>(), 0);
     }
 
+    #[test]
+    fn test_dirty() {
+        let mut target = RenderTarget::new(4, 4);
+        assert!(target.dirty);
+        target.dirty = false;
+        target.set_pixel(4, 0, WHITE);
+        target.fill_rect(-2, 0, 2, 4, WHITE);
+        assert!(!target.dirty);
+        target.fill_rect(0, 0, 1, 1, WHITE);
+        assert!(target.dirty);
+
+        target.dirty = false;
+        target.clip_rect = Some(Rect::new(5, 5, 1, 1));
+        target.clear(BLACK);
+        assert!(!target.dirty);
+    }
+
+    #[test]
+    fn test_pixels() {
+        let mut target = RenderTarget::new(3, 2);
+        target.set_pixel(2, 1, WHITE);
+        assert_eq!(target.pixels().count(), 6);
+        assert_eq!(target.pixels().find(|&(_, _, c)| c == WHITE), Some((2, 1, WHITE)));
+
+        for (x, _, p) in target.pixels_mut() {
+            if x == 0 { *p = MAGENTA.into(); }
+        }
+        assert_eq!(target.get_pixel(0, 1), Some(MAGENTA));
+
+        target.clip_rect = Some(Rect::new(1, 0, 5, 1));
+        target.map_pixels(|x, y, c| if c == NONE { Color::rgb(x as u8, y as u8, 1) } else { c });
+        assert_eq!(target.get_pixel(1, 0), Some(Color::rgb(1, 0, 1)));
+        assert_eq!(target.get_pixel(0, 0), Some(MAGENTA));
+        assert_eq!(target.get_pixel(1, 1), Some(NONE));
+        assert_eq!(target.get_pixel(2, 1), Some(WHITE));
+    }
+
+    #[test]
+    fn test_dither() {
+        let mut target = RenderTarget::new(4, 4);
+        target.clear(Color::rgb(128, 128, 128));
+        target.clip_rect = Some(Rect::new(0, 0, 4, 2));
+        target.dither(&Palette::new(&[BLACK, WHITE]));
+        assert_eq!(target.pixels().filter(|&(_, _, c)| c == WHITE).count(), 4);
+        assert_eq!(target.pixels().filter(|&(_, _, c)| c == BLACK).count(), 4);
+        assert_eq!(target.get_pixel(0, 3), Some(Color::rgb(128, 128, 128)));
+    }
+
+    #[test]
+    fn test_copy_region() {
+        let mut target = RenderTarget::new(6, 6);
+        target.map_pixels(|x, y, _| Color::rgb(x as u8, y as u8, 0));
+
+        // overlapping copy down and to the right
+        target.copy_region(Rect::new(0, 0, 4, 4), 2, 2);
+        assert_eq!(target.get_pixel(2, 2), Some(Color::rgb(0, 0, 0)));
+        assert_eq!(target.get_pixel(5, 5), Some(Color::rgb(3, 3, 0)));
+        assert_eq!(target.get_pixel(1, 1), Some(Color::rgb(1, 1, 0)));
+
+        // clipped to the target and the clip rect
+        target.clip_rect = Some(Rect::new(0, 0, 6, 1));
+        target.copy_region(Rect::new(-2, 4, 4, 4), 0, 0);
+        assert_eq!(target.get_pixel(0, 0), Some(Color::rgb(0, 0, 0)));
+        assert_eq!(target.get_pixel(3, 0), Some(Color::rgb(1, 4, 0)));
+        assert_eq!(target.get_pixel(2, 1), Some(Color::rgb(2, 1, 0)));
+    }
+
+    #[test]
+    fn test_scroll() {
+        let mut target = RenderTarget::new(4, 4);
+        target.map_pixels(|x, y, _| Color::rgb(x as u8, y as u8, 1));
+        target.scroll(1, -2);
+        assert_eq!(target.get_pixel(1, 0), Some(Color::rgb(0, 2, 1)));
+        assert_eq!(target.get_pixel(3, 1), Some(Color::rgb(2, 3, 1)));
+        assert_eq!(target.get_pixel(0, 0), Some(NONE));
+        assert_eq!(target.get_pixel(2, 2), Some(NONE));
+
+        // only the clip rect moves
+        target.map_pixels(|x, y, _| Color::rgb(x as u8, y as u8, 1));
+        target.clip_rect = Some(Rect::new(0, 2, 4, 2));
+        target.scroll(0, -1);
+        assert_eq!(target.get_pixel(1, 1), Some(Color::rgb(1, 1, 1)));
+        assert_eq!(target.get_pixel(1, 2), Some(Color::rgb(1, 3, 1)));
+        assert_eq!(target.get_pixel(1, 3), Some(NONE));
+
+        target.scroll(0, 10);
+        assert_eq!(target.get_pixel(1, 2), Some(NONE));
+    }
+
+    #[test]
+    fn test_depth() {
+        let mut target = RenderTarget::new(2, 2);
+        assert!(target.depth_test(0, 0, 5.0));
+        assert_eq!(target.get_depth(0, 0), None);
+
+        target.enable_depth();
+        assert!(target.depth_test(0, 0, 5.0));
+        assert!(!target.depth_test(0, 0, 6.0));
+        assert!(!target.depth_test(0, 0, f32::NAN));
+        assert!(target.depth_test(0, 0, 1.0));
+        assert_eq!(target.get_depth(0, 0), Some(1.0));
+        assert!(!target.depth_test(2, 0, 0.0));
+
+        target.clear_depth();
+        assert_eq!(target.get_depth(0, 0), Some(f32::INFINITY));
+        target.resize(3, 3);
+        assert_eq!(target.get_depth(2, 2), Some(f32::INFINITY));
+    }
+
+    #[test]
+    fn test_resize() {
+        let mut target = RenderTarget::new(3, 3);
+        target.clear(WHITE);
+        target.resize(4, 2);
+        assert_eq!(target.data.len(), 4 * 2);
+        assert_eq!(target.get_pixel(2, 1), Some(WHITE));
+        assert_eq!(target.get_pixel(3, 0), Some(NONE));
+        assert_eq!(target.get_pixel(0, 2), None);
+    }
+
     #[test]
     fn test_clear() {
         let mut target = RenderTarget::new(10, 10);
@@ -60,6 +674,97 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_to_rgba8() {
+        let mut target = RenderTarget::new(2, 1);
+        target.set_pixel(1, 0, Color::rgb(10, 20, 30));
+        assert_eq!(target.to_rgba8(), vec![0, 0, 0, 0, 10, 20, 30, 255]);
+
+        let target = RenderTarget::from_rgba8(2, 1, &target.to_rgba8()).unwrap();
+        assert_eq!((target.width, target.height), (2, 1));
+        assert_eq!(target.get_pixel(1, 0), Some(Color::rgb(10, 20, 30)));
+        assert!(RenderTarget::from_rgba8(2, 2, &[0; 8]).is_none());
+    }
+
+    #[test]
+    #[cfg(feature = "image")]
+    fn test_dynamic_image() {
+        let mut target = RenderTarget::new(1, 2);
+        target.set_pixel(0, 1, Color::rgb(10, 20, 30));
+        let image = image::DynamicImage::from(&target);
+        assert_eq!((image.width(), image.height()), (1, 2));
+
+        let target = RenderTarget::from(image);
+        assert_eq!((target.width, target.height), (1, 2));
+        assert_eq!(target.get_pixel(0, 1), Some(Color::rgb(10, 20, 30)));
+        assert_eq!(target.get_pixel(0, 0), Some(NONE));
+    }
+
+    #[test]
+    fn test_blit_target() {
+        let mut src = RenderTarget::new(3, 2);
+        src.set_pixel(0, 0, WHITE);
+        src.set_pixel(2, 1, Color::rgba(0, 0, 0, 128));
+
+        let mut target = RenderTarget::new(4, 4);
+        target.clear(MAGENTA);
+        target.blit(1, 1, &src, None);
+        assert_eq!(target.get_pixel(1, 1), Some(WHITE));
+        assert_eq!(target.get_pixel(2, 1), Some(MAGENTA));
+        assert_eq!(target.get_pixel(3, 2).unwrap().r, 127);
+
+        // parts outside of the source and the target are clipped
+        target.clear(MAGENTA);
+        target.blit(0, 0, &src, Some(Rect::new(-1, 0, 2, 5)));
+        assert_eq!(target.get_pixel(0, 0), Some(MAGENTA));
+        assert_eq!(target.get_pixel(1, 0), Some(WHITE));
+        target.blit(3, 3, &src, None);
+        assert_eq!(target.get_pixel(3, 3), Some(WHITE));
+
+        // replace copies without blending
+        target.blend_mode = BlendMode::Replace;
+        target.blit(0, 0, &src, Some(Rect::new(2, 1, 1, 1)));
+        assert_eq!(target.get_pixel(0, 0), Some(Color::rgba(0, 0, 0, 128)));
+    }
+
+    #[test]
+    fn test_clip_rect() {
+        let mut target = RenderTarget::new(4, 4);
+        target.clip_rect = Some(Rect::new(1, 1, 2, 5));
+        target.clear(WHITE);
+        assert_eq!(target.get_pixel(0, 0), Some(NONE));
+        assert_eq!(target.get_pixel(1, 3), Some(WHITE));
+
+        target.fill_rect(0, 0, 4, 4, BLACK);
+        target.set_pixel(3, 3, BLACK);
+        target.blend_pixel(0, 1, BLACK, 1.0);
+        assert_eq!(target.data.iter().filter(|&&p| p == BLACK.into()).count(), 6);
+
+        // flood fill stops at the clip rect and doesn't start outside of it
+        target.clip_rect = Some(Rect::new(0, 0, 2, 4));
+        target.flood_fill(0, 0, MAGENTA);
+        assert_eq!(target.get_pixel(0, 3), Some(MAGENTA));
+        assert_eq!(target.get_pixel(3, 3), Some(NONE));
+        target.flood_fill(3, 3, WHITE);
+        assert_eq!(target.get_pixel(3, 3), Some(NONE));
+
+        // nothing is drawn when the clip rect is outside of the target
+        target.clip_rect = Some(Rect::new(5, 5, 2, 2));
+        target.clear(WHITE);
+        target.fill_rect(0, 0, 10, 10, WHITE);
+        assert!(!target.data.contains(&WHITE.into()));
+    }
+
+    #[test]
+    fn test_to_sprite() {
+        let mut target = RenderTarget::new(2, 1);
+        target.set_pixel(1, 0, WHITE);
+        let sprite = target.to_sprite();
+        assert_eq!((sprite.width, sprite.height), (2, 1));
+        assert_eq!(sprite.get_pixel(0, 0), Some(NONE));
+        assert_eq!(sprite.get_pixel(1, 0), Some(WHITE));
+    }
+
     #[test]
     fn test_set_pixel() {
         let mut target = RenderTarget::new(10, 10);
@@ -70,6 +775,115 @@ mod test {
         assert_eq!(target.data[x as usize + y as usize * 10], color.into());
     }
 
+    #[test]
+    fn test_set_pixel_blends() {
+        let mut target = RenderTarget::new(10, 10);
+        target.clear(BLACK);
+
+        target.set_pixel(1, 1, Color::rgba(255, 0, 0, 128));
+        assert_eq!(target.get_pixel(1, 1), Some(Color::rgba(128, 0, 0, 255)));
+
+        target.set_pixel(2, 2, Color::rgba(255, 0, 0, 0));
+        assert_eq!(target.get_pixel(2, 2), Some(BLACK));
+
+        target.set_pixel(3, 3, WHITE);
+        assert_eq!(target.get_pixel(3, 3), Some(WHITE));
+    }
+
+    #[test]
+    fn test_set_pixel_blend_mode() {
+        let mut target = RenderTarget::new(10, 10);
+        target.clear(Color::rgb(100, 100, 100));
+
+        target.blend_mode = BlendMode::Additive;
+        target.set_pixel(1, 1, Color::rgb(100, 200, 0));
+        assert_eq!(target.get_pixel(1, 1), Some(Color::rgb(200, 255, 100)));
+
+        target.blend_mode = BlendMode::Replace;
+        target.set_pixel(2, 2, Color::rgba(255, 0, 0, 0));
+        assert_eq!(target.get_pixel(2, 2), Some(Color::rgba(255, 0, 0, 0)));
+    }
+
+    #[test]
+    fn test_fill_rect() {
+        let mut target = RenderTarget::new(10, 10);
+        target.fill_rect(-2, 8, 5, 10, WHITE);
+
+        assert_eq!(target.get_pixel(0, 8), Some(WHITE));
+        assert_eq!(target.get_pixel(2, 9), Some(WHITE));
+        assert_eq!(target.get_pixel(3, 9), Some(NONE));
+        assert_eq!(target.get_pixel(0, 7), Some(NONE));
+        assert_eq!(target.data.iter().filter(|&&p| p != 0).count(), 6);
+
+        // translucent fills blend with the existing pixels
+        target.clear(BLACK);
+        target.fill_rect(0, 0, 10, 10, Color::rgba(255, 0, 0, 128));
+        assert!(target.data.iter().all(|&p| Color::from(p) == Color::rgb(128, 0, 0)));
+
+        target.fill_rect(20, 0, 5, 5, WHITE);
+        target.fill_rect(0, 0, -5, 5, WHITE);
+        assert!(target.data.iter().all(|&p| Color::from(p) == Color::rgb(128, 0, 0)));
+    }
+
+    #[test]
+    fn test_blit() {
+        let mut target = RenderTarget::new(4, 4);
+        let src = Sprite::from_colors(2, 2, &[WHITE, NONE, Color::rgba(255, 255, 255, 128), WHITE]).unwrap();
+
+        // clipped at the top left corner
+        target.blit_sprite(-1, -1, &src, Flip::None, WHITE);
+        assert_eq!(target.get_pixel(0, 0), Some(WHITE));
+        assert_eq!(target.data.iter().filter(|&&p| p != 0).count(), 1);
+
+        target.clear(BLACK);
+        target.blit_sprite(3, 2, &src, Flip::None, WHITE);
+        assert_eq!(target.get_pixel(3, 2), Some(WHITE));
+        assert_eq!(target.get_pixel(3, 3), Some(Color::rgb(128, 128, 128)));
+        assert_eq!(target.get_pixel(2, 2), Some(BLACK));
+
+        // flipped and clipped on the right
+        target.clear(BLACK);
+        target.blit_sprite(3, 0, &src, Flip::Both, WHITE);
+        assert_eq!(target.get_pixel(3, 0), Some(WHITE));
+        assert_eq!(target.get_pixel(3, 1), Some(BLACK));
+    }
+
+    #[test]
+    fn test_blit_part() {
+        let mut target = RenderTarget::new(2, 2);
+        let src = Sprite::from_colors(3, 2, &[WHITE, BLACK, MAGENTA, BLACK, MAGENTA, WHITE]).unwrap();
+
+        target.blit_sprite_part(0, 0, &src, Rect::new(1, 0, 2, 2), Flip::None, WHITE);
+        assert_eq!(target.get_pixel(0, 0), Some(BLACK));
+        assert_eq!(target.get_pixel(1, 1), Some(WHITE));
+
+        target.blit_sprite_part(-1, 0, &src, Rect::new(1, 0, 2, 2), Flip::Horizontal, WHITE);
+        assert_eq!(target.get_pixel(0, 0), Some(BLACK));
+        assert_eq!(target.get_pixel(0, 1), Some(MAGENTA));
+        assert_eq!(target.get_pixel(1, 0), Some(MAGENTA));
+    }
+
+    #[test]
+    fn test_blit_tint() {
+        let mut target = RenderTarget::new(2, 1);
+        let src = Sprite::from_colors(2, 1, &[WHITE, Color::rgb(100, 200, 50)]).unwrap();
+
+        target.blit_sprite(0, 0, &src, Flip::None, Color::rgb(255, 0, 0));
+        assert_eq!(target.get_pixel(0, 0), Some(Color::rgb(255, 0, 0)));
+        assert_eq!(target.get_pixel(1, 0), Some(Color::rgb(100, 0, 0)));
+    }
+
+    #[test]
+    fn test_blit_color_key() {
+        let mut target = RenderTarget::new(2, 1);
+        let mut src = Sprite::from_colors(2, 1, &[MAGENTA, WHITE]).unwrap();
+        src.color_key = Some(MAGENTA);
+
+        target.blit_sprite(0, 0, &src, Flip::None, WHITE);
+        assert_eq!(target.get_pixel(0, 0), Some(NONE));
+        assert_eq!(target.get_pixel(1, 0), Some(WHITE));
+    }
+
     #[test]
     fn test_get_pixel() {
         let mut target = RenderTarget::new(10, 10);
@@ -80,4 +894,35 @@ mod test {
         assert_eq!(target.get_pixel(x, y), Some(color));
         assert_eq!(target.get_pixel(100, 100), None);
     }
+
+    #[test]
+    fn test_flood_fill() {
+        let mut target = RenderTarget::new(10, 10);
+
+        // wall splitting the target in two
+        for y in 0..10 {
+            target.set_pixel(4, y, WHITE);
+        }
+        target.flood_fill(1, 1, BLACK);
+
+        assert_eq!(target.get_pixel(0, 9), Some(BLACK));
+        assert_eq!(target.get_pixel(3, 0), Some(BLACK));
+        assert_eq!(target.get_pixel(4, 5), Some(WHITE));
+        assert_eq!(target.get_pixel(5, 5), Some(Color::rgba(0, 0, 0, 0)));
+    }
+
+    #[test]
+    fn test_blend_pixel() {
+        let mut target = RenderTarget::new(10, 10);
+        target.clear(BLACK);
+
+        target.blend_pixel(1, 1, WHITE, 1.0);
+        assert_eq!(target.get_pixel(1, 1), Some(WHITE));
+
+        target.blend_pixel(2, 2, WHITE, 0.5);
+        assert_eq!(target.get_pixel(2, 2), Some(Color::rgba(128, 128, 128, 255)));
+
+        target.blend_pixel(3, 3, Color::rgba(255, 0, 0, 0), 1.0);
+        assert_eq!(target.get_pixel(3, 3), Some(BLACK));
+    }
 }
\ No newline at end of file
@@ -0,0 +1,137 @@
+use crate::core::{Color, Rect, RenderTarget};
+
+#[derive(Debug, PartialEq, Copy, Clone, Default)]
+/// How the screen is scaled to fill the window when it has a different size, set it with
+/// [`set_logical_size`]
+///
+/// [`set_logical_size`]: struct.RainCore.html#method.set_logical_size
+pub enum ScaleMode {
+    /// Fills the whole window, pixels are stretched if the aspect ratio doesn't match
+    Stretch,
+
+    /// Scales as large as possible while keeping the aspect ratio, the rest of the window is black
+    #[default]
+    Letterbox,
+
+    /// Scales by the largest whole number that fits so every pixel has the same size, falls back to
+    /// letterboxing when the window is smaller than the screen
+    Integer,
+}
+
+impl ScaleMode {
+    /// Gets the part of a window of `window_width` by `window_height` pixels that shows a screen
+    /// of `width` by `height` pixels, centered in the window
+    ///
+    /// ### Example
+    /// ```
+    ///# use rain2d::core::ScaleMode;
+    ///# use rain2d::core::Rect;
+    /// assert_eq!(ScaleMode::Letterbox.viewport(320, 180, 800, 600), Rect::new(0, 75, 800, 450));
+    /// assert_eq!(ScaleMode::Integer.viewport(320, 180, 800, 600), Rect::new(80, 120, 640, 360));
+    /// ```
+    pub fn viewport(&self, width: usize, height: usize, window_width: usize, window_height: usize) -> Rect {
+        let (window_width, window_height) = (window_width as i32, window_height as i32);
+        if width == 0 || height == 0 {
+            return Rect::new(0, 0, window_width, window_height);
+        }
+
+        let (sx, sy) = (window_width as f32 / width as f32, window_height as f32 / height as f32);
+        let scale = match self {
+            ScaleMode::Stretch => return Rect::new(0, 0, window_width, window_height),
+            ScaleMode::Integer if sx >= 1.0 && sy >= 1.0 => sx.min(sy).floor(),
+            _ => sx.min(sy),
+        };
+        let (w, h) = ((width as f32 * scale).round() as i32, (height as f32 * scale).round() as i32);
+        Rect::new((window_width - w) / 2, (window_height - h) / 2, w, h)
+    }
+}
+
+// scales src into the viewport of dst using the nearest pixel, the rest of dst is cleared
+pub fn scale_into(src: &RenderTarget, dst: &mut RenderTarget, viewport: Rect) {
+    dst.data.fill(0);
+    let bounds = Rect::new(0, 0, dst.width as i32, dst.height as i32);
+    let visible = match viewport.intersect(bounds) {
+        Some(visible) => visible,
+        None => return,
+    };
+
+    // source column of every destination column
+    let columns: Vec<usize> = (visible.x..visible.right())
+        .map(|x| (x - viewport.x) as usize * src.width / viewport.width as usize)
+        .collect();
+    for y in visible.y..visible.bottom() {
+        let sy = (y - viewport.y) as usize * src.height / viewport.height as usize;
+        let src_row = &src.data[sy * src.width..(sy + 1) * src.width];
+        let start = y as usize * dst.width + visible.x as usize;
+        for (pixel, &sx) in dst.data[start..start + columns.len()].iter_mut().zip(&columns) {
+            *pixel = src_row[sx];
+        }
+    }
+}
+
+// averages every factor by factor block of src into a pixel of dst
+pub fn downsample(src: &RenderTarget, dst: &mut RenderTarget, factor: usize) {
+    let (width, height) = (src.width / factor, src.height / factor);
+    dst.resize(width, height);
+    let count = (factor * factor) as u32;
+    for y in 0..height {
+        for x in 0..width {
+            let mut sum = [0u32; 4];
+            for sy in y * factor..(y + 1) * factor {
+                for &p in &src.data[sy * src.width + x * factor..sy * src.width + (x + 1) * factor] {
+                    let c = Color::from(p);
+                    sum[0] += c.r as u32;
+                    sum[1] += c.g as u32;
+                    sum[2] += c.b as u32;
+                    sum[3] += c.a as u32;
+                }
+            }
+            let avg = |v: u32| ((v + count / 2) / count) as u8;
+            dst.data[y * width + x] = Color::rgba(avg(sum[0]), avg(sum[1]), avg(sum[2]), avg(sum[3])).into();
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::core::color::{WHITE, RED, NONE};
+
+    #[test]
+    fn test_viewport() {
+        assert_eq!(ScaleMode::Stretch.viewport(320, 180, 800, 600), Rect::new(0, 0, 800, 600));
+        assert_eq!(ScaleMode::Letterbox.viewport(100, 100, 300, 200), Rect::new(50, 0, 200, 200));
+        assert_eq!(ScaleMode::Integer.viewport(100, 100, 300, 250), Rect::new(50, 25, 200, 200));
+        // smaller windows scale down
+        assert_eq!(ScaleMode::Integer.viewport(100, 100, 50, 80), Rect::new(0, 15, 50, 50));
+    }
+
+    #[test]
+    fn test_downsample() {
+        let mut src = RenderTarget::new(5, 2);
+        src.fill_rect(0, 0, 1, 2, WHITE);
+        src.set_pixel(2, 0, RED);
+        let mut dst = RenderTarget::new(0, 0);
+        downsample(&src, &mut dst, 2);
+        assert_eq!((dst.width, dst.height), (2, 1));
+        assert_eq!(dst.get_pixel(0, 0), Some(Color::rgba(128, 128, 128, 128)));
+        assert_eq!(dst.get_pixel(1, 0), Some(Color::rgba(64, 0, 0, 64)));
+    }
+
+    #[test]
+    fn test_scale_into() {
+        let mut src = RenderTarget::new(2, 1);
+        src.set_pixel(0, 0, WHITE);
+        src.set_pixel(1, 0, RED);
+        let mut dst = RenderTarget::new(6, 4);
+        dst.clear(WHITE);
+        scale_into(&src, &mut dst, ScaleMode::Letterbox.viewport(2, 1, 6, 4));
+
+        // 2x1 fits as 6x3 at the top, the bottom row is cleared
+        assert_eq!(dst.get_pixel(0, 0), Some(WHITE));
+        assert_eq!(dst.get_pixel(2, 2), Some(WHITE));
+        assert_eq!(dst.get_pixel(3, 1), Some(RED));
+        assert_eq!(dst.get_pixel(5, 2), Some(RED));
+        assert_eq!(dst.get_pixel(0, 3), Some(NONE));
+    }
+}
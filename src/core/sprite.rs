@@ -0,0 +1,522 @@
+use std::{error::Error, fmt, fs, io, path::Path};
+
+use crate::core::{Color, NONE};
+
+#[derive(Debug)]
+/// Error returned when loading an image fails
+pub enum ImageError {
+    /// The file couldn't be read
+    Io(io::Error),
+
+    /// The data is not a valid image
+    Invalid(&'static str),
+
+    /// The image uses a feature that isn't supported
+    Unsupported(&'static str),
+}
+
+impl fmt::Display for ImageError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ImageError::Io(e) => write!(f, "failed to read image: {}", e),
+            ImageError::Invalid(reason) => write!(f, "invalid image: {}", reason),
+            ImageError::Unsupported(feature) => write!(f, "unsupported image: {}", feature),
+        }
+    }
+}
+
+impl Error for ImageError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ImageError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for ImageError {
+    fn from(e: io::Error) -> Self {
+        ImageError::Io(e)
+    }
+}
+
+#[derive(Debug, PartialEq, Copy, Clone, Default)]
+/// Sampling used when sprites are drawn at a different size
+pub enum Filter {
+    /// Uses the closest pixel, keeps pixel art sharp
+    #[default]
+    Nearest,
+
+    /// Interpolates between the four closest pixels, smooths out photographic images
+    Bilinear,
+}
+
+#[derive(Debug, PartialEq, Copy, Clone, Default)]
+/// Mirrors sprites when drawing them
+pub enum Flip {
+    /// Draws the sprite as is
+    #[default]
+    None,
+
+    /// Mirrors the sprite left to right
+    Horizontal,
+
+    /// Mirrors the sprite top to bottom
+    Vertical,
+
+    /// Mirrors the sprite in both directions, same as rotating it by 180 degrees
+    Both,
+}
+
+impl Flip {
+    /// Checks if the sprite is mirrored left to right
+    pub fn horizontal(self) -> bool {
+        self == Flip::Horizontal || self == Flip::Both
+    }
+
+    /// Checks if the sprite is mirrored top to bottom
+    pub fn vertical(self) -> bool {
+        self == Flip::Vertical || self == Flip::Both
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+/// Image that can be drawn with [`draw_sprite`]
+///
+/// [`draw_sprite`]: struct.RainCore.html#method.draw_sprite
+///
+/// ### Example
+/// ```
+///# use rain2d::core::*;
+/// let mut sprite = Sprite::new(16, 16);
+/// sprite.set_pixel(8, 8, RED);
+/// assert_eq!(sprite.get_pixel(8, 8), Some(RED));
+/// ```
+pub struct Sprite {
+    /// Width in pixels
+    pub width: usize,
+
+    /// Height in pixels
+    pub height: usize,
+
+    /// Pixels stored row by row in the same format as [`Color`] converts to
+    ///
+    /// [`Color`]: struct.Color.html
+    pub data: Vec<u32>,
+
+    /// Pixels with this color are skipped when drawing, for images without an alpha channel,
+    /// the alpha of the pixels is ignored when comparing
+    pub color_key: Option<Color>,
+
+    /// Smaller versions of the sprite created by [`generate_mips`], each one is half the size
+    /// of the previous one
+    ///
+    /// [`generate_mips`]: #method.generate_mips
+    pub mips: Vec<Sprite>,
+}
+
+impl Sprite {
+    /// Creates a sprite with the specified dimensions, all pixels are transparent
+    pub fn new(width: usize, height: usize) -> Self {
+        Sprite { width, height, data: vec![0; width * height], color_key: None, mips: Vec::new() }
+    }
+
+    /// Creates a sprite from colors stored row by row, returns `None` if
+    /// the number of colors doesn't match the dimensions
+    pub fn from_colors(width: usize, height: usize, colors: &[Color]) -> Option<Self> {
+        if colors.len() != width * height { return None; }
+        let data = colors.iter().map(|&c| c.into()).collect();
+        Some(Sprite { width, height, data, color_key: None, mips: Vec::new() })
+    }
+
+    /// Creates a sprite from 8 bit rgba bytes stored row by row, returns `None` if
+    /// the number of bytes doesn't match the dimensions
+    ///
+    /// This is the layout used by most image libraries, for example `RgbaImage` from the image crate
+    ///
+    /// ### Example
+    /// ```
+    ///# use rain2d::core::*;
+    /// let bytes = [255, 0, 0, 255, 0, 0, 255, 128];
+    /// let sprite = Sprite::from_rgba8(2, 1, &bytes).unwrap();
+    /// assert_eq!(sprite.get_pixel(1, 0), Some(Color::rgba(0, 0, 255, 128)));
+    /// assert_eq!(sprite.to_rgba8(), bytes);
+    /// ```
+    pub fn from_rgba8(width: usize, height: usize, bytes: &[u8]) -> Option<Self> {
+        if bytes.len() != width * height * 4 { return None; }
+        let data = bytes.chunks_exact(4).map(|p| Color::rgba(p[0], p[1], p[2], p[3]).into()).collect();
+        Some(Sprite { width, height, data, color_key: None, mips: Vec::new() })
+    }
+
+    /// Converts the pixels to 8 bit rgba bytes stored row by row
+    pub fn to_rgba8(&self) -> Vec<u8> {
+        rgba8(&self.data)
+    }
+
+    /// Creates a sprite from ascii art where every character is a pixel with the color it has
+    /// in the palette, characters that aren't in the palette are transparent
+    ///
+    /// Empty lines and whitespace around lines are ignored so the art can be indented,
+    /// shorter lines are padded with transparent pixels
+    ///
+    /// ### Example
+    /// ```
+    ///# use rain2d::core::*;
+    /// let coin = Sprite::from_ascii("
+    ///     .yy.
+    ///     yYYy
+    ///     yYYy
+    ///     .yy.
+    /// ", &[('y', Color::rgb(200, 160, 0)), ('Y', YELLOW)]);
+    /// assert_eq!((coin.width, coin.height), (4, 4));
+    /// assert_eq!(coin.get_pixel(1, 1), Some(YELLOW));
+    /// assert_eq!(coin.get_pixel(0, 0), Some(NONE));
+    /// ```
+    pub fn from_ascii(art: &str, palette: &[(char, Color)]) -> Self {
+        let rows: Vec<&str> = art.lines().map(str::trim).filter(|line| !line.is_empty()).collect();
+        let width = rows.iter().map(|row| row.chars().count()).max().unwrap_or(0);
+
+        let mut sprite = Sprite::new(width, rows.len());
+        for (y, row) in rows.iter().enumerate() {
+            for (x, c) in row.chars().enumerate() {
+                if let Some(&(_, color)) = palette.iter().find(|&&(key, _)| key == c) {
+                    sprite.data[y * width + x] = color.into();
+                }
+            }
+        }
+        sprite
+    }
+
+    /// Loads a png file
+    ///
+    /// Interlaced images are not supported
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// let player = Sprite::from_png("assets/player.png").expect("failed to load player");
+    /// ```
+    #[cfg(feature = "png")]
+    pub fn from_png<P: AsRef<Path>>(path: P) -> Result<Self, ImageError> {
+        Self::from_png_bytes(&fs::read(path)?)
+    }
+
+    /// Decodes a png file that is already in memory, useful together with `include_bytes!`
+    #[cfg(feature = "png")]
+    pub fn from_png_bytes(bytes: &[u8]) -> Result<Self, ImageError> {
+        crate::core::png::decode(bytes)
+    }
+
+    /// Loads an uncompressed 24 or 32 bit bmp file
+    ///
+    /// Unlike [`from_png`] this is always available
+    ///
+    /// [`from_png`]: #method.from_png
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # use rain2d::core::*;
+    /// let tiles = Sprite::from_bmp("assets/tiles.bmp").expect("failed to load tiles");
+    /// ```
+    pub fn from_bmp<P: AsRef<Path>>(path: P) -> Result<Self, ImageError> {
+        Self::from_bmp_bytes(&fs::read(path)?)
+    }
+
+    /// Decodes a bmp file that is already in memory
+    pub fn from_bmp_bytes(bytes: &[u8]) -> Result<Self, ImageError> {
+        crate::core::bmp::decode(bytes)
+    }
+
+    /// Copies the part of the sprite at `(x, y)` with the specified dimensions into a new sprite,
+    /// the part outside of the sprite is transparent
+    pub fn crop(&self, x: usize, y: usize, width: usize, height: usize) -> Sprite {
+        let mut sprite = Sprite { color_key: self.color_key, ..Sprite::new(width, height) };
+        for row in 0..height.min(self.height.saturating_sub(y)) {
+            let len = width.min(self.width.saturating_sub(x));
+            let src = (y + row) * self.width + x;
+            sprite.data[row * width..row * width + len].copy_from_slice(&self.data[src..src + len]);
+        }
+        sprite
+    }
+
+    /// Creates smaller versions of the sprite that are used when it is drawn scaled down, this
+    /// reduces shimmering when the sprite is drawn at many different sizes
+    ///
+    /// Every level is half the size of the previous one down to a single pixel, call this
+    /// again after changing the pixels
+    ///
+    /// ### Example
+    #[cfg_attr(feature = "png", doc = "```no_run")]
+    #[cfg_attr(not(feature = "png"), doc = "```ignore")]
+    /// # use rain2d::core::*;
+    /// # let mut core = RainCore::init("example app", 640, 360, true);
+    /// let mut planet = Sprite::from_png("assets/planet.png").unwrap();
+    /// planet.generate_mips();
+    ///
+    /// // far away
+    /// core.draw_sprite_scaled(100, 100, &planet, 0.1, 0.1, Filter::Bilinear);
+    /// ```
+    pub fn generate_mips(&mut self) {
+        self.mips.clear();
+        if self.width == 0 || self.height == 0 { return; }
+        let mut mips: Vec<Sprite> = Vec::new();
+        let (mut width, mut height) = (self.width, self.height);
+        while width > 1 || height > 1 {
+            let prev = mips.last().unwrap_or(self);
+            let next = prev.downsample();
+            width = next.width;
+            height = next.height;
+            mips.push(next);
+        }
+        self.mips = mips;
+    }
+
+    // picks the mip level closest to the scale the sprite is drawn at
+    pub(crate) fn mip_for_scale(&self, scale: f32) -> &Sprite {
+        if scale >= 1.0 || self.mips.is_empty() { return self; }
+        let level = (-scale.log2()).round() as usize;
+        match level {
+            0 => self,
+            level => &self.mips[(level - 1).min(self.mips.len() - 1)],
+        }
+    }
+
+    // halves the size by averaging 2x2 blocks, pixels are weighted by their alpha so
+    // transparent pixels don't darken the edges
+    fn downsample(&self) -> Sprite {
+        let (width, height) = ((self.width / 2).max(1), (self.height / 2).max(1));
+        let mut sprite = Sprite::new(width, height);
+        for y in 0..height {
+            for x in 0..width {
+                let (mut r, mut g, mut b, mut a) = (0, 0, 0, 0);
+                for (dx, dy) in [(0, 0), (1, 0), (0, 1), (1, 1)] {
+                    let px = (x * 2 + dx).min(self.width - 1) as i32;
+                    let py = (y * 2 + dy).min(self.height - 1) as i32;
+                    let c = self.visible_pixel(px, py);
+                    let weight = c.a as u32;
+                    r += c.r as u32 * weight;
+                    g += c.g as u32 * weight;
+                    b += c.b as u32 * weight;
+                    a += weight;
+                }
+                if a == 0 { continue; }
+                let color = Color::rgba((r / a) as u8, (g / a) as u8, (b / a) as u8, (a / 4) as u8);
+                sprite.data[y * width + x] = color.into();
+            }
+        }
+        sprite
+    }
+
+    /// Gets the color at `(u, v)` in pixels, the center of the upper left pixel is at `(0.5, 0.5)`
+    ///
+    /// Nearest sampling returns [`NONE`] outside of the sprite, bilinear sampling
+    /// repeats the edge pixels
+    ///
+    /// [`NONE`]: constant.NONE.html
+    pub fn sample(&self, u: f32, v: f32, filter: Filter) -> Color {
+        match filter {
+            Filter::Nearest => self.visible_pixel(u.floor() as i32, v.floor() as i32),
+            Filter::Bilinear => {
+                if self.width == 0 || self.height == 0 { return NONE; }
+                let (u, v) = (u - 0.5, v - 0.5);
+                let (x, y) = (u.floor(), v.floor());
+                let (tx, ty) = (u - x, v - y);
+
+                let pixel = |dx: i32, dy: i32| {
+                    let px = (x as i32 + dx).clamp(0, self.width as i32 - 1);
+                    let py = (y as i32 + dy).clamp(0, self.height as i32 - 1);
+                    self.visible_pixel(px, py)
+                };
+                let top = pixel(0, 0).lerp(pixel(1, 0), tx);
+                let bottom = pixel(0, 1).lerp(pixel(1, 1), tx);
+                top.lerp(bottom, ty)
+            }
+        }
+    }
+
+    /// Gets the pixel at `(x, y)`, returns `None` if the location is out of bounds
+    pub fn get_pixel(&self, x: i32, y: i32) -> Option<Color> {
+        self.index(x, y).map(|i| self.data[i].into())
+    }
+
+    /// Overwrites the pixel at `(x, y)` if the location is in bounds
+    pub fn set_pixel(&mut self, x: i32, y: i32, color: Color) {
+        if let Some(i) = self.index(x, y) {
+            self.data[i] = color.into();
+        }
+    }
+
+    // checks if the pixel matches the color key
+    pub(crate) fn is_keyed(&self, p: u32) -> bool {
+        self.color_key.is_some_and(|key| u32::from(key) & 0xFFFFFF == p & 0xFFFFFF)
+    }
+
+    // gets the pixel as it is drawn, transparent outside of the sprite or if it matches the color key
+    fn visible_pixel(&self, x: i32, y: i32) -> Color {
+        match self.index(x, y) {
+            Some(i) if !self.is_keyed(self.data[i]) => self.data[i].into(),
+            _ => NONE,
+        }
+    }
+
+    fn index(&self, x: i32, y: i32) -> Option<usize> {
+        if x >= 0 && x < self.width as i32 && y >= 0 && y < self.height as i32 {
+            return Some(x as usize + y as usize * self.width);
+        }
+        None
+    }
+}
+
+/// Converts an image decoded or processed with the image crate, available with the `image`
+/// feature
+///
+/// ### Example
+/// ```no_run
+/// # use rain2d::core::*;
+/// let player = Sprite::from(image::open("assets/player.webp").unwrap());
+/// ```
+#[cfg(feature = "image")]
+impl From<image::DynamicImage> for Sprite {
+    fn from(image: image::DynamicImage) -> Self {
+        let image = image.into_rgba8();
+        let (width, height) = (image.width() as usize, image.height() as usize);
+        Sprite::from_rgba8(width, height, image.as_raw()).expect("rgba images have 4 bytes per pixel")
+    }
+}
+
+/// Converts the sprite to an rgba image of the image crate, available with the `image` feature
+#[cfg(feature = "image")]
+impl From<&Sprite> for image::DynamicImage {
+    fn from(sprite: &Sprite) -> Self {
+        rgba_image(sprite.width, sprite.height, &sprite.data)
+    }
+}
+
+// converts pixels in the format Color converts to into rgba bytes
+pub(crate) fn rgba8(pixels: &[u32]) -> Vec<u8> {
+    pixels.iter().flat_map(|&p| {
+        let c = Color::from(p);
+        [c.r, c.g, c.b, c.a]
+    }).collect()
+}
+
+// converts pixels in the format Color converts to into an image of the image crate
+#[cfg(feature = "image")]
+pub(crate) fn rgba_image(width: usize, height: usize, pixels: &[u32]) -> image::DynamicImage {
+    let image = image::RgbaImage::from_raw(width as u32, height as u32, rgba8(pixels));
+    image::DynamicImage::ImageRgba8(image.expect("there are 4 bytes for every pixel"))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::core::color::{RED, BLUE, MAGENTA};
+
+    #[test]
+    fn test_from_colors() {
+        let sprite = Sprite::from_colors(2, 1, &[RED, BLUE]).unwrap();
+        assert_eq!(sprite.get_pixel(0, 0), Some(RED));
+        assert_eq!(sprite.get_pixel(1, 0), Some(BLUE));
+        assert_eq!(sprite.get_pixel(2, 0), None);
+
+        assert_eq!(Sprite::from_colors(2, 2, &[RED]), None);
+    }
+
+    #[test]
+    #[cfg(feature = "image")]
+    fn test_dynamic_image() {
+        let image = image::DynamicImage::ImageRgb8(image::RgbImage::from_raw(2, 1, vec![255, 0, 0, 0, 0, 255]).unwrap());
+        let sprite = Sprite::from(image);
+        assert_eq!((sprite.width, sprite.height), (2, 1));
+        assert_eq!(sprite.get_pixel(1, 0), Some(BLUE));
+
+        let image = image::DynamicImage::from(&sprite);
+        assert_eq!(image.as_rgba8().unwrap().as_raw(), &vec![255, 0, 0, 255, 0, 0, 255, 255]);
+    }
+
+    #[test]
+    fn test_rgba8() {
+        let bytes = [255, 0, 0, 255, 0, 0, 255, 255, 1, 2, 3, 4];
+        let sprite = Sprite::from_rgba8(3, 1, &bytes).unwrap();
+        assert_eq!(sprite.get_pixel(0, 0), Some(RED));
+        assert_eq!(sprite.get_pixel(1, 0), Some(BLUE));
+        assert_eq!(sprite.get_pixel(2, 0), Some(Color::rgba(1, 2, 3, 4)));
+        assert_eq!(sprite.to_rgba8(), bytes);
+
+        assert_eq!(Sprite::from_rgba8(2, 2, &bytes), None);
+    }
+
+    #[test]
+    fn test_from_ascii() {
+        let sprite = Sprite::from_ascii("
+            r.b
+            b
+
+        ", &[('r', RED), ('b', BLUE)]);
+        assert_eq!((sprite.width, sprite.height), (3, 2));
+        assert_eq!(sprite.data, [RED, NONE, BLUE, BLUE, NONE, NONE].iter().map(|&c| c.into()).collect::<Vec<u32>>());
+
+        assert_eq!(Sprite::from_ascii("", &[]), Sprite::new(0, 0));
+    }
+
+    #[test]
+    fn test_generate_mips() {
+        let mut sprite = Sprite::from_colors(4, 2, &[RED, RED, BLUE, NONE, RED, RED, BLUE, BLUE]).unwrap();
+        sprite.generate_mips();
+
+        let sizes: Vec<_> = sprite.mips.iter().map(|mip| (mip.width, mip.height)).collect();
+        assert_eq!(sizes, vec![(2, 1), (1, 1)]);
+        assert_eq!(sprite.mips[0].get_pixel(0, 0), Some(RED));
+        assert_eq!(sprite.mips[0].get_pixel(1, 0), Some(Color::rgba(0, 0, 255, 191)));
+
+        assert_eq!(sprite.mip_for_scale(2.0).width, 4);
+        assert_eq!(sprite.mip_for_scale(0.6).width, 2);
+        assert_eq!(sprite.mip_for_scale(0.1).width, 1);
+    }
+
+    #[test]
+    fn test_crop() {
+        let sprite = Sprite::from_colors(3, 2, &[RED, BLUE, RED, BLUE, RED, BLUE]).unwrap();
+
+        let part = sprite.crop(1, 0, 2, 2);
+        assert_eq!(part, Sprite::from_colors(2, 2, &[BLUE, RED, RED, BLUE]).unwrap());
+
+        let part = sprite.crop(2, 1, 2, 2);
+        assert_eq!(part, Sprite::from_colors(2, 2, &[BLUE, NONE, NONE, NONE]).unwrap());
+        assert_eq!(sprite.crop(5, 5, 1, 1).data, vec![0]);
+    }
+
+    #[test]
+    fn test_sample() {
+        let sprite = Sprite::from_colors(2, 1, &[Color::rgb(0, 0, 0), Color::rgb(200, 100, 0)]).unwrap();
+
+        assert_eq!(sprite.sample(1.9, 0.5, Filter::Nearest), Color::rgb(200, 100, 0));
+        assert_eq!(sprite.sample(2.1, 0.5, Filter::Nearest), NONE);
+        assert_eq!(sprite.sample(1.0, 0.5, Filter::Bilinear), Color::rgb(100, 50, 0));
+        assert_eq!(sprite.sample(0.2, 0.9, Filter::Bilinear), Color::rgb(0, 0, 0));
+        assert_eq!(sprite.sample(5.0, -3.0, Filter::Bilinear), Color::rgb(200, 100, 0));
+    }
+
+    #[test]
+    fn test_color_key() {
+        let mut sprite = Sprite::from_colors(2, 1, &[MAGENTA, RED]).unwrap();
+        sprite.color_key = Some(Color::rgba(255, 0, 255, 0));
+
+        assert!(sprite.is_keyed(MAGENTA.into()));
+        assert!(!sprite.is_keyed(RED.into()));
+        assert_eq!(sprite.sample(0.5, 0.5, Filter::Nearest), NONE);
+        assert_eq!(sprite.sample(1.5, 0.5, Filter::Nearest), RED);
+        assert_eq!(sprite.get_pixel(0, 0), Some(MAGENTA));
+        assert_eq!(sprite.crop(0, 0, 1, 1).color_key, sprite.color_key);
+    }
+
+    #[test]
+    fn test_set_pixel() {
+        let mut sprite = Sprite::new(4, 4);
+        sprite.set_pixel(1, 2, RED);
+        sprite.set_pixel(-1, 2, RED);
+        assert_eq!(sprite.get_pixel(1, 2), Some(RED));
+        assert_eq!(sprite.data.iter().filter(|&&p| p != 0).count(), 1);
+        assert_eq!(sprite.get_pixel(0, 0), Some(NONE));
+    }
+}
@@ -0,0 +1,91 @@
+use crate::core::{RainCore, Sprite};
+
+#[derive(Debug, PartialEq, Clone, Default)]
+/// Collects sprites during a frame and draws them sorted by depth
+///
+/// Sprites with a lower `z` are drawn first and end up behind sprites with a higher `z`,
+/// sprites with the same `z` are drawn in the order they were added
+///
+/// ### Example
+#[cfg_attr(feature = "png", doc = "```no_run")]
+#[cfg_attr(not(feature = "png"), doc = "```ignore")]
+/// # use rain2d::core::*;
+/// # let mut core = RainCore::init("example app", 640, 360, true);
+/// let tree = Sprite::from_png("assets/tree.png").unwrap();
+/// let player = Sprite::from_png("assets/player.png").unwrap();
+///
+/// let mut batch = SpriteBatch::new();
+/// // things lower on the screen are in front
+/// batch.add(&player, 100, 120, 120);
+/// batch.add(&tree, 90, 80, 80);
+/// batch.flush(&mut core);
+/// ```
+pub struct SpriteBatch<'a> {
+    entries: Vec<(i32, i32, i32, &'a Sprite)>,
+}
+
+impl<'a> SpriteBatch<'a> {
+    /// Creates an empty sprite batch
+    pub fn new() -> Self {
+        SpriteBatch { entries: Vec::new() }
+    }
+
+    /// Adds the sprite with its upper left corner at `(x, y)` and depth `z`
+    pub fn add(&mut self, sprite: &'a Sprite, x: i32, y: i32, z: i32) {
+        self.entries.push((z, x, y, sprite));
+    }
+
+    /// Gets the number of sprites waiting to be drawn
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Checks if there are no sprites waiting to be drawn
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Removes all sprites without drawing them
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Draws all sprites sorted by `z` using [`draw_sprite`] and empties the batch
+    ///
+    /// [`draw_sprite`]: struct.RainCore.html#method.draw_sprite
+    pub fn flush(&mut self, core: &mut RainCore) {
+        // stable sort keeps the submission order for equal depths
+        self.entries.sort_by_key(|&(z, ..)| z);
+        for (_, x, y, sprite) in self.entries.drain(..) {
+            core.draw_sprite(x, y, sprite);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::core::color::{RED, GREEN, BLUE};
+
+    #[test]
+    fn test_flush() {
+        let mut core = RainCore::init("", 4, 4, false);
+        let (red, green, blue) = (
+            Sprite::from_colors(1, 1, &[RED]).unwrap(),
+            Sprite::from_colors(1, 1, &[GREEN]).unwrap(),
+            Sprite::from_colors(1, 1, &[BLUE]).unwrap(),
+        );
+
+        let mut batch = SpriteBatch::new();
+        batch.add(&red, 0, 0, 5);
+        batch.add(&green, 0, 0, -1);
+        batch.add(&green, 1, 0, 2);
+        batch.add(&blue, 1, 0, 2);
+        assert_eq!(batch.len(), 4);
+
+        batch.flush(&mut core);
+        assert!(batch.is_empty());
+        assert_eq!(core.render_target.get_pixel(0, 0), Some(RED));
+        assert_eq!(core.render_target.get_pixel(1, 0), Some(BLUE));
+    }
+}
@@ -0,0 +1,109 @@
+use crate::core::{Color, Font};
+
+#[derive(Debug, PartialEq, Copy, Clone, Default)]
+/// Horizontal alignment of every line in a block of text
+pub enum TextAlign {
+    /// Lines start at the left edge
+    #[default]
+    Left,
+
+    /// Lines are centered between the left and right edge
+    Center,
+
+    /// Lines end at the right edge
+    Right,
+}
+
+#[derive(Debug, PartialEq, Copy, Clone, Default)]
+/// Vertical alignment of a block of text
+pub enum VerticalAlign {
+    /// The first line starts at the top edge
+    #[default]
+    Top,
+
+    /// The block is centered between the top and bottom edge
+    Middle,
+
+    /// The last line ends at the bottom edge
+    Bottom,
+}
+
+#[derive(Debug, PartialEq, Copy, Clone, Default)]
+/// Outline and drop shadow drawn behind text to keep it readable on busy backgrounds
+///
+/// ### Example
+/// ```no_run
+/// # use rain2d::core::*;
+/// # let mut core = RainCore::init("example app", 640, 360, true);
+/// let style = TextStyle { outline: Some((BLACK, 2)), shadow: Some((Color::rgba(0, 0, 0, 128), 3, 3)) };
+/// core.draw_text_styled(10, 10, "Level 1", &Font::builtin(), 32.0, &style, WHITE);
+/// ```
+pub struct TextStyle {
+    /// Color and thickness in pixels of the outline around every glyph
+    pub outline: Option<(Color, i32)>,
+
+    /// Color and offset in pixels of the shadow, the shadow includes the outline
+    pub shadow: Option<(Color, i32, i32)>,
+}
+
+// splits text into lines that fit in width, lines break after `\n`, at the last space that fits
+// and inside words that don't fit on a line by themselves. returns every line with its width
+pub fn wrap<'a>(text: &'a str, font: &Font, size: f32, width: f32) -> Vec<(&'a str, f32)> {
+    let measure = |line: &str| font.line_width(line, size);
+    let mut lines = Vec::new();
+    for paragraph in text.split('\n') {
+        // start of the current line and the last space in it
+        let (mut start, mut space, mut prev) = (0, None, None);
+        let mut line_width = 0.0;
+        for (i, c) in paragraph.char_indices() {
+            if c == ' ' {
+                space = Some(i);
+            }
+            line_width += font.advance(c, size) + prev.map_or(0.0, |prev| font.kerning(prev, c, size));
+            prev = Some(c);
+            // spaces are allowed to hang over the edge
+            if line_width <= width || c == ' ' || i == start { continue; }
+
+            let end = match space {
+                Some(space) if space > start => space,
+                _ => i,
+            };
+            let line = paragraph[start..end].trim_end();
+            lines.push((line, measure(line)));
+            start = if end == i { i } else { end + 1 };
+            space = None;
+            line_width = measure(&paragraph[start..i + c.len_utf8()]);
+        }
+        let line = paragraph[start..].trim_end();
+        lines.push((line, measure(line)));
+    }
+    lines
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn wrap_lines(text: &str, width: f32) -> Vec<&str> {
+        // every character of the built-in font is 6 pixels wide at size 8
+        wrap(text, &Font::builtin(), 8.0, width).into_iter().map(|(line, _)| line).collect()
+    }
+
+    #[test]
+    fn test_wrap() {
+        assert_eq!(wrap_lines("", 60.0), vec![""]);
+        assert_eq!(wrap_lines("hello world", 66.0), vec!["hello world"]);
+        assert_eq!(wrap_lines("hello world", 60.0), vec!["hello", "world"]);
+        assert_eq!(wrap_lines("a b c d e", 24.0), vec!["a b", "c d", "e"]);
+        assert_eq!(wrap_lines("one\n\ntwo", 60.0), vec!["one", "", "two"]);
+        assert_eq!(wrap("ab ", &Font::builtin(), 8.0, 60.0), vec![("ab", 12.0)]);
+    }
+
+    #[test]
+    fn test_wrap_long_words() {
+        assert_eq!(wrap_lines("abcdefg", 18.0), vec!["abc", "def", "g"]);
+        assert_eq!(wrap_lines("a bcdefg", 18.0), vec!["a", "bcd", "efg"]);
+        // at least one character per line so wrapping always finishes
+        assert_eq!(wrap_lines("ab", 0.0), vec!["a", "b"]);
+    }
+}
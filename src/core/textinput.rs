@@ -0,0 +1,63 @@
+// collects the characters typed between two calls of take_text_input
+
+use std::{cell::RefCell, rc::Rc};
+
+use minifb::InputCallback;
+
+// backspace is added when the key is pressed, some platforms also send it as a character
+pub const BACKSPACE: char = '\u{8}';
+
+#[derive(Clone, Default)]
+pub struct TextInput(pub Rc<RefCell<String>>);
+
+impl TextInput {
+    // gets the typed text, backspaces remove the character typed before them and are kept when
+    // there is nothing to remove
+    pub fn take(&self) -> String {
+        let input = self.0.replace(String::new());
+        let mut text = String::with_capacity(input.len());
+        for c in input.chars() {
+            if c == BACKSPACE && text.chars().last().is_some_and(|last| last != BACKSPACE) {
+                text.pop();
+            } else {
+                text.push(c);
+            }
+        }
+        text
+    }
+
+    pub fn push(&self, c: char) {
+        self.0.borrow_mut().push(c);
+    }
+}
+
+impl InputCallback for TextInput {
+    fn add_char(&mut self, uni_char: u32) {
+        if let Some(c) = char::from_u32(uni_char).filter(|c| !c.is_control()) {
+            self.push(c);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_take() {
+        let mut input = TextInput::default();
+        for c in "ab\u{1}c".chars() {
+            input.add_char(c as u32);
+        }
+        input.push(BACKSPACE);
+        input.add_char('é' as u32);
+        assert_eq!(input.take(), "abé");
+        assert_eq!(input.take(), "");
+
+        input.push(BACKSPACE);
+        input.push(BACKSPACE);
+        input.add_char('x' as u32);
+        input.push(BACKSPACE);
+        assert_eq!(input.take(), "\u{8}\u{8}");
+    }
+}
@@ -0,0 +1,194 @@
+//! Procedural texture generators
+//!
+//! Every generator overwrites all pixels of the render target, the blend mode is ignored.
+//! Generators that take a `seed` always produce the same texture for the same seed
+//!
+//! ### Example
+//! ```no_run
+//! # use rain2d::core::*;
+//! # let mut core = RainCore::init("example app", 640, 360, true);
+//! let mut grass = RenderTarget::new(64, 64);
+//! texture::value_noise(&mut grass, 8.0, 3, 42, Color::rgb(20, 80, 20), Color::rgb(60, 160, 40));
+//!
+//! core.fill_rect_pattern(0, 200, 640, 160, &FillPattern::Tile(&grass));
+//! ```
+
+use crate::core::{Color, GradientDirection, RenderTarget};
+
+/// Fills the render target with squares of `cell_size` pixels alternating between `a` and `b`,
+/// the upper left square is `a`
+pub fn checkerboard(target: &mut RenderTarget, cell_size: usize, a: Color, b: Color) {
+    let cell_size = cell_size.max(1);
+    generate(target, |x, y| if (x / cell_size + y / cell_size).is_multiple_of(2) { a } else { b });
+}
+
+/// Fills the render target with a linear gradient from `from` to `to`
+pub fn linear_gradient(target: &mut RenderTarget, from: Color, to: Color, direction: GradientDirection) {
+    let (width, height) = (target.width, target.height);
+    generate(target, |x, y| {
+        let (pos, len) = match direction {
+            GradientDirection::Horizontal => (x, width),
+            GradientDirection::Vertical => (y, height),
+        };
+        from.lerp(to, pos as f32 / (len.max(2) - 1) as f32)
+    });
+}
+
+/// Fills the render target with a radial gradient from `inner` at the center to `outer`
+/// at the corners
+pub fn radial_gradient(target: &mut RenderTarget, inner: Color, outer: Color) {
+    let (cx, cy) = (target.width as f32 / 2.0, target.height as f32 / 2.0);
+    let max = (cx * cx + cy * cy).sqrt().max(f32::EPSILON);
+    generate(target, |x, y| {
+        let (dx, dy) = (x as f32 + 0.5 - cx, y as f32 + 0.5 - cy);
+        inner.lerp(outer, (dx * dx + dy * dy).sqrt() / max)
+    });
+}
+
+/// Fills the render target with smooth value noise blending between `from` and `to`
+///
+/// `scale` is the size of the noise features in pixels, every extra octave adds detail
+/// at half the size and half the strength of the previous one
+pub fn value_noise(target: &mut RenderTarget, scale: f32, octaves: u32, seed: u32, from: Color, to: Color) {
+    let scale = scale.max(1.0);
+    let octaves = octaves.max(1);
+    generate(target, |x, y| {
+        let (mut total, mut amplitude, mut sum, mut frequency) = (0.0, 1.0, 0.0, 1.0 / scale);
+        for octave in 0..octaves {
+            let seed = seed.wrapping_add(octave);
+            total += amplitude * noise(x as f32 * frequency, y as f32 * frequency, seed);
+            sum += amplitude;
+            amplitude /= 2.0;
+            frequency *= 2.0;
+        }
+        from.lerp(to, total / sum)
+    });
+}
+
+/// Fills the render target with Voronoi cells around `cells` random points, every cell
+/// gets a color from `palette`
+///
+/// Pixels are shaded towards `edge` near the border between two cells, use a color
+/// with an alpha of 0 to disable the edges
+pub fn voronoi(target: &mut RenderTarget, cells: usize, seed: u32, palette: &[Color], edge: Color) {
+    if palette.is_empty() { return; }
+    let (width, height) = (target.width as f32, target.height as f32);
+    let points: Vec<(f32, f32)> = (0..cells.max(1) as u32)
+        .map(|i| (unit(hash(i, 0, seed)) * width, unit(hash(i, 1, seed)) * height))
+        .collect();
+
+    generate(target, |x, y| {
+        let (px, py) = (x as f32 + 0.5, y as f32 + 0.5);
+        let (mut closest, mut d1, mut d2) = (0, f32::MAX, f32::MAX);
+        for (i, &(cx, cy)) in points.iter().enumerate() {
+            let d = ((px - cx).powi(2) + (py - cy).powi(2)).sqrt();
+            if d < d1 {
+                d2 = d1;
+                d1 = d;
+                closest = i;
+            } else if d < d2 {
+                d2 = d;
+            }
+        }
+
+        let color = palette[closest % palette.len()];
+        // distance to the border is half the difference between the two closest points
+        let border = (d2 - d1) / 2.0;
+        if edge.a == 0 || border >= 2.0 { color } else { edge.lerp(color, border / 2.0) }
+    });
+}
+
+// overwrites every pixel with the color returned for its position
+fn generate<F: FnMut(usize, usize) -> Color>(target: &mut RenderTarget, mut color: F) {
+    let width = target.width;
+    for (i, p) in target.data.iter_mut().enumerate() {
+        *p = color(i % width, i / width).into();
+    }
+}
+
+// smoothly interpolated random values on a grid with a spacing of 1, between 0 and 1
+fn noise(x: f32, y: f32, seed: u32) -> f32 {
+    let (x0, y0) = (x.floor(), y.floor());
+    let (ix, iy) = (x0 as i32 as u32, y0 as i32 as u32);
+    let smooth = |t: f32| t * t * (3.0 - 2.0 * t);
+    let (tx, ty) = (smooth(x - x0), smooth(y - y0));
+
+    let value = |dx: u32, dy: u32| unit(hash(ix.wrapping_add(dx), iy.wrapping_add(dy), seed));
+    let top = value(0, 0) + (value(1, 0) - value(0, 0)) * tx;
+    let bottom = value(0, 1) + (value(1, 1) - value(0, 1)) * tx;
+    top + (bottom - top) * ty
+}
+
+// mixes the inputs into a pseudo random number
+fn hash(x: u32, y: u32, seed: u32) -> u32 {
+    let mut h = seed ^ x.wrapping_mul(0x27D4EB2D) ^ y.wrapping_mul(0x165667B1);
+    h = (h ^ (h >> 15)).wrapping_mul(0x85EBCA6B);
+    h = (h ^ (h >> 13)).wrapping_mul(0xC2B2AE35);
+    h ^ (h >> 16)
+}
+
+// maps a random number to a value between 0 and 1
+fn unit(h: u32) -> f32 {
+    (h >> 8) as f32 / (1 << 24) as f32
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::core::color::{WHITE, BLACK, RED, BLUE};
+
+    #[test]
+    fn test_checkerboard() {
+        let mut target = RenderTarget::new(4, 4);
+        checkerboard(&mut target, 2, WHITE, BLACK);
+        assert_eq!(target.get_pixel(1, 1), Some(WHITE));
+        assert_eq!(target.get_pixel(2, 1), Some(BLACK));
+        assert_eq!(target.get_pixel(1, 2), Some(BLACK));
+        assert_eq!(target.get_pixel(3, 3), Some(WHITE));
+    }
+
+    #[test]
+    fn test_gradients() {
+        let mut target = RenderTarget::new(3, 2);
+        linear_gradient(&mut target, BLACK, WHITE, GradientDirection::Horizontal);
+        assert_eq!(target.get_pixel(0, 1), Some(BLACK));
+        assert_eq!(target.get_pixel(1, 0), Some(Color::rgb(128, 128, 128)));
+        assert_eq!(target.get_pixel(2, 1), Some(WHITE));
+
+        linear_gradient(&mut target, BLACK, WHITE, GradientDirection::Vertical);
+        assert_eq!(target.get_pixel(2, 0), Some(BLACK));
+        assert_eq!(target.get_pixel(0, 1), Some(WHITE));
+
+        let mut target = RenderTarget::new(5, 5);
+        radial_gradient(&mut target, WHITE, BLACK);
+        assert_eq!(target.get_pixel(2, 2), Some(WHITE));
+        assert!(target.get_pixel(0, 0).unwrap().r < 64);
+    }
+
+    #[test]
+    fn test_value_noise() {
+        let mut a = RenderTarget::new(16, 16);
+        let mut b = RenderTarget::new(16, 16);
+        value_noise(&mut a, 4.0, 2, 7, BLACK, WHITE);
+        value_noise(&mut b, 4.0, 2, 7, BLACK, WHITE);
+        assert_eq!(a.data, b.data);
+
+        // neighbouring pixels are close to each other
+        let (p, q) = (a.get_pixel(5, 5).unwrap(), a.get_pixel(6, 5).unwrap());
+        assert!((p.r as i32 - q.r as i32).abs() < 64);
+
+        value_noise(&mut b, 4.0, 2, 8, BLACK, WHITE);
+        assert_ne!(a.data, b.data);
+    }
+
+    #[test]
+    fn test_voronoi() {
+        let mut target = RenderTarget::new(32, 32);
+        voronoi(&mut target, 4, 1, &[RED, BLUE], Color::rgba(0, 0, 0, 0));
+        assert!(target.data.iter().all(|&p| p == RED.into() || p == BLUE.into()));
+
+        // a single cell covers everything
+        voronoi(&mut target, 1, 1, &[RED], BLACK);
+        assert!(target.data.iter().all(|&p| p == RED.into()));
+    }
+}
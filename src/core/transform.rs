@@ -0,0 +1,145 @@
+#[derive(Debug, PartialEq, Copy, Clone)]
+/// 2D affine transformation matrix
+///
+/// Maps `(x, y)` to `(a * x + c * y + tx, b * x + d * y + ty)`
+///
+/// ### Example
+/// ```
+///# use rain2d::core::Transform;
+/// let t = Transform::scaling(2.0, 2.0).then(Transform::translation(10.0, 0.0));
+/// assert_eq!(t.apply(1.0, 1.0), (12.0, 2.0));
+/// ```
+pub struct Transform {
+    /// Horizontal scale
+    pub a: f32,
+
+    /// Vertical skew
+    pub b: f32,
+
+    /// Horizontal skew
+    pub c: f32,
+
+    /// Vertical scale
+    pub d: f32,
+
+    /// Horizontal translation
+    pub tx: f32,
+
+    /// Vertical translation
+    pub ty: f32,
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Transform::IDENTITY
+    }
+}
+
+impl Transform {
+    /// Transform that doesn't change anything
+    pub const IDENTITY: Transform = Transform { a: 1.0, b: 0.0, c: 0.0, d: 1.0, tx: 0.0, ty: 0.0 };
+
+    /// Creates a transform that moves by `(x, y)`
+    pub fn translation(x: f32, y: f32) -> Self {
+        Transform { tx: x, ty: y, ..Transform::IDENTITY }
+    }
+
+    /// Creates a transform that rotates around the origin, `angle` is in radians, increasing clockwise
+    pub fn rotation(angle: f32) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        Transform { a: cos, b: sin, c: -sin, d: cos, tx: 0.0, ty: 0.0 }
+    }
+
+    /// Creates a transform that scales relative to the origin
+    pub fn scaling(sx: f32, sy: f32) -> Self {
+        Transform { a: sx, d: sy, ..Transform::IDENTITY }
+    }
+
+    /// Combines two transforms, the result applies `self` first and then `next`
+    pub fn then(self, next: Transform) -> Self {
+        Transform {
+            a: next.a * self.a + next.c * self.b,
+            b: next.b * self.a + next.d * self.b,
+            c: next.a * self.c + next.c * self.d,
+            d: next.b * self.c + next.d * self.d,
+            tx: next.a * self.tx + next.c * self.ty + next.tx,
+            ty: next.b * self.tx + next.d * self.ty + next.ty,
+        }
+    }
+
+    /// Transforms a point
+    pub fn apply(&self, x: f32, y: f32) -> (f32, f32) {
+        (self.a * x + self.c * y + self.tx, self.b * x + self.d * y + self.ty)
+    }
+
+    /// Gets the transform that undoes this one, returns `None` if it can't be inverted
+    pub fn inverse(&self) -> Option<Transform> {
+        let det = self.a * self.d - self.b * self.c;
+        if det == 0.0 { return None; }
+
+        Some(Transform {
+            a: self.d / det,
+            b: -self.b / det,
+            c: -self.c / det,
+            d: self.a / det,
+            tx: (self.c * self.ty - self.d * self.tx) / det,
+            ty: (self.b * self.tx - self.a * self.ty) / det,
+        })
+    }
+
+    /// Checks if this is the identity transform
+    pub fn is_identity(&self) -> bool {
+        *self == Transform::IDENTITY
+    }
+
+    /// Gets how much lengths along the x and y axes are scaled
+    pub fn scale_factors(&self) -> (f32, f32) {
+        (f32::sqrt(self.a * self.a + self.b * self.b), f32::sqrt(self.c * self.c + self.d * self.d))
+    }
+
+    /// Gets the angle the x axis is rotated by in radians
+    pub fn rotation_angle(&self) -> f32 {
+        self.b.atan2(self.a)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn assert_near(a: (f32, f32), b: (f32, f32)) {
+        assert!((a.0 - b.0).abs() < 1e-4 && (a.1 - b.1).abs() < 1e-4, "{:?} != {:?}", a, b);
+    }
+
+    #[test]
+    fn test_identity() {
+        assert!(Transform::default().is_identity());
+        assert_eq!(Transform::IDENTITY.apply(3.0, 4.0), (3.0, 4.0));
+    }
+
+    #[test]
+    fn test_rotation() {
+        let t = Transform::rotation(std::f32::consts::FRAC_PI_2);
+        assert_near(t.apply(1.0, 0.0), (0.0, 1.0));
+        assert!((t.rotation_angle() - std::f32::consts::FRAC_PI_2).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_then() {
+        let t = Transform::translation(5.0, 0.0).then(Transform::rotation(std::f32::consts::FRAC_PI_2));
+        assert_near(t.apply(0.0, 0.0), (0.0, 5.0));
+
+        let t = Transform::scaling(2.0, 3.0).then(Transform::translation(1.0, 1.0));
+        assert_eq!(t.apply(1.0, 1.0), (3.0, 4.0));
+        assert_eq!(t.scale_factors(), (2.0, 3.0));
+    }
+
+    #[test]
+    fn test_inverse() {
+        let t = Transform::rotation(0.7).then(Transform::scaling(2.0, 0.5)).then(Transform::translation(3.0, -2.0));
+        let p = t.apply(4.0, 5.0);
+        assert_near(t.inverse().unwrap().apply(p.0, p.1), (4.0, 5.0));
+
+        assert_eq!(Transform::scaling(0.0, 1.0).inverse(), None);
+    }
+}
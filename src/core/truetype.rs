@@ -0,0 +1,480 @@
+// truetype font parser, reads glyph outlines, metrics and the character map
+
+use crate::core::curve::{flatten_quad, Point};
+use crate::core::{FontError, Transform};
+
+const INVALID: FontError = FontError::Invalid("corrupt font file");
+
+// composite glyphs can reference other composite glyphs, this limits the nesting
+const MAX_COMPONENT_DEPTH: u32 = 8;
+
+#[derive(Debug, Clone)]
+pub struct TrueType {
+    data: Vec<u8>,
+    units_per_em: f32,
+    long_loca: bool,
+    num_glyphs: u16,
+    num_h_metrics: u16,
+    ascent: f32,
+    descent: f32,
+    line_gap: f32,
+    cmap: usize,
+    loca: usize,
+    glyf: usize,
+    hmtx: usize,
+}
+
+impl TrueType {
+    pub fn parse(data: Vec<u8>) -> Result<Self, FontError> {
+        match data.get(..4) {
+            Some([0, 1, 0, 0]) | Some(b"true") => (),
+            Some(b"OTTO") => return Err(FontError::Unsupported("opentype font with cff outlines")),
+            Some(b"ttcf") => return Err(FontError::Unsupported("font collection")),
+            _ => return Err(FontError::Invalid("not a truetype font")),
+        }
+
+        let table = |tag: &[u8; 4]| -> Result<usize, FontError> {
+            let count = u16_at(&data, 4)? as usize;
+            for i in 0..count {
+                let record = 12 + i * 16;
+                if data.get(record..record + 4).ok_or(INVALID)? == tag {
+                    return Ok(u32_at(&data, record + 8)? as usize);
+                }
+            }
+            Err(FontError::Invalid("font is missing a required table"))
+        };
+
+        let head = table(b"head")?;
+        let hhea = table(b"hhea")?;
+        let maxp = table(b"maxp")?;
+        let font = TrueType {
+            units_per_em: u16_at(&data, head + 18)?.max(1) as f32,
+            long_loca: i16_at(&data, head + 50)? != 0,
+            num_glyphs: u16_at(&data, maxp + 4)?,
+            num_h_metrics: u16_at(&data, hhea + 34)?,
+            ascent: i16_at(&data, hhea + 4)? as f32,
+            descent: i16_at(&data, hhea + 6)? as f32,
+            line_gap: i16_at(&data, hhea + 8)? as f32,
+            cmap: find_cmap(&data, table(b"cmap")?)?,
+            loca: table(b"loca")?,
+            glyf: table(b"glyf")?,
+            hmtx: table(b"hmtx")?,
+            data,
+        };
+        if font.num_h_metrics == 0 { return Err(INVALID); }
+        Ok(font)
+    }
+
+    pub fn units_per_em(&self) -> f32 {
+        self.units_per_em
+    }
+
+    // distance from the baseline to the top of the highest and bottom of the lowest glyph,
+    // the descent is negative
+    pub fn ascent(&self) -> f32 {
+        self.ascent
+    }
+
+    pub fn descent(&self) -> f32 {
+        self.descent
+    }
+
+    pub fn line_gap(&self) -> f32 {
+        self.line_gap
+    }
+
+    // gets the glyph for the character, 0 is the glyph for missing characters
+    pub fn glyph_index(&self, c: char) -> u16 {
+        self.lookup(c as u32).unwrap_or(0)
+    }
+
+    // horizontal distance to the next glyph in font units
+    pub fn advance(&self, glyph: u16) -> f32 {
+        let i = glyph.min(self.num_h_metrics - 1) as usize;
+        u16_at(&self.data, self.hmtx + i * 4).unwrap_or(0) as f32
+    }
+
+    // gets the contours of the glyph mapped by the transform and flattened into lines,
+    // font units have y going up
+    pub fn outline(&self, glyph: u16, transform: Transform) -> Vec<Vec<Point>> {
+        let mut contours = Vec::new();
+        // broken glyphs are drawn as empty instead of failing the whole text
+        let _ = self.glyph_contours(glyph, transform, &mut contours, 0);
+        contours
+    }
+
+    fn lookup(&self, c: u32) -> Result<u16, FontError> {
+        let data = &self.data;
+        let table = self.cmap;
+        match u16_at(data, table)? {
+            4 => {
+                if c > 0xFFFF { return Ok(0); }
+                let seg_count = u16_at(data, table + 6)? as usize / 2;
+                let ends = table + 14;
+                let starts = ends + seg_count * 2 + 2;
+                let deltas = starts + seg_count * 2;
+                let range_offsets = deltas + seg_count * 2;
+
+                for i in 0..seg_count {
+                    if (u16_at(data, ends + i * 2)? as u32) < c { continue; }
+                    let start = u16_at(data, starts + i * 2)? as u32;
+                    if start > c { return Ok(0); }
+
+                    let delta = u16_at(data, deltas + i * 2)?;
+                    let range_offset = u16_at(data, range_offsets + i * 2)? as usize;
+                    if range_offset == 0 {
+                        return Ok((c as u16).wrapping_add(delta));
+                    }
+                    // the offset is relative to its own location in the table
+                    let at = range_offsets + i * 2 + range_offset + (c - start) as usize * 2;
+                    let glyph = u16_at(data, at)?;
+                    return Ok(if glyph == 0 { 0 } else { glyph.wrapping_add(delta) });
+                }
+                Ok(0)
+            }
+            12 => {
+                let groups = u32_at(data, table + 12)? as usize;
+                for i in 0..groups {
+                    let group = table + 16 + i * 12;
+                    let (start, end) = (u32_at(data, group)?, u32_at(data, group + 4)?);
+                    if (start..=end).contains(&c) {
+                        return Ok((u32_at(data, group + 8)? + c - start) as u16);
+                    }
+                }
+                Ok(0)
+            }
+            _ => Ok(0),
+        }
+    }
+
+    // gets the location of the glyph data, returns None for glyphs without an outline
+    fn glyph_range(&self, glyph: u16) -> Result<Option<(usize, usize)>, FontError> {
+        if glyph >= self.num_glyphs { return Ok(None); }
+        let i = glyph as usize;
+        let (start, end) = if self.long_loca {
+            (u32_at(&self.data, self.loca + i * 4)? as usize, u32_at(&self.data, self.loca + i * 4 + 4)? as usize)
+        } else {
+            (u16_at(&self.data, self.loca + i * 2)? as usize * 2, u16_at(&self.data, self.loca + i * 2 + 2)? as usize * 2)
+        };
+        if start >= end { return Ok(None); }
+        Ok(Some((self.glyf + start, self.glyf + end)))
+    }
+
+    fn glyph_contours(&self, glyph: u16, transform: Transform, contours: &mut Vec<Vec<Point>>, depth: u32)
+        -> Result<(), FontError> {
+        let (start, end) = match self.glyph_range(glyph)? {
+            Some(range) => range,
+            None => return Ok(()),
+        };
+        let data = self.data.get(start..end).ok_or(INVALID)?;
+        let num_contours = i16_at(data, 0)?;
+        if num_contours >= 0 {
+            simple_glyph(data, num_contours as usize, transform, contours)
+        } else if depth < MAX_COMPONENT_DEPTH {
+            self.composite_glyph(data, transform, contours, depth)
+        } else {
+            Err(INVALID)
+        }
+    }
+
+    fn composite_glyph(&self, data: &[u8], transform: Transform, contours: &mut Vec<Vec<Point>>, depth: u32)
+        -> Result<(), FontError> {
+        const ARG_WORDS: u16 = 0x0001;
+        const ARGS_ARE_XY: u16 = 0x0002;
+        const SCALE: u16 = 0x0008;
+        const MORE_COMPONENTS: u16 = 0x0020;
+        const XY_SCALE: u16 = 0x0040;
+        const TWO_BY_TWO: u16 = 0x0080;
+
+        let mut pos = 10;
+        loop {
+            let flags = u16_at(data, pos)?;
+            let glyph = u16_at(data, pos + 2)?;
+            pos += 4;
+
+            let (dx, dy) = if flags & ARG_WORDS != 0 {
+                pos += 4;
+                (i16_at(data, pos - 4)? as f32, i16_at(data, pos - 2)? as f32)
+            } else {
+                pos += 2;
+                (*data.get(pos - 2).ok_or(INVALID)? as i8 as f32, *data.get(pos - 1).ok_or(INVALID)? as i8 as f32)
+            };
+            // matching points instead of offsets is rare enough to place the component unmoved
+            let (dx, dy) = if flags & ARGS_ARE_XY != 0 { (dx, dy) } else { (0.0, 0.0) };
+
+            let f2dot14 = |at: usize| -> Result<f32, FontError> { Ok(i16_at(data, at)? as f32 / 16384.0) };
+            let (mut a, mut b, mut c, mut d) = (1.0, 0.0, 0.0, 1.0);
+            if flags & SCALE != 0 {
+                a = f2dot14(pos)?;
+                d = a;
+                pos += 2;
+            } else if flags & XY_SCALE != 0 {
+                a = f2dot14(pos)?;
+                d = f2dot14(pos + 2)?;
+                pos += 4;
+            } else if flags & TWO_BY_TWO != 0 {
+                a = f2dot14(pos)?;
+                b = f2dot14(pos + 2)?;
+                c = f2dot14(pos + 4)?;
+                d = f2dot14(pos + 6)?;
+                pos += 8;
+            }
+
+            let component = Transform { a, b, c, d, tx: dx, ty: dy }.then(transform);
+            self.glyph_contours(glyph, component, contours, depth + 1)?;
+            if flags & MORE_COMPONENTS == 0 { return Ok(()); }
+        }
+    }
+}
+
+// picks the unicode character map, prefers the one that covers characters outside of the bmp
+fn find_cmap(data: &[u8], cmap: usize) -> Result<usize, FontError> {
+    let count = u16_at(data, cmap + 2)? as usize;
+    let mut best = None;
+    for i in 0..count {
+        let record = cmap + 4 + i * 8;
+        let (platform, encoding) = (u16_at(data, record)?, u16_at(data, record + 2)?);
+        let table = cmap + u32_at(data, record + 4)? as usize;
+        let format = u16_at(data, table)?;
+        let unicode = platform == 0 || (platform == 3 && (encoding == 1 || encoding == 10));
+        match format {
+            12 if unicode => return Ok(table),
+            4 if unicode => best = Some(table),
+            _ => (),
+        }
+    }
+    best.ok_or(FontError::Unsupported("font without a unicode character map"))
+}
+
+fn simple_glyph(data: &[u8], num_contours: usize, transform: Transform, contours: &mut Vec<Vec<Point>>)
+    -> Result<(), FontError> {
+    const ON_CURVE: u8 = 0x01;
+    const X_SHORT: u8 = 0x02;
+    const Y_SHORT: u8 = 0x04;
+    const REPEAT: u8 = 0x08;
+    const X_SAME_OR_POSITIVE: u8 = 0x10;
+    const Y_SAME_OR_POSITIVE: u8 = 0x20;
+
+    let mut ends = Vec::with_capacity(num_contours);
+    for i in 0..num_contours {
+        ends.push(u16_at(data, 10 + i * 2)? as usize);
+    }
+    let num_points = match ends.last() {
+        Some(&last) => last + 1,
+        None => return Ok(()),
+    };
+    let instructions = u16_at(data, 10 + num_contours * 2)? as usize;
+    let mut pos = 12 + num_contours * 2 + instructions;
+
+    let mut flags = Vec::with_capacity(num_points);
+    while flags.len() < num_points {
+        let flag = *data.get(pos).ok_or(INVALID)?;
+        pos += 1;
+        flags.push(flag);
+        if flag & REPEAT != 0 {
+            let count = *data.get(pos).ok_or(INVALID)?;
+            pos += 1;
+            for _ in 0..count {
+                flags.push(flag);
+            }
+        }
+    }
+    flags.truncate(num_points);
+
+    // coordinates are stored as deltas, first all x and then all y
+    let mut read = |short: u8, same_or_positive: u8| -> Result<Vec<f32>, FontError> {
+        let mut values = Vec::with_capacity(num_points);
+        let mut value = 0i32;
+        for &flag in &flags {
+            if flag & short != 0 {
+                let delta = *data.get(pos).ok_or(INVALID)? as i32;
+                pos += 1;
+                value += if flag & same_or_positive != 0 { delta } else { -delta };
+            } else if flag & same_or_positive == 0 {
+                value += i16_at(data, pos)? as i32;
+                pos += 2;
+            }
+            values.push(value as f32);
+        }
+        Ok(values)
+    };
+    let xs = read(X_SHORT, X_SAME_OR_POSITIVE)?;
+    let ys = read(Y_SHORT, Y_SAME_OR_POSITIVE)?;
+
+    let mut start = 0;
+    for &end in &ends {
+        if end < start || end >= num_points { return Err(INVALID); }
+        let points: Vec<(Point, bool)> = (start..=end)
+            .map(|i| (transform.apply(xs[i], ys[i]), flags[i] & ON_CURVE != 0))
+            .collect();
+        start = end + 1;
+        if points.len() >= 2 {
+            contours.push(flatten_contour(&points));
+        }
+    }
+    Ok(())
+}
+
+// converts on and off curve points into lines, two off curve points in a row have an
+// implied on curve point between them
+fn flatten_contour(points: &[(Point, bool)]) -> Vec<Point> {
+    let mid = |a: Point, b: Point| ((a.0 + b.0) / 2.0, (a.1 + b.1) / 2.0);
+    let n = points.len();
+
+    // start at an on curve point, or between two off curve points
+    let first_on = points.iter().position(|p| p.1);
+    let (start, offset) = match first_on {
+        Some(i) => (points[i].0, i),
+        None => (mid(points[0].0, points[1].0), 0),
+    };
+
+    let mut out = vec![start];
+    let mut control: Option<Point> = None;
+    let mut current = start;
+    for k in 1..=n {
+        let (p, on) = points[(offset + k) % n];
+        match (on, control) {
+            (true, None) => {
+                out.push(p);
+                current = p;
+            }
+            (true, Some(c)) => {
+                flatten_quad(current, c, p, &mut out);
+                current = p;
+                control = None;
+            }
+            (false, None) => control = Some(p),
+            (false, Some(c)) => {
+                let m = mid(c, p);
+                flatten_quad(current, c, m, &mut out);
+                current = m;
+                control = Some(p);
+            }
+        }
+    }
+    // the last point is the start point again
+    if let Some(c) = control {
+        flatten_quad(current, c, start, &mut out);
+    }
+    out.pop();
+    out
+}
+
+fn u16_at(data: &[u8], i: usize) -> Result<u16, FontError> {
+    let b = data.get(i..i + 2).ok_or(INVALID)?;
+    Ok(u16::from_be_bytes([b[0], b[1]]))
+}
+
+fn i16_at(data: &[u8], i: usize) -> Result<i16, FontError> {
+    u16_at(data, i).map(|v| v as i16)
+}
+
+fn u32_at(data: &[u8], i: usize) -> Result<u32, FontError> {
+    let b = data.get(i..i + 4).ok_or(INVALID)?;
+    Ok(u32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+}
+
+// builds a font with a 100 by 100 square for 'A' and the same square moved right by 50 as a
+// composite glyph for 'B', 100 units per em
+#[cfg(test)]
+pub fn test_font() -> Vec<u8> {
+    fn be16(out: &mut Vec<u8>, values: &[i32]) {
+        for &v in values {
+            out.extend_from_slice(&(v as u16).to_be_bytes());
+        }
+    }
+
+    let mut cmap = Vec::new();
+    be16(&mut cmap, &[0, 1, 3, 1, 0, 12]);
+    be16(&mut cmap, &[4, 32, 0, 4, 4, 1, 0]);
+    be16(&mut cmap, &[66, 0xFFFF, 0, 65, 0xFFFF, 1 - 65, 1, 0, 0]);
+
+    let mut square = Vec::new();
+    be16(&mut square, &[1, 0, 0, 100, 100, 3, 0]);
+    square.extend_from_slice(&[1; 4]);
+    be16(&mut square, &[0, 100, 0, -100, 0, 0, 100, 0]);
+    let mut composite = Vec::new();
+    be16(&mut composite, &[-1, 50, 0, 150, 100, 0x0003, 1, 50, 0]);
+    let glyf = [square.clone(), composite.clone()].concat();
+
+    let mut loca = Vec::new();
+    be16(&mut loca, &[0, 0, square.len() as i32 / 2, (square.len() + composite.len()) as i32 / 2]);
+
+    let mut head = vec![0; 54];
+    head[18..20].copy_from_slice(&100u16.to_be_bytes());
+    let mut hhea = vec![0; 36];
+    hhea[4..6].copy_from_slice(&80i16.to_be_bytes());
+    hhea[6..8].copy_from_slice(&(-20i16).to_be_bytes());
+    hhea[34..36].copy_from_slice(&3u16.to_be_bytes());
+    let mut hmtx = Vec::new();
+    be16(&mut hmtx, &[50, 0, 120, 0, 170, 0]);
+    let mut maxp = Vec::new();
+    be16(&mut maxp, &[0, 0x5000, 3]);
+
+    let tables: [(&[u8; 4], Vec<u8>); 7] = [
+        (b"cmap", cmap), (b"glyf", glyf), (b"head", head), (b"hhea", hhea),
+        (b"hmtx", hmtx), (b"loca", loca), (b"maxp", maxp),
+    ];
+    let mut out = Vec::new();
+    be16(&mut out, &[1, 0, tables.len() as i32, 0, 0, 0]);
+    let mut offset = 12 + tables.len() * 16;
+    for (tag, data) in &tables {
+        out.extend_from_slice(*tag);
+        out.extend_from_slice(&[0; 4]);
+        out.extend_from_slice(&(offset as u32).to_be_bytes());
+        out.extend_from_slice(&(data.len() as u32).to_be_bytes());
+        offset += data.len();
+    }
+    for (_, data) in &tables {
+        out.extend_from_slice(data);
+    }
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let font = TrueType::parse(test_font()).unwrap();
+        assert_eq!(font.units_per_em(), 100.0);
+        assert_eq!((font.ascent(), font.descent(), font.line_gap()), (80.0, -20.0, 0.0));
+        assert_eq!(font.glyph_index('A'), 1);
+        assert_eq!(font.glyph_index('B'), 2);
+        assert_eq!(font.glyph_index('C'), 0);
+        assert_eq!(font.glyph_index('😀'), 0);
+        assert_eq!(font.advance(1), 120.0);
+    }
+
+    #[test]
+    fn test_outline() {
+        let font = TrueType::parse(test_font()).unwrap();
+        let square = vec![(0.0, 0.0), (100.0, 0.0), (100.0, 100.0), (0.0, 100.0)];
+        assert_eq!(font.outline(1, Transform::IDENTITY), vec![square]);
+
+        let moved = vec![(25.0, 0.0), (75.0, 0.0), (75.0, -50.0), (25.0, -50.0)];
+        assert_eq!(font.outline(2, Transform::scaling(0.5, -0.5)), vec![moved]);
+        assert!(font.outline(0, Transform::IDENTITY).is_empty());
+    }
+
+    #[test]
+    fn test_flatten_contour() {
+        // off curve points in a row have an implied point between them
+        let points = [((0.0, 0.0), false), ((10.0, 0.0), false), ((10.0, 10.0), false), ((0.0, 10.0), false)];
+        let contour = flatten_contour(&points);
+        assert!(contour.contains(&(5.0, 0.0)));
+        assert!(contour.contains(&(0.0, 5.0)));
+        assert!(contour.len() > 4);
+        assert!(contour.iter().all(|&(x, y)| (0.0..=10.0).contains(&x) && (0.0..=10.0).contains(&y)));
+    }
+
+    #[test]
+    fn test_invalid() {
+        assert!(TrueType::parse(b"not a font".to_vec()).is_err());
+        assert!(matches!(TrueType::parse(b"OTTO1234".to_vec()), Err(FontError::Unsupported(_))));
+        let mut truncated = test_font();
+        truncated.truncate(100);
+        assert!(TrueType::parse(truncated).is_err());
+    }
+}
@@ -0,0 +1,611 @@
+#[cfg(target_os = "linux")]
+use std::{env, ffi::CStr, ptr};
+
+use minifb::{Key, Window, WindowOptions};
+#[cfg(target_os = "linux")]
+use raw_window_handle::{HasRawWindowHandle, RawWindowHandle};
+#[cfg(target_os = "linux")]
+use x11_dl::xlib::{self, Xlib};
+
+#[cfg(target_os = "linux")]
+use crate::core::input::KEYS;
+use crate::core::WindowScale;
+
+#[derive(Debug, Copy, Clone)]
+/// Options used when the window is created, pass them to [`init_with_config`]
+///
+/// [`init_with_config`]: struct.RainCore.html#method.init_with_config
+///
+/// ### Example
+/// ```no_run
+/// # use rain2d::core::*;
+/// // pixel art game shown at four times its size
+/// let config = WindowConfig { resizable: false, scale: WindowScale::X4, ..WindowConfig::default() };
+/// let mut core = RainCore::init_with_config("example app", 320, 180, true, config);
+/// ```
+pub struct WindowConfig {
+    /// Allows the window to be resized, see [`on_resize`](trait.RainApp.html#method.on_resize)
+    pub resizable: bool,
+
+    /// Removes the title bar and border
+    pub borderless: bool,
+
+    /// Keeps the window above all other windows
+    pub topmost: bool,
+
+    /// Shows every pixel of the screen as a block of pixels, `WindowScale::FitScreen` picks the
+    /// largest size that fits on the desktop
+    pub scale: WindowScale,
+
+    /// Gives every pixel of the display its own screen pixel on desktops that scale windows up,
+    /// instead of stretching the screen by the
+    /// [scale factor](struct.RainCore.html#method.get_scale_factor). The window size is then
+    /// measured in pixels of the display, so the same size looks smaller on high dpi displays.
+    /// Supported on macos and windows, X11 never stretches windows and wayland is always stretched
+    pub native_resolution: bool,
+}
+
+impl Default for WindowConfig {
+    fn default() -> Self {
+        WindowConfig {
+            resizable: true,
+            borderless: false,
+            topmost: false,
+            scale: WindowScale::X1,
+            native_resolution: false,
+        }
+    }
+}
+
+impl WindowConfig {
+    pub(crate) fn options(&self) -> WindowOptions {
+        WindowOptions {
+            resize: self.resizable,
+            borderless: self.borderless,
+            title: !self.borderless,
+            topmost: self.topmost,
+            scale: self.scale,
+            ..WindowOptions::default()
+        }
+    }
+}
+
+// runs f with libX11, which is loaded once per thread, None when it isn't installed
+#[cfg(target_os = "linux")]
+pub fn with_xlib<T>(f: impl FnOnce(&Xlib) -> Option<T>) -> Option<T> {
+    thread_local! {
+        static XLIB: Option<Xlib> = Xlib::open().ok();
+    }
+    XLIB.with(|xlib| xlib.as_ref().and_then(f))
+}
+
+// the X11 connection and id of the window, None for wayland windows
+#[cfg(target_os = "linux")]
+pub fn xlib_window(window: &Window) -> Option<(*mut xlib::Display, xlib::Window)> {
+    match window.raw_window_handle() {
+        RawWindowHandle::Xlib(handle) if !handle.display.is_null() => {
+            Some((handle.display as *mut xlib::Display, handle.window))
+        }
+        _ => None,
+    }
+}
+
+// gets the size of the X11 screen the window is on, wayland has no way to ask for it. Without a
+// window minifb picks wayland when WAYLAND_DISPLAY is set, otherwise a short lived connection to
+// the X server is used
+#[cfg(target_os = "linux")]
+pub fn desktop_size(window: Option<&Window>) -> Option<(usize, usize)> {
+    with_xlib(|xlib| unsafe {
+        let screen_size = |display| {
+            let screen = (xlib.XDefaultScreen)(display);
+            ((xlib.XDisplayWidth)(display, screen), (xlib.XDisplayHeight)(display, screen))
+        };
+        let (width, height) = match window {
+            Some(window) => screen_size(xlib_window(window)?.0),
+            None if env::var_os("WAYLAND_DISPLAY").is_some() => return None,
+            None => {
+                let display = (xlib.XOpenDisplay)(ptr::null());
+                if display.is_null() { return None; }
+                let size = screen_size(display);
+                (xlib.XCloseDisplay)(display);
+                size
+            }
+        };
+        Some((width as usize, height as usize)).filter(|_| width > 0 && height > 0)
+    })
+}
+
+// gets the size of the primary monitor
+#[cfg(windows)]
+pub fn desktop_size(_window: Option<&Window>) -> Option<(usize, usize)> {
+    use winapi::um::winuser::{GetSystemMetrics, SM_CXSCREEN, SM_CYSCREEN};
+
+    let (width, height) = unsafe { (GetSystemMetrics(SM_CXSCREEN), GetSystemMetrics(SM_CYSCREEN)) };
+    Some((width as usize, height as usize)).filter(|_| width > 0 && height > 0)
+}
+
+#[cfg(target_os = "macos")]
+mod cg {
+    use std::os::raw::c_void;
+
+    #[repr(C)]
+    pub struct CGPoint {
+        pub x: f64,
+        pub y: f64,
+    }
+
+    #[repr(C)]
+    pub struct CGSize {
+        pub width: f64,
+        pub height: f64,
+    }
+
+    #[repr(C)]
+    pub struct CGRect {
+        pub origin: CGPoint,
+        pub size: CGSize,
+    }
+
+    #[link(name = "CoreGraphics", kind = "framework")]
+    extern "C" {
+        pub fn CGMainDisplayID() -> u32;
+        pub fn CGDisplayBounds(display: u32) -> CGRect;
+        pub fn CGEventCreate(source: *const c_void) -> *const c_void;
+        pub fn CGEventGetLocation(event: *const c_void) -> CGPoint;
+        pub fn CGWarpMouseCursorPosition(point: CGPoint) -> i32;
+        pub fn CGAssociateMouseAndMouseCursorPosition(connected: u32) -> i32;
+    }
+
+    #[link(name = "CoreFoundation", kind = "framework")]
+    extern "C" {
+        pub fn CFRelease(object: *const c_void);
+    }
+}
+
+// gets the size of the main display in points, the same unit minifb sizes windows in
+#[cfg(target_os = "macos")]
+pub fn desktop_size(_window: Option<&Window>) -> Option<(usize, usize)> {
+    let bounds = unsafe { cg::CGDisplayBounds(cg::CGMainDisplayID()) };
+    let (width, height) = (bounds.size.width.round(), bounds.size.height.round());
+    Some((width as usize, height as usize)).filter(|_| width > 0.0 && height > 0.0)
+}
+
+#[cfg(not(any(target_os = "linux", windows, target_os = "macos")))]
+pub fn desktop_size(_window: Option<&Window>) -> Option<(usize, usize)> {
+    None
+}
+
+// gets how much the desktop scales windows up, from GDK_SCALE or the font dpi of X11 where 96 is
+// unscaled. The dpi comes from the resources the X server sent when the window connected, wayland
+// has no cheap way to tell
+#[cfg(target_os = "linux")]
+pub fn scale_factor(window: Option<&Window>) -> f32 {
+    let gdk_scale = env::var("GDK_SCALE").ok().and_then(|scale| scale.trim().parse::<f32>().ok());
+    gdk_scale.filter(|&scale| scale > 0.0)
+        .or_else(|| with_xlib(|xlib| unsafe {
+            let resources = (xlib.XResourceManagerString)(xlib_window(window?)?.0);
+            if resources.is_null() { return None; }
+            parse_xft_dpi(&CStr::from_ptr(resources).to_string_lossy()).map(|dpi| dpi / 96.0)
+        }))
+        .unwrap_or(1.0)
+}
+
+// gets the dpi of the desktop over 96. Windows lies about the size of the display to processes
+// that aren't dpi aware, comparing it to the real size of the primary monitor gives the scale
+#[cfg(windows)]
+pub fn scale_factor(_window: Option<&Window>) -> f32 {
+    use std::{mem, ptr};
+    use winapi::um::{wingdi::{GetDeviceCaps, DEVMODEW, LOGPIXELSX}, winuser::*};
+
+    unsafe {
+        if IsProcessDPIAware() != 0 {
+            let dc = GetDC(ptr::null_mut());
+            if dc.is_null() { return 1.0; }
+            let dpi = GetDeviceCaps(dc, LOGPIXELSX);
+            ReleaseDC(ptr::null_mut(), dc);
+            return if dpi > 0 { dpi as f32 / 96.0 } else { 1.0 };
+        }
+        let mut mode: DEVMODEW = mem::zeroed();
+        mode.dmSize = mem::size_of::<DEVMODEW>() as u16;
+        let width = GetSystemMetrics(SM_CXSCREEN);
+        if EnumDisplaySettingsW(ptr::null(), ENUM_CURRENT_SETTINGS, &mut mode) == 0 || width <= 0 {
+            return 1.0;
+        }
+        (mode.dmPelsWidth as f32 / width as f32).max(1.0)
+    }
+}
+
+#[cfg(target_os = "macos")]
+pub mod objc {
+    use std::os::raw::{c_char, c_void};
+
+    pub type Id = *mut c_void;
+
+    #[link(name = "AppKit", kind = "framework")]
+    extern "C" {}
+
+    #[link(name = "objc")]
+    extern "C" {
+        pub fn objc_getClass(name: *const c_char) -> Id;
+        pub fn sel_registerName(name: *const c_char) -> Id;
+        pub fn object_getClass(object: Id) -> Id;
+        pub fn class_addMethod(class: Id, selector: Id, imp: *const c_void, types: *const c_char) -> i8;
+        pub fn objc_msgSend();
+    }
+
+    // sends a message without arguments, T has to match what the method returns
+    pub unsafe fn send<T>(receiver: Id, selector: &[u8]) -> T {
+        let send: unsafe extern "C" fn(Id, Id) -> T = std::mem::transmute(objc_msgSend as unsafe extern "C" fn());
+        send(receiver, selector_named(selector))
+    }
+
+    // sends a message with one argument
+    pub unsafe fn send1<A, T>(receiver: Id, selector: &[u8], a: A) -> T {
+        let send: unsafe extern "C" fn(Id, Id, A) -> T = std::mem::transmute(objc_msgSend as unsafe extern "C" fn());
+        send(receiver, selector_named(selector), a)
+    }
+
+    // sends a message with two arguments
+    pub unsafe fn send2<A, B, T>(receiver: Id, selector: &[u8], a: A, b: B) -> T {
+        let send: unsafe extern "C" fn(Id, Id, A, B) -> T = std::mem::transmute(objc_msgSend as unsafe extern "C" fn());
+        send(receiver, selector_named(selector), a, b)
+    }
+
+    // names are nul terminated byte strings like b"count\0"
+    pub unsafe fn selector_named(name: &[u8]) -> Id {
+        sel_registerName(name.as_ptr() as *const c_char)
+    }
+
+    pub unsafe fn class_named(name: &[u8]) -> Id {
+        objc_getClass(name.as_ptr() as *const c_char)
+    }
+}
+
+// gets the backing scale of the window, or of the main screen before there is a window
+#[cfg(target_os = "macos")]
+pub fn scale_factor(window: Option<&Window>) -> f32 {
+    use raw_window_handle::{HasRawWindowHandle, RawWindowHandle};
+
+    unsafe {
+        let target = match window.map(|window| window.raw_window_handle()) {
+            Some(RawWindowHandle::MacOS(handle)) => handle.ns_window as objc::Id,
+            _ => objc::send(objc::class_named(b"NSScreen\0"), b"mainScreen\0"),
+        };
+        if target.is_null() { return 1.0; }
+        let scale: f64 = objc::send(target, b"backingScaleFactor\0");
+        if scale > 0.0 { scale as f32 } else { 1.0 }
+    }
+}
+
+#[cfg(not(any(target_os = "linux", windows, target_os = "macos")))]
+pub fn scale_factor(_window: Option<&Window>) -> f32 {
+    1.0
+}
+
+// prepares the process for windows that aren't stretched by the desktop, windows has to be told
+// before the window is created. Returns how many pixels of the display a window pixel covers,
+// macos keeps measuring windows in points
+#[cfg(windows)]
+pub fn enable_native_resolution(_window: Option<&Window>) -> f32 {
+    unsafe { winapi::um::winuser::SetProcessDPIAware() };
+    1.0
+}
+
+#[cfg(target_os = "macos")]
+pub fn enable_native_resolution(window: Option<&Window>) -> f32 {
+    scale_factor(window)
+}
+
+#[cfg(not(any(windows, target_os = "macos")))]
+pub fn enable_native_resolution(_window: Option<&Window>) -> f32 {
+    1.0
+}
+
+// checks if the cursor can be confined to the window, only X11 can on linux. Without a window
+// minifb picks wayland when WAYLAND_DISPLAY is set
+#[cfg(target_os = "linux")]
+pub fn can_grab_cursor(window: Option<&Window>) -> bool {
+    match window {
+        Some(window) => xlib_window(window).is_some(),
+        None => env::var_os("WAYLAND_DISPLAY").is_none() && with_xlib(|_| Some(())).is_some(),
+    }
+}
+
+#[cfg(any(windows, target_os = "macos"))]
+pub fn can_grab_cursor(_window: Option<&Window>) -> bool {
+    true
+}
+
+#[cfg(not(any(target_os = "linux", windows, target_os = "macos")))]
+pub fn can_grab_cursor(_window: Option<&Window>) -> bool {
+    false
+}
+
+// confines the cursor to the window or releases it, returns false if it couldn't be confined.
+// X11 grabs the pointer on minifb's own connection so minifb keeps getting the events, grabs
+// fail until the window is shown
+#[cfg(target_os = "linux")]
+pub fn confine_cursor(window: &Window, confine: bool) -> bool {
+    with_xlib(|xlib| unsafe {
+        let (display, handle) = xlib_window(window)?;
+        let confined = if confine {
+            let mask = xlib::ButtonPressMask | xlib::ButtonReleaseMask | xlib::PointerMotionMask;
+            (xlib.XGrabPointer)(display, handle, xlib::True, mask as u32, xlib::GrabModeAsync,
+                                xlib::GrabModeAsync, handle, 0, xlib::CurrentTime) == xlib::GrabSuccess
+        } else {
+            (xlib.XUngrabPointer)(display, xlib::CurrentTime);
+            true
+        };
+        (xlib.XFlush)(display);
+        Some(confined)
+    }).unwrap_or(false)
+}
+
+// moves the cursor to a position in window pixels, from is where it is now
+#[cfg(target_os = "linux")]
+pub fn warp_cursor(window: &Window, _from: (f32, f32), to: (f32, f32)) -> bool {
+    with_xlib(|xlib| unsafe {
+        let (display, handle) = xlib_window(window)?;
+        (xlib.XWarpPointer)(display, 0, handle, 0, 0, 0, 0, to.0 as i32, to.1 as i32);
+        (xlib.XFlush)(display);
+        Some(true)
+    }).unwrap_or(false)
+}
+
+#[cfg(windows)]
+pub fn hwnd(window: &Window) -> Option<winapi::shared::windef::HWND> {
+    use raw_window_handle::{HasRawWindowHandle, RawWindowHandle};
+
+    match window.raw_window_handle() {
+        RawWindowHandle::Windows(handle) if !handle.hwnd.is_null() => Some(handle.hwnd as _),
+        _ => None,
+    }
+}
+
+// clips the cursor to the client area of the window, windows drops the clip when the window
+// loses focus
+#[cfg(windows)]
+pub fn confine_cursor(window: &Window, confine: bool) -> bool {
+    use std::{mem, ptr};
+    use winapi::{shared::windef::{POINT, RECT}, um::winuser::*};
+
+    let hwnd = match hwnd(window) {
+        Some(hwnd) => hwnd,
+        None => return false,
+    };
+    unsafe {
+        if !confine {
+            return ClipCursor(ptr::null()) != 0;
+        }
+        let mut rect: RECT = mem::zeroed();
+        if GetClientRect(hwnd, &mut rect) == 0 { return false; }
+        MapWindowPoints(hwnd, ptr::null_mut(), &mut rect as *mut RECT as *mut POINT, 2);
+        ClipCursor(&rect) != 0
+    }
+}
+
+#[cfg(windows)]
+pub fn warp_cursor(window: &Window, _from: (f32, f32), to: (f32, f32)) -> bool {
+    use winapi::{shared::windef::POINT, um::winuser::{ClientToScreen, SetCursorPos}};
+
+    let hwnd = match hwnd(window) {
+        Some(hwnd) => hwnd,
+        None => return false,
+    };
+    let mut point = POINT { x: to.0 as i32, y: to.1 as i32 };
+    unsafe { ClientToScreen(hwnd, &mut point) != 0 && SetCursorPos(point.x, point.y) != 0 }
+}
+
+// macos can't confine the cursor, it's warped back into the window when it leaves instead
+#[cfg(target_os = "macos")]
+pub fn confine_cursor(_window: &Window, _confine: bool) -> bool {
+    true
+}
+
+// the window's place on the desktop isn't known, the distance from where the cursor is now is
+// added to its position on the desktop instead
+#[cfg(target_os = "macos")]
+pub fn warp_cursor(_window: &Window, from: (f32, f32), to: (f32, f32)) -> bool {
+    unsafe {
+        let event = cg::CGEventCreate(std::ptr::null());
+        if event.is_null() { return false; }
+        let location = cg::CGEventGetLocation(event);
+        cg::CFRelease(event);
+        let point = cg::CGPoint { x: location.x + (to.0 - from.0) as f64, y: location.y + (to.1 - from.1) as f64 };
+        // warping stops the cursor for a moment unless it's attached to the mouse again
+        cg::CGWarpMouseCursorPosition(point) == 0 && cg::CGAssociateMouseAndMouseCursorPosition(1) == 0
+    }
+}
+
+#[cfg(not(any(target_os = "linux", windows, target_os = "macos")))]
+pub fn confine_cursor(_window: &Window, _confine: bool) -> bool {
+    false
+}
+
+#[cfg(not(any(target_os = "linux", windows, target_os = "macos")))]
+pub fn warp_cursor(_window: &Window, _from: (f32, f32), _to: (f32, f32)) -> bool {
+    false
+}
+
+// gets the keys that are down by their position from the keymap of the X server, which holds
+// every key and not only those the window got events for. None when minifb's keys have to do,
+// wayland has no way to ask
+#[cfg(target_os = "linux")]
+pub fn physical_keys(window: &Window) -> Option<Vec<Key>> {
+    with_xlib(|xlib| unsafe {
+        let (display, _) = xlib_window(window)?;
+        let mut keymap = [0; 32];
+        (xlib.XQueryKeymap)(display, keymap.as_mut_ptr());
+        let down = |code: u8| {
+            // X11 keycodes are the evdev codes of linux plus 8
+            let code = code as usize + 8;
+            keymap[code / 8] as u8 & (1 << (code % 8)) != 0
+        };
+        Some(KEYS.iter().copied().filter(|&key| evdev_code(key).is_some_and(down)).collect())
+    })
+}
+
+// minifb's keys already are positions on windows and macos
+#[cfg(not(target_os = "linux"))]
+pub fn physical_keys(_window: &Window) -> Option<Vec<Key>> {
+    None
+}
+
+// gets the linux input code of the key in the position of the key on the US layout
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn evdev_code(key: Key) -> Option<u8> {
+    let code = match key {
+        Key::Escape => 1,
+        Key::Key1 => 2,
+        Key::Key2 => 3,
+        Key::Key3 => 4,
+        Key::Key4 => 5,
+        Key::Key5 => 6,
+        Key::Key6 => 7,
+        Key::Key7 => 8,
+        Key::Key8 => 9,
+        Key::Key9 => 10,
+        Key::Key0 => 11,
+        Key::Minus => 12,
+        Key::Equal => 13,
+        Key::Backspace => 14,
+        Key::Tab => 15,
+        Key::Q => 16,
+        Key::W => 17,
+        Key::E => 18,
+        Key::R => 19,
+        Key::T => 20,
+        Key::Y => 21,
+        Key::U => 22,
+        Key::I => 23,
+        Key::O => 24,
+        Key::P => 25,
+        Key::LeftBracket => 26,
+        Key::RightBracket => 27,
+        Key::Enter => 28,
+        Key::LeftCtrl => 29,
+        Key::A => 30,
+        Key::S => 31,
+        Key::D => 32,
+        Key::F => 33,
+        Key::G => 34,
+        Key::H => 35,
+        Key::J => 36,
+        Key::K => 37,
+        Key::L => 38,
+        Key::Semicolon => 39,
+        Key::Apostrophe => 40,
+        Key::Backquote => 41,
+        Key::LeftShift => 42,
+        Key::Backslash => 43,
+        Key::Z => 44,
+        Key::X => 45,
+        Key::C => 46,
+        Key::V => 47,
+        Key::B => 48,
+        Key::N => 49,
+        Key::M => 50,
+        Key::Comma => 51,
+        Key::Period => 52,
+        Key::Slash => 53,
+        Key::RightShift => 54,
+        Key::NumPadAsterisk => 55,
+        Key::LeftAlt => 56,
+        Key::Space => 57,
+        Key::CapsLock => 58,
+        Key::F1 => 59,
+        Key::F2 => 60,
+        Key::F3 => 61,
+        Key::F4 => 62,
+        Key::F5 => 63,
+        Key::F6 => 64,
+        Key::F7 => 65,
+        Key::F8 => 66,
+        Key::F9 => 67,
+        Key::F10 => 68,
+        Key::NumLock => 69,
+        Key::ScrollLock => 70,
+        Key::NumPad7 => 71,
+        Key::NumPad8 => 72,
+        Key::NumPad9 => 73,
+        Key::NumPadMinus => 74,
+        Key::NumPad4 => 75,
+        Key::NumPad5 => 76,
+        Key::NumPad6 => 77,
+        Key::NumPadPlus => 78,
+        Key::NumPad1 => 79,
+        Key::NumPad2 => 80,
+        Key::NumPad3 => 81,
+        Key::NumPad0 => 82,
+        Key::NumPadDot => 83,
+        Key::F11 => 87,
+        Key::F12 => 88,
+        Key::NumPadEnter => 96,
+        Key::RightCtrl => 97,
+        Key::NumPadSlash => 98,
+        Key::RightAlt => 100,
+        Key::Home => 102,
+        Key::Up => 103,
+        Key::PageUp => 104,
+        Key::Left => 105,
+        Key::Right => 106,
+        Key::End => 107,
+        Key::Down => 108,
+        Key::PageDown => 109,
+        Key::Insert => 110,
+        Key::Delete => 111,
+        Key::Pause => 119,
+        Key::LeftSuper => 125,
+        Key::RightSuper => 126,
+        Key::Menu => 127,
+        Key::F13 => 183,
+        Key::F14 => 184,
+        Key::F15 => 185,
+        _ => return None,
+    };
+    Some(code)
+}
+
+// reads the dpi from a line like "Xft.dpi:\t144" in the X11 resources
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn parse_xft_dpi(output: &str) -> Option<f32> {
+    let dpi = output.lines().find_map(|line| line.strip_prefix("Xft.dpi:"))?.trim().parse::<f32>().ok()?;
+    Some(dpi).filter(|&dpi| dpi > 0.0)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_options() {
+        let options = WindowConfig::default().options();
+        assert!(options.resize && options.title && !options.borderless && !options.topmost);
+
+        let config = WindowConfig { resizable: false, borderless: true, topmost: true, scale: WindowScale::X2,
+                                    native_resolution: true };
+        let options = config.options();
+        assert!(!options.resize && !options.title && options.borderless && options.topmost);
+        assert!(matches!(options.scale, WindowScale::X2));
+    }
+
+    #[test]
+    fn test_evdev_code() {
+        assert_eq!(evdev_code(Key::W), Some(17));
+        assert_eq!(evdev_code(Key::A), Some(30));
+        assert_eq!(evdev_code(Key::Unknown), None);
+
+        // every key that can be bound has its own code
+        let mut codes: Vec<_> = crate::core::input::KEYS.iter().map(|&key| evdev_code(key).unwrap()).collect();
+        codes.sort_unstable();
+        codes.dedup();
+        assert_eq!(codes.len(), crate::core::input::KEYS.len());
+    }
+
+    #[test]
+    fn test_parse_xft_dpi() {
+        assert_eq!(parse_xft_dpi("Xcursor.size:\t24\nXft.dpi:\t144\n"), Some(144.0));
+        assert_eq!(parse_xft_dpi("Xcursor.size:\t24\n"), None);
+        assert_eq!(parse_xft_dpi("Xft.dpi:\t0"), None);
+    }
+}